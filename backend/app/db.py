@@ -296,6 +296,23 @@ class DatabaseManager:
             logger.error(f"Error getting meeting: {str(e)}")
             raise
 
+    async def replace_meeting_transcripts(self, meeting_id: str, transcripts: list):
+        """Replace all transcript segments of a meeting, keeping the meeting itself"""
+        now = datetime.utcnow().isoformat()
+        async with self._get_connection() as conn:
+            await conn.execute("DELETE FROM transcripts WHERE meeting_id = ?", (meeting_id,))
+            await conn.executemany("""
+                INSERT INTO transcripts (
+                    meeting_id, transcript, timestamp, summary, action_items, key_points
+                ) VALUES (?, ?, ?, '', '', '')
+            """, [(meeting_id, t['text'], t['timestamp']) for t in transcripts])
+            await conn.execute("""
+                UPDATE meetings
+                SET updated_at = ?
+                WHERE id = ?
+            """, (now, meeting_id))
+            await conn.commit()
+
     async def update_meeting_title(self, meeting_id: str, new_title: str):
         """Update a meeting's title"""
         now = datetime.utcnow().isoformat()
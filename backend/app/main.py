@@ -87,6 +87,10 @@ class SaveTranscriptRequest(BaseModel):
     meeting_title: str
     transcripts: List[Transcript]
 
+class UpdateTranscriptsRequest(BaseModel):
+    meeting_id: str
+    transcripts: List[Transcript]
+
 class SaveModelConfigRequest(BaseModel):
     provider: str
     model: str
@@ -197,6 +201,19 @@ async def save_meeting_title(data: MeetingTitleUpdate):
         logger.error(f"Error saving meeting title: {str(e)}", exc_info=True)
         raise HTTPException(status_code=500, detail=str(e))
 
+@app.post("/update-transcripts")
+async def update_transcripts(data: UpdateTranscriptsRequest):
+    """Replace the transcript segments of an existing meeting"""
+    try:
+        await db.replace_meeting_transcripts(
+            data.meeting_id,
+            [{'text': t.text, 'timestamp': t.timestamp} for t in data.transcripts]
+        )
+        return {"message": "Transcripts updated successfully"}
+    except Exception as e:
+        logger.error(f"Error updating transcripts: {str(e)}", exc_info=True)
+        raise HTTPException(status_code=500, detail=str(e))
+
 @app.post("/delete-meeting")
 async def delete_meeting(data: DeleteMeetingRequest):
     """Delete a meeting and all its associated data"""
@@ -0,0 +1,22 @@
+-- Sample transcript plugin: expands common meeting acronyms.
+--
+-- Globals available to plugins:
+--   transcript     the full transcript text (read/write)
+--   meeting_title  the meeting title (read-only)
+--   replace(from, to)  replaces every occurrence of `from` in the transcript
+
+local acronyms = {
+    ["EOD"] = "end of day",
+    ["ETA"] = "estimated time of arrival",
+    ["OKR"] = "objectives and key results",
+    ["PTO"] = "paid time off",
+}
+
+for short, long in pairs(acronyms) do
+    replace(short, short .. " (" .. long .. ")")
+end
+
+-- Plugins may also assign to `transcript` directly
+if meeting_title ~= "" then
+    transcript = "# " .. meeting_title .. "\n\n" .. transcript
+end
@@ -0,0 +1,607 @@
+// Local usage analytics, persisted as newline-delimited JSON in the app data directory
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use log::{info as log_info, error as log_error, warn as log_warn};
+use once_cell::sync::OnceCell;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{http_client, store};
+
+const EVENT_QUEUE_FILE: &str = "analytics_events.jsonl";
+const POSTHOG_BATCH_URL: &str = "https://us.i.posthog.com/batch";
+const BATCH_CONFIG_KEY: &str = "analyticsBatchConfig";
+const PAUSED_KEY: &str = "analyticsPaused";
+const OPT_OUT_KEY: &str = "analyticsOptOut";
+
+static EVENT_QUEUE: OnceCell<EventQueue> = OnceCell::new();
+// Only set when a PostHog key is configured; local events are recorded either way
+static ANALYTICS_CLIENT: OnceCell<AnalyticsClient> = OnceCell::new();
+// While set, events keep queueing but nothing is sent to PostHog
+static ANALYTICS_PAUSED: AtomicBool = AtomicBool::new(false);
+// Unlike pausing, opting out stops recording events at all, locally included
+static ANALYTICS_OPTED_OUT: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsEvent {
+    pub event: String,
+    pub user_id: String,
+    pub timestamp: String,
+    pub properties: HashMap<String, String>,
+}
+
+pub struct EventQueue {
+    path: PathBuf,
+    user_id: String,
+    // Serializes appends and rewrites of the queue file
+    lock: Mutex<()>,
+}
+
+impl EventQueue {
+    pub fn new(path: PathBuf, user_id: String) -> Self {
+        Self { path, user_id, lock: Mutex::new(()) }
+    }
+
+    pub fn push(&self, event: &AnalyticsEvent) -> Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(event)?)?;
+        Ok(())
+    }
+
+    pub fn load(&self) -> Result<Vec<AnalyticsEvent>> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        self.read_events()
+    }
+
+    /// Keeps only the events matching `keep` and returns how many were removed.
+    pub fn retain<F: Fn(&AnalyticsEvent) -> bool>(&self, keep: F) -> Result<usize> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let events = self.read_events()?;
+        let total = events.len();
+        let kept: Vec<_> = events.into_iter().filter(|e| keep(e)).collect();
+
+        let mut contents = String::new();
+        for event in &kept {
+            contents.push_str(&serde_json::to_string(event)?);
+            contents.push('\n');
+        }
+        fs::write(&self.path, contents)?;
+
+        Ok(total - kept.len())
+    }
+
+    fn read_events(&self) -> Result<Vec<AnalyticsEvent>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let reader = BufReader::new(fs::File::open(&self.path)?);
+        let mut events = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<AnalyticsEvent>(&line) {
+                Ok(event) => events.push(event),
+                Err(e) => log_warn!("Skipping malformed analytics event: {}", e),
+            }
+        }
+        Ok(events)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsBatchConfig {
+    pub batch_size: usize,
+    pub flush_interval_secs: u32,
+    // Oldest events are dropped past this while PostHog is unreachable
+    pub max_queue_size: usize,
+}
+
+impl Default for AnalyticsBatchConfig {
+    fn default() -> Self {
+        Self { batch_size: 20, flush_interval_secs: 60, max_queue_size: 1000 }
+    }
+}
+
+enum ClientMessage {
+    Event(AnalyticsEvent),
+    Configure(AnalyticsBatchConfig),
+    Flush(oneshot::Sender<Result<usize, String>>),
+    // Last send before opting out; the queue is emptied whether or not it goes out
+    OptOut(oneshot::Sender<Result<usize, String>>),
+}
+
+/// Sends events to PostHog in batches from a background task.
+pub struct AnalyticsClient {
+    sender: mpsc::UnboundedSender<ClientMessage>,
+    // The client lives in a OnceCell, so this is toggled through a shared reference
+    enabled: AtomicBool,
+}
+
+impl AnalyticsClient {
+    pub fn new(api_key: String, config: AnalyticsBatchConfig) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tauri::async_runtime::spawn(run_batcher(Batcher::new(api_key, POSTHOG_BATCH_URL), config, receiver));
+        Self { sender, enabled: AtomicBool::new(true) }
+    }
+
+    /// Events captured while disabled are dropped; ones already queued are still sent.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    pub fn capture(&self, event: AnalyticsEvent) {
+        if !self.enabled.load(Ordering::SeqCst) {
+            return;
+        }
+        let _ = self.sender.send(ClientMessage::Event(event));
+    }
+
+    pub fn configure(&self, config: AnalyticsBatchConfig) {
+        let _ = self.sender.send(ClientMessage::Configure(config));
+    }
+
+    pub async fn flush(&self) -> Result<usize, String> {
+        self.request(ClientMessage::Flush).await
+    }
+
+    /// Sends what is queued unless analytics is paused, then discards the queue either way.
+    pub async fn opt_out(&self) -> Result<usize, String> {
+        self.request(ClientMessage::OptOut).await
+    }
+
+    async fn request(
+        &self,
+        message: fn(oneshot::Sender<Result<usize, String>>) -> ClientMessage,
+    ) -> Result<usize, String> {
+        let (reply, response) = oneshot::channel();
+        self.sender
+            .send(message(reply))
+            .map_err(|_| "Analytics batcher is not running".to_string())?;
+        response.await.map_err(|_| "Analytics batcher stopped".to_string())?
+    }
+}
+
+fn flush_interval(config: &AnalyticsBatchConfig) -> tokio::time::Interval {
+    let period = Duration::from_secs(config.flush_interval_secs.max(1) as u64);
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    interval
+}
+
+// Queue and delivery state owned by the background task
+struct Batcher {
+    api_key: String,
+    url: String,
+    pending: Vec<AnalyticsEvent>,
+    // Set while PostHog is failing; retries then wait for the interval tick instead of every new event
+    last_failed_at: Option<Instant>,
+}
+
+impl Batcher {
+    fn new(api_key: String, url: &str) -> Self {
+        Self { api_key, url: url.to_string(), pending: Vec::new(), last_failed_at: None }
+    }
+
+    /// Queues `event` and reports whether a full batch should be sent right away.
+    fn push(&mut self, event: AnalyticsEvent, config: &AnalyticsBatchConfig) -> bool {
+        self.pending.push(event);
+        if self.pending.len() > config.max_queue_size {
+            let overflow = self.pending.len() - config.max_queue_size;
+            self.pending.drain(..overflow);
+        }
+        self.pending.len() >= config.batch_size && self.last_failed_at.is_none()
+    }
+
+    // Events stay queued when the request fails so the next tick retries them
+    async fn send(&mut self, paused: bool) -> Result<usize, String> {
+        if self.pending.is_empty() || paused {
+            return Ok(0);
+        }
+
+        match post_batch(&self.url, &self.api_key, &self.pending).await {
+            Ok(()) => {
+                if let Some(failed_at) = self.last_failed_at.take() {
+                    log_info!("Analytics delivery recovered after {}s", failed_at.elapsed().as_secs());
+                }
+                let sent = self.pending.len();
+                self.pending.clear();
+                Ok(sent)
+            }
+            Err(e) => {
+                self.last_failed_at.get_or_insert_with(Instant::now);
+                Err(e)
+            }
+        }
+    }
+
+    // Paused means nothing leaves the machine, so opting out while paused drops the queue unsent
+    async fn send_final(&mut self, paused: bool) -> Result<usize, String> {
+        let result = self.send(paused).await;
+        if !self.pending.is_empty() {
+            log_info!("Discarding {} unsent analytics events after opt-out", self.pending.len());
+            self.pending.clear();
+        }
+        result
+    }
+}
+
+async fn run_batcher(
+    mut batcher: Batcher,
+    mut config: AnalyticsBatchConfig,
+    mut receiver: mpsc::UnboundedReceiver<ClientMessage>,
+) {
+    let mut interval = flush_interval(&config);
+    let paused = || ANALYTICS_PAUSED.load(Ordering::SeqCst);
+
+    loop {
+        tokio::select! {
+            message = receiver.recv() => match message {
+                Some(ClientMessage::Event(event)) => {
+                    if batcher.push(event, &config) {
+                        let _ = batcher.send(paused()).await;
+                    }
+                }
+                Some(ClientMessage::Configure(new_config)) => {
+                    interval = flush_interval(&new_config);
+                    config = new_config;
+                }
+                Some(ClientMessage::Flush(reply)) => {
+                    let _ = reply.send(batcher.send(paused()).await);
+                }
+                Some(ClientMessage::OptOut(reply)) => {
+                    let _ = reply.send(batcher.send_final(paused()).await);
+                }
+                None => {
+                    let _ = batcher.send(paused()).await;
+                    return;
+                }
+            },
+            _ = interval.tick() => {
+                let _ = batcher.send(paused()).await;
+            }
+        }
+    }
+}
+
+async fn post_batch(url: &str, api_key: &str, events: &[AnalyticsEvent]) -> Result<(), String> {
+    let batch: Vec<serde_json::Value> = events
+        .iter()
+        .map(|event| {
+            serde_json::json!({
+                "event": event.event,
+                "distinct_id": event.user_id,
+                "timestamp": event.timestamp,
+                "properties": event.properties,
+            })
+        })
+        .collect();
+
+    let response = http_client::get_client()
+        .post(url)
+        .json(&serde_json::json!({ "api_key": api_key, "batch": batch }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send analytics batch: {}", e))?;
+    if !response.status().is_success() {
+        log_warn!("PostHog rejected analytics batch: {}", response.status());
+        return Err(format!("PostHog returned HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+pub fn init<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+    fs::create_dir_all(&data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    // Anonymous id, generated once and kept in the store
+    let user_id = match store::get_value::<String>(app, "analyticsUserId") {
+        Some(id) => id,
+        None => {
+            let id = format!("{:032x}", rand::thread_rng().gen::<u128>());
+            store::set_value(app, "analyticsUserId", &id)?;
+            id
+        }
+    };
+
+    EVENT_QUEUE
+        .set(EventQueue::new(data_dir.join(EVENT_QUEUE_FILE), user_id))
+        .map_err(|_| "Analytics already initialized".to_string())?;
+    log_info!("Analytics event queue initialized");
+
+    if store::get_value::<bool>(app, PAUSED_KEY).unwrap_or(false) {
+        ANALYTICS_PAUSED.store(true, Ordering::SeqCst);
+        log_info!("Analytics sending is paused");
+    }
+
+    let opted_out = get_analytics_opt_out(app.clone());
+    ANALYTICS_OPTED_OUT.store(opted_out, Ordering::SeqCst);
+    if opted_out {
+        log_info!("User opted out of analytics");
+    }
+
+    if let Some(api_key) = std::env::var("POSTHOG_API_KEY").ok().filter(|key| !key.trim().is_empty()) {
+        let config = store::get_value::<AnalyticsBatchConfig>(app, BATCH_CONFIG_KEY).unwrap_or_default();
+        let client = AnalyticsClient::new(api_key, config);
+        client.set_enabled(!opted_out);
+        let _ = ANALYTICS_CLIENT.set(client);
+        log_info!("Analytics batching to PostHog enabled");
+    }
+    Ok(())
+}
+
+pub fn track_event(event: &str, properties: HashMap<String, String>) {
+    if ANALYTICS_OPTED_OUT.load(Ordering::SeqCst) {
+        return;
+    }
+    let Some(queue) = EVENT_QUEUE.get() else {
+        return;
+    };
+
+    let event = AnalyticsEvent {
+        event: event.to_string(),
+        user_id: queue.user_id.clone(),
+        timestamp: Utc::now().to_rfc3339(),
+        properties,
+    };
+    if let Err(e) = queue.push(&event) {
+        log_error!("Failed to record analytics event {}: {}", event.event, e);
+    }
+    if let Some(client) = ANALYTICS_CLIENT.get() {
+        client.capture(event);
+    }
+}
+
+pub fn track_feature_used(feature: &str) {
+    let mut properties = HashMap::new();
+    properties.insert("feature".to_string(), feature.to_string());
+    track_event("feature_used", properties);
+}
+
+fn event_queue() -> Result<&'static EventQueue, String> {
+    EVENT_QUEUE
+        .get()
+        .ok_or_else(|| "Analytics is not initialized".to_string())
+}
+
+/// All locally queued events, or none if analytics never initialized.
+pub fn local_events() -> Result<Vec<AnalyticsEvent>, String> {
+    match EVENT_QUEUE.get() {
+        Some(queue) => queue.load().map_err(|e| format!("Failed to read analytics events: {}", e)),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date '{}', expected YYYY-MM-DD: {}", value, e))
+}
+
+fn event_date(event: &AnalyticsEvent) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(&event.timestamp)
+        .ok()
+        .map(|t| t.with_timezone(&Utc).date_naive())
+}
+
+#[tauri::command]
+pub async fn export_analytics_events(
+    output_path: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<usize, String> {
+    let start = start_date.as_deref().map(parse_date).transpose()?;
+    let end = end_date.as_deref().map(parse_date).transpose()?;
+
+    let events = event_queue()?
+        .load()
+        .map_err(|e| format!("Failed to read analytics events: {}", e))?;
+
+    let mut contents = String::new();
+    let mut written = 0;
+    for event in events.iter().filter(|e| {
+        let Some(date) = event_date(e) else {
+            return false;
+        };
+        start.map_or(true, |s| date >= s) && end.map_or(true, |end| date <= end)
+    }) {
+        contents.push_str(&serde_json::to_string(event).map_err(|e| e.to_string())?);
+        contents.push('\n');
+        written += 1;
+    }
+
+    if let Some(parent) = std::path::Path::new(&output_path).parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+    }
+    fs::write(&output_path, contents)
+        .map_err(|e| format!("Failed to write analytics export: {}", e))?;
+
+    log_info!("Exported {} analytics events to {}", written, output_path);
+    Ok(written)
+}
+
+#[tauri::command]
+pub fn clear_local_analytics_cache(before_date: Option<String>) -> Result<usize, String> {
+    let before = before_date.as_deref().map(parse_date).transpose()?;
+
+    let removed = event_queue()?
+        .retain(|event| match (before, event_date(event)) {
+            (Some(before), Some(date)) => date >= before,
+            // Without a cutoff everything goes; undated events are always dropped
+            _ => false,
+        })
+        .map_err(|e| format!("Failed to clear analytics events: {}", e))?;
+
+    log_info!("Removed {} local analytics events", removed);
+    Ok(removed)
+}
+
+#[tauri::command]
+pub fn set_analytics_batch_config<R: Runtime>(app: AppHandle<R>, config: AnalyticsBatchConfig) -> Result<(), String> {
+    if config.batch_size == 0 || config.flush_interval_secs == 0 {
+        return Err("Batch size and flush interval must be greater than zero".to_string());
+    }
+    if config.max_queue_size < config.batch_size {
+        return Err("Max queue size must be at least the batch size".to_string());
+    }
+
+    store::set_value(&app, BATCH_CONFIG_KEY, &config)?;
+    if let Some(client) = ANALYTICS_CLIENT.get() {
+        client.configure(config);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn flush_analytics_batch() -> Result<usize, String> {
+    let client = ANALYTICS_CLIENT
+        .get()
+        .ok_or_else(|| "Analytics batching is not enabled".to_string())?;
+    let sent = client.flush().await?;
+    log_info!("Flushed {} analytics events", sent);
+    Ok(sent)
+}
+
+#[tauri::command]
+pub fn get_analytics_opt_out<R: Runtime>(app: AppHandle<R>) -> bool {
+    store::get_value::<bool>(&app, OPT_OUT_KEY).unwrap_or(false)
+}
+
+/// Persists the choice. Opting out records one last event and sends PostHog `$opt_out`
+/// so the server side knows too, unless sending is paused; either way the PostHog queue
+/// is emptied and nothing is tracked after that.
+#[tauri::command]
+pub fn set_analytics_opt_out<R: Runtime>(app: AppHandle<R>, opt_out: bool) -> Result<(), String> {
+    store::set_value(&app, OPT_OUT_KEY, &opt_out)?;
+    if opt_out == ANALYTICS_OPTED_OUT.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    if opt_out {
+        track_event("analytics_opted_out", HashMap::new());
+        track_event("$opt_out", HashMap::new());
+        ANALYTICS_OPTED_OUT.store(true, Ordering::SeqCst);
+        if let Some(client) = ANALYTICS_CLIENT.get() {
+            client.set_enabled(false);
+            // Deliver the two final events now rather than leaving them for a flush that may never come
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = client.opt_out().await {
+                    log_warn!("Failed to send analytics opt-out: {}", e);
+                }
+            });
+        }
+        log_info!("User opted out of analytics");
+    } else {
+        ANALYTICS_OPTED_OUT.store(false, Ordering::SeqCst);
+        if let Some(client) = ANALYTICS_CLIENT.get() {
+            client.set_enabled(true);
+        }
+        log_info!("User opted back in to analytics");
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn pause_analytics<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    ANALYTICS_PAUSED.store(true, Ordering::SeqCst);
+    store::set_value(&app, PAUSED_KEY, &true)?;
+    log_info!("Analytics sending paused");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn resume_analytics<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    ANALYTICS_PAUSED.store(false, Ordering::SeqCst);
+    store::set_value(&app, PAUSED_KEY, &false)?;
+    log_info!("Analytics sending resumed");
+
+    // Send whatever queued up while paused instead of waiting for the next interval
+    if let Some(client) = ANALYTICS_CLIENT.get() {
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = client.flush().await {
+                log_warn!("Failed to flush analytics after resuming: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn is_analytics_paused() -> bool {
+    ANALYTICS_PAUSED.load(Ordering::SeqCst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Nothing listens on the discard port, so every send fails fast
+    const UNREACHABLE_URL: &str = "http://127.0.0.1:9/batch";
+
+    fn event(name: &str) -> AnalyticsEvent {
+        AnalyticsEvent {
+            event: name.to_string(),
+            user_id: "test-user".to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            properties: HashMap::new(),
+        }
+    }
+
+    fn config(batch_size: usize) -> AnalyticsBatchConfig {
+        AnalyticsBatchConfig { batch_size, flush_interval_secs: 60, max_queue_size: 100 }
+    }
+
+    #[tokio::test]
+    async fn failed_batch_waits_for_the_tick_instead_of_resending_per_event() {
+        let mut batcher = Batcher::new("key".to_string(), UNREACHABLE_URL);
+        let config = config(2);
+
+        assert!(!batcher.push(event("a"), &config));
+        assert!(batcher.push(event("b"), &config));
+        assert!(batcher.send(false).await.is_err());
+        assert_eq!(batcher.pending.len(), 2);
+        assert!(batcher.last_failed_at.is_some());
+
+        assert!(!batcher.push(event("c"), &config));
+        assert!(!batcher.push(event("d"), &config));
+        assert_eq!(batcher.pending.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn opt_out_while_paused_discards_without_sending() {
+        let mut batcher = Batcher::new("key".to_string(), UNREACHABLE_URL);
+        batcher.push(event("analytics_opted_out"), &config(10));
+        batcher.push(event("$opt_out"), &config(10));
+
+        // A request would fail against the unreachable URL, so Ok(0) means none was made
+        assert_eq!(batcher.send_final(true).await, Ok(0));
+        assert!(batcher.pending.is_empty());
+    }
+
+    #[tokio::test]
+    async fn opt_out_clears_the_queue_when_the_send_fails() {
+        let mut batcher = Batcher::new("key".to_string(), UNREACHABLE_URL);
+        batcher.push(event("$opt_out"), &config(10));
+
+        assert!(batcher.send_final(false).await.is_err());
+        assert!(batcher.pending.is_empty());
+    }
+}
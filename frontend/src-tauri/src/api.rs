@@ -0,0 +1,3328 @@
+// Meeting data lives in the Python backend; these commands proxy its HTTP API
+use chrono::{DateTime, NaiveDateTime, Timelike, Datelike};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use log::{info as log_info, error as log_error};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::audio::codec::{self, AudioFormat, FlacCompressionLevel};
+use crate::audio::decoder::{decode_audio_file_ffmpeg, decode_audio_file_ffmpeg_interleaved, is_ffmpeg_available};
+use crate::audio::podcast;
+use crate::audio::AudioTranscriptionEngine;
+use crate::audio::audio_processing::{
+    audio_to_mono, average_noise_spectrum, current_downmix_strategy, read_wav_file, read_wav_frames, read_wav_range, resample,
+    rms_and_peak, spectral_subtraction, trim_silence, write_wav_file, SPECTRAL_WINDOW_SIZE,
+};
+use crate::storage::archive::{self, ArchivedMeeting};
+use crate::transcript::{self, AggregatedTranscript, TranscriptCoverage, TranscriptParagraph};
+use crate::jira_integration::{self, JiraConfig};
+use crate::{analytics, deepgram, groq, llm, notion_integration, ollama, slack_integration, storage, store, templates, TranscriptUpdate};
+
+pub const BACKEND_URL: &str = "http://localhost:5167";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Transcript {
+    pub id: String,
+    pub text: String,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MeetingSummary {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MeetingDetails {
+    pub id: String,
+    pub title: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub transcripts: Vec<Transcript>,
+}
+
+#[derive(Debug, Serialize)]
+struct SaveTranscriptRequest {
+    meeting_title: String,
+    transcripts: Vec<Transcript>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SaveTranscriptResponse {
+    meeting_id: String,
+}
+
+pub async fn fetch_meetings() -> Result<Vec<MeetingSummary>, String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("{}/get-meetings", BACKEND_URL))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach backend: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to load meetings: HTTP {}", response.status()));
+    }
+
+    response
+        .json::<Vec<MeetingSummary>>()
+        .await
+        .map_err(|e| format!("Failed to parse meetings response: {}", e))
+}
+
+pub async fn fetch_meeting(meeting_id: &str) -> Result<MeetingDetails, String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("{}/get-meeting/{}", BACKEND_URL, meeting_id))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach backend: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to load meeting {}: HTTP {}", meeting_id, response.status()));
+    }
+
+    response
+        .json::<MeetingDetails>()
+        .await
+        .map_err(|e| format!("Failed to parse meeting response: {}", e))
+}
+
+// Returns the summary JSON for a meeting, or None if no completed summary exists
+pub async fn fetch_summary(meeting_id: &str) -> Result<Option<serde_json::Value>, String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("{}/get-summary/{}", BACKEND_URL, meeting_id))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach backend: {}", e))?;
+
+    if response.status() != reqwest::StatusCode::OK {
+        return Ok(None);
+    }
+
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse summary response: {}", e))?;
+    Ok(body.get("data").filter(|data| !data.is_null()).cloned())
+}
+
+// Provider/model the user picked in settings
+#[derive(Debug, Deserialize, Clone)]
+pub struct ModelConfig {
+    pub provider: String,
+    pub model: String,
+}
+
+pub async fn fetch_model_config() -> Result<ModelConfig, String> {
+    let response = reqwest::get(format!("{}/get-model-config", BACKEND_URL))
+        .await
+        .map_err(|e| format!("Failed to reach backend: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to fetch model config: HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse model config: {}", e))
+}
+
+// The backend stores naive UTC timestamps from Python's isoformat()
+pub fn parse_created_at(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f"))
+        .ok()
+        .or_else(|| DateTime::parse_from_rfc3339(value).ok().map(|t| t.naive_utc()))
+}
+
+// Transcript timestamps are stored as "t0 - t1" in seconds from recording start
+pub fn parse_timestamp_range(timestamp: &str) -> Option<(f32, f32)> {
+    let (start, end) = timestamp.split_once('-')?;
+    Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
+}
+
+/// Strict counterpart of `parse_timestamp_range` for callers that must not skip bad segments.
+pub fn parse_timestamp_seconds(timestamp: &str) -> anyhow::Result<(f32, f32)> {
+    let (start, end) = timestamp
+        .split_once('-')
+        .ok_or_else(|| anyhow::anyhow!("Timestamp {:?} is not in \"t0 - t1\" form", timestamp))?;
+    let parse = |value: &str, name: &str| -> anyhow::Result<f32> {
+        let value = value.trim();
+        anyhow::ensure!(!value.is_empty(), "Timestamp {:?} is missing {}", timestamp, name);
+        let seconds: f32 = value
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid {} {:?} in timestamp {:?}: {}", name, value, timestamp, e))?;
+        anyhow::ensure!(seconds.is_finite() && seconds >= 0.0, "{} in timestamp {:?} is out of range", name, timestamp);
+        Ok(seconds)
+    };
+    let (start, end) = (parse(start, "t0")?, parse(end, "t1")?);
+    anyhow::ensure!(end >= start, "Timestamp {:?} ends before it starts", timestamp);
+    Ok((start, end))
+}
+
+// Joins the stored transcript segments into a single block of text
+pub fn transcript_text(meeting: &MeetingDetails) -> String {
+    meeting
+        .transcripts
+        .iter()
+        .map(|t| t.text.trim())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Set by start_recording; the next transcript saved from the UI is that recording
+pub const PENDING_RECORDING_START_KEY: &str = "pendingRecordingStartedAt";
+
+fn recording_start_key(meeting_id: &str) -> String {
+    format!("recordingStartedAt_{}", meeting_id)
+}
+
+#[tauri::command]
+pub async fn api_save_transcript<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_title: String,
+    transcripts: Vec<Transcript>,
+) -> Result<String, String> {
+    let meeting_id = save_transcript(meeting_title, transcripts).await?;
+    // The backend only records when the meeting was saved, to the second
+    if let Some(started_at) = store::get_value::<String>(&app, PENDING_RECORDING_START_KEY) {
+        store::set_value(&app, &recording_start_key(&meeting_id), &started_at)?;
+        store::delete_value(&app, PENDING_RECORDING_START_KEY)?;
+    }
+    crate::transcript_store::claim_pending(&app, &meeting_id);
+    Ok(meeting_id)
+}
+
+pub async fn save_transcript(meeting_title: String, transcripts: Vec<Transcript>) -> Result<String, String> {
+    log_info!("Saving {} transcript segments for meeting: {}", transcripts.len(), meeting_title);
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/save-transcript", BACKEND_URL))
+        .json(&SaveTranscriptRequest { meeting_title, transcripts })
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach backend: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        log_error!("Failed to save transcript: {} {}", status, body);
+        return Err(format!("Failed to save transcript: HTTP {}", status));
+    }
+
+    let saved: SaveTranscriptResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse save response: {}", e))?;
+    invalidate_dashboard_cache();
+    Ok(saved.meeting_id)
+}
+
+#[tauri::command]
+pub async fn api_delete_meeting<R: Runtime>(app: AppHandle<R>, meeting_id: String) -> Result<(), String> {
+    delete_meeting(&app, &meeting_id).await.map(|_| ())
+}
+
+// Deletes the meeting from the backend along with its local recordings; returns the bytes freed on disk
+async fn delete_meeting<R: Runtime>(app: &AppHandle<R>, meeting_id: &str) -> Result<u64, String> {
+    log_info!("Deleting meeting: {}", meeting_id);
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/delete-meeting", BACKEND_URL))
+        .json(&serde_json::json!({ "meeting_id": meeting_id }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach backend: {}", e))?;
+
+    if !response.status().is_success() {
+        log_error!("Failed to delete meeting {}: {}", meeting_id, response.status());
+        return Err(format!("Failed to delete meeting {}: HTTP {}", meeting_id, response.status()));
+    }
+
+    invalidate_dashboard_cache();
+    Ok(delete_meeting_files(app, meeting_id))
+}
+
+fn delete_meeting_files<R: Runtime>(app: &AppHandle<R>, meeting_id: &str) -> u64 {
+    let mut files = Vec::new();
+    for key in [audio_path_key(meeting_id), format!("denoisedAudioPath_{}", meeting_id)] {
+        if let Some(path) = store::get_value::<String>(app, &key) {
+            files.push(storage::metadata::metadata_path(&path));
+            files.push(path);
+        }
+        if let Err(e) = store::delete_value(app, &key) {
+            log_error!("Failed to remove {} from store: {}", key, e);
+        }
+    }
+
+    for key in [archive::archive_key(meeting_id), recording_start_key(meeting_id)] {
+        if let Err(e) = store::delete_value(app, &key) {
+            log_error!("Failed to remove {} from store: {}", key, e);
+        }
+    }
+
+    let mut freed = 0;
+    for file in files {
+        let Ok(metadata) = std::fs::metadata(&file) else {
+            continue;
+        };
+        match std::fs::remove_file(&file) {
+            Ok(()) => freed += metadata.len(),
+            Err(e) => log_error!("Failed to delete {}: {}", file, e),
+        }
+    }
+    freed
+}
+
+// Overwrites the meeting's transcript segments in place
+pub async fn replace_transcripts(meeting_id: &str, transcripts: Vec<Transcript>) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/update-transcripts", BACKEND_URL))
+        .json(&serde_json::json!({ "meeting_id": meeting_id, "transcripts": transcripts }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach backend: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to update transcripts: HTTP {}", response.status()));
+    }
+    invalidate_dashboard_cache();
+    Ok(())
+}
+
+fn archived_meeting<R: Runtime>(app: &AppHandle<R>, meeting_id: &str) -> Option<ArchivedMeeting> {
+    store::get_value::<ArchivedMeeting>(app, &archive::archive_key(meeting_id)).filter(|archived| archived.archived)
+}
+
+// Moves the recording and its sidecar together; returns the new recording path
+fn move_recording(audio_path: &str, target_dir: &Path) -> Result<String, String> {
+    let file_name = Path::new(audio_path)
+        .file_name()
+        .ok_or_else(|| format!("Invalid recording path: {}", audio_path))?;
+    let target = target_dir.join(file_name);
+    archive::move_file(Path::new(audio_path), &target).map_err(|e| format!("Failed to move recording: {}", e))?;
+
+    let sidecar = storage::metadata::metadata_path(audio_path);
+    let target = target.to_string_lossy().to_string();
+    if Path::new(&sidecar).exists() {
+        if let Err(e) = archive::move_file(Path::new(&sidecar), Path::new(&storage::metadata::metadata_path(&target))) {
+            log_error!("Failed to move recording metadata {}: {}", sidecar, e);
+        }
+    }
+    Ok(target)
+}
+
+/// Compresses the meeting's transcript into the store, clears it from the backend and moves
+/// the recording into `{app_data_dir}/archive/`.
+#[tauri::command]
+pub async fn api_archive_meeting<R: Runtime>(app: AppHandle<R>, meeting_id: String) -> Result<(), String> {
+    if archived_meeting(&app, &meeting_id).is_some() {
+        return Err(format!("Meeting {} is already archived", meeting_id));
+    }
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let mut record = ArchivedMeeting {
+        meeting_id: meeting_id.clone(),
+        title: meeting.title.clone(),
+        created_at: meeting.created_at.clone(),
+        transcript: archive::compress_transcripts(&meeting.transcripts)
+            .map_err(|e| format!("Failed to compress transcript: {}", e))?,
+        archived: true,
+        archived_at: chrono::Utc::now().to_rfc3339(),
+        original_audio_path: None,
+    };
+    let key = archive::archive_key(&meeting_id);
+    store::set_value(&app, &key, &record)?;
+
+    // The compressed copy is saved, so the backend rows can go
+    if let Err(e) = replace_transcripts(&meeting_id, Vec::new()).await {
+        let _ = store::delete_value(&app, &key);
+        return Err(e);
+    }
+
+    if let Some(audio_path) = meeting_audio_path(&app, &meeting_id) {
+        let archive_dir = archive::archive_dir(&app).map_err(|e| e.to_string())?;
+        let archived_path = move_recording(&audio_path, &archive_dir)?;
+        set_meeting_audio_path(&app, &meeting_id, &archived_path)?;
+        record.original_audio_path = Some(audio_path);
+        store::set_value(&app, &key, &record)?;
+    }
+
+    log_info!("Archived meeting {} ({} transcript segments)", meeting_id, meeting.transcripts.len());
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn api_unarchive_meeting<R: Runtime>(app: AppHandle<R>, meeting_id: String) -> Result<(), String> {
+    let record = archived_meeting(&app, &meeting_id)
+        .ok_or_else(|| format!("Meeting {} is not archived", meeting_id))?;
+    let transcripts = archive::decompress_transcripts(&record.transcript)
+        .map_err(|e| format!("Failed to decompress transcript: {}", e))?;
+    replace_transcripts(&meeting_id, transcripts).await?;
+
+    if let (Some(audio_path), Some(original)) = (meeting_audio_path(&app, &meeting_id), &record.original_audio_path) {
+        let original_dir = Path::new(original)
+            .parent()
+            .ok_or_else(|| format!("Invalid recording path: {}", original))?;
+        std::fs::create_dir_all(original_dir).map_err(|e| format!("Failed to recreate {}: {}", original_dir.display(), e))?;
+        let restored = move_recording(&audio_path, original_dir)?;
+        set_meeting_audio_path(&app, &meeting_id, &restored)?;
+    }
+
+    store::delete_value(&app, &archive::archive_key(&meeting_id))?;
+    log_info!("Unarchived meeting {}", meeting_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn api_get_meetings<R: Runtime>(app: AppHandle<R>, include_archived: Option<bool>) -> Result<Vec<MeetingSummary>, String> {
+    let meetings = fetch_meetings().await?;
+    if include_archived.unwrap_or(false) {
+        return Ok(meetings);
+    }
+    Ok(meetings
+        .into_iter()
+        .filter(|meeting| archived_meeting(&app, &meeting.id).is_none())
+        .collect())
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TranscriptSearchResult {
+    pub meeting_id: String,
+    pub title: String,
+    pub transcript_id: String,
+    pub text: String,
+    pub timestamp: String,
+    pub archived: bool,
+}
+
+/// Case-insensitive substring search over every meeting's transcript, archived ones included.
+#[tauri::command]
+pub async fn api_search_transcripts<R: Runtime>(app: AppHandle<R>, query: String) -> Result<Vec<TranscriptSearchResult>, String> {
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut results = Vec::new();
+    for summary in fetch_meetings().await? {
+        let (transcripts, archived) = match archived_meeting(&app, &summary.id) {
+            Some(record) => match archive::decompress_transcripts(&record.transcript) {
+                Ok(transcripts) => (transcripts, true),
+                Err(e) => {
+                    log_error!("Skipping unreadable archive for meeting {}: {}", summary.id, e);
+                    continue;
+                }
+            },
+            None => (fetch_meeting(&summary.id).await?.transcripts, false),
+        };
+        results.extend(
+            transcripts
+                .into_iter()
+                .filter(|t| t.text.to_lowercase().contains(&needle))
+                .map(|t| TranscriptSearchResult {
+                    meeting_id: summary.id.clone(),
+                    title: summary.title.clone(),
+                    transcript_id: t.id,
+                    text: t.text,
+                    timestamp: t.timestamp,
+                    archived,
+                }),
+        );
+    }
+    Ok(results)
+}
+
+#[derive(Debug, Deserialize)]
+struct ProcessTranscriptResponse {
+    process_id: String,
+}
+
+pub const DEFAULT_MODEL_CONTEXT_WINDOW: usize = 4096;
+// Share of the context window a transcript chunk may use; the rest is for the prompt and reply
+const CHUNK_CONTEXT_SHARE: f32 = 0.6;
+const CHUNK_OVERLAP_SENTENCES: usize = 2;
+
+pub fn model_context_window<R: Runtime>(app: &AppHandle<R>) -> usize {
+    store::get_value::<usize>(app, "modelContextWindow")
+        .filter(|tokens| *tokens > 0)
+        .unwrap_or(DEFAULT_MODEL_CONTEXT_WINDOW)
+}
+
+// Condenses each chunk to plain-text notes; the backend then summarizes the combined notes
+async fn summarize_in_chunks(provider: &str, text: &str, context_window: usize) -> Result<String, String> {
+    let max_tokens = (context_window as f32 * CHUNK_CONTEXT_SHARE) as usize;
+    let chunks = transcript::chunk_transcript_for_llm(text, max_tokens, CHUNK_OVERLAP_SENTENCES);
+    log_info!("Transcript exceeds {} tokens, summarizing in {} chunks", context_window, chunks.len());
+
+    let mut notes = Vec::with_capacity(chunks.len());
+    for (i, chunk) in chunks.iter().enumerate() {
+        let prompt = format!(
+            "This is part {} of {} of a meeting transcript. Summarize it as concise notes, keeping every \
+             decision, deadline, action item and owner. Reply with the notes only.\n\n{}",
+            i + 1,
+            chunks.len(),
+            chunk.text
+        );
+        let summary = llm::generate(provider, &prompt)
+            .await
+            .map_err(|e| format!("Failed to summarize transcript part {}: {}", i + 1, e))?;
+        notes.push(format!("Part {}:\n{}", i + 1, summary.trim()));
+    }
+    Ok(notes.join("\n\n"))
+}
+
+/// Starts summary generation in the backend with the configured model; poll `/get-summary` for the result.
+/// In local-only mode this is the same as `api_process_transcript_local`.
+#[tauri::command]
+pub async fn api_process_transcript<R: Runtime>(app: AppHandle<R>, meeting_id: String) -> Result<String, String> {
+    if is_local_only_mode(app.clone()) {
+        return api_process_transcript_local(app, meeting_id).await;
+    }
+    process_transcript(&meeting_id, model_context_window(&app), false).await
+}
+
+/// Summarizes with the local Ollama server only, whatever cloud model is configured.
+#[tauri::command]
+pub async fn api_process_transcript_local<R: Runtime>(app: AppHandle<R>, meeting_id: String) -> Result<String, String> {
+    let process_id = process_transcript(&meeting_id, model_context_window(&app), true).await?;
+    analytics::track_feature_used("local_only_summary");
+    Ok(process_id)
+}
+
+#[tauri::command]
+pub fn is_local_only_mode<R: Runtime>(app: AppHandle<R>) -> bool {
+    store::get_value::<bool>(&app, "localOnlyMode").unwrap_or(false)
+}
+
+// Keeps the user's Ollama model if that is what they picked; never falls back to a cloud provider
+async fn local_model_config() -> Result<ModelConfig, String> {
+    if !ollama::test_ollama_connection(None).await.unwrap_or(false) {
+        return Err("Local model unavailable: start Ollama first".to_string());
+    }
+    let model = fetch_model_config()
+        .await
+        .ok()
+        .filter(|config| config.provider == "ollama" && !config.model.is_empty())
+        .map(|config| config.model)
+        .or_else(|| llm::default_model("ollama").map(str::to_string))
+        .unwrap_or_default();
+    Ok(ModelConfig { provider: "ollama".to_string(), model })
+}
+
+/// Transcripts longer than `context_window` tokens are condensed chunk by chunk first,
+/// and the backend produces the final summary from those notes.
+pub async fn process_transcript(meeting_id: &str, context_window: usize, local_only: bool) -> Result<String, String> {
+    let meeting = fetch_meeting(meeting_id).await?;
+    let mut text = transcript_text(&meeting);
+    if text.is_empty() {
+        return Err(format!("Meeting {} has no transcript to summarize", meeting_id));
+    }
+    let config = if local_only { local_model_config().await? } else { fetch_model_config().await? };
+    if transcript::chunking::estimate_tokens(&text) > context_window {
+        text = summarize_in_chunks(&config.provider, &text, context_window).await?;
+    }
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/process-transcript", BACKEND_URL))
+        .json(&serde_json::json!({
+            "text": text,
+            "model": config.provider,
+            "model_name": config.model,
+            "meeting_id": meeting_id,
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach backend: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to start summary: HTTP {}", response.status()));
+    }
+    let started: ProcessTranscriptResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse process response: {}", e))?;
+    log_info!("Started summary process {} for meeting {}", started.process_id, meeting_id);
+    Ok(started.process_id)
+}
+
+#[tauri::command]
+pub async fn api_save_meeting_title(meeting_id: String, title: String) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/save-meeting-title", BACKEND_URL))
+        .json(&serde_json::json!({ "meeting_id": meeting_id, "title": title }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach backend: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to save meeting title: HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+// Recordings are saved by the frontend; the store maps each meeting to its WAV file
+fn audio_path_key(meeting_id: &str) -> String {
+    format!("audioPath_{}", meeting_id)
+}
+
+pub fn meeting_audio_path<R: Runtime>(app: &AppHandle<R>, meeting_id: &str) -> Option<String> {
+    store::get_value::<String>(app, &audio_path_key(meeting_id))
+        .filter(|path| Path::new(path).exists())
+}
+
+pub fn set_meeting_audio_path<R: Runtime>(app: &AppHandle<R>, meeting_id: &str, audio_path: &str) -> Result<(), String> {
+    store::set_value(app, &audio_path_key(meeting_id), &audio_path.to_string())
+}
+
+// Tag ids attached to a meeting; the backend has no tag table, so they live in the store
+pub fn meeting_tags<R: Runtime>(app: &AppHandle<R>, meeting_id: &str) -> Vec<String> {
+    store::get_value::<Vec<String>>(app, &format!("meetingTags_{}", meeting_id)).unwrap_or_default()
+}
+
+// Display names keyed by tag id, kept in the store next to the per-meeting tag lists
+const TAG_NAMES_KEY: &str = "tagNames";
+
+// Ids without a stored name (e.g. a deleted tag) are shown as the id itself
+fn resolve_tag_names(tag_ids: &[String], names: &HashMap<String, String>) -> Vec<String> {
+    tag_ids
+        .iter()
+        .map(|id| names.get(id).cloned().unwrap_or_else(|| id.clone()))
+        .collect()
+}
+
+pub fn meeting_tag_names<R: Runtime>(app: &AppHandle<R>, meeting_id: &str) -> Vec<String> {
+    let names = store::get_value::<HashMap<String, String>>(app, TAG_NAMES_KEY).unwrap_or_default();
+    resolve_tag_names(&meeting_tags(app, meeting_id), &names)
+}
+
+#[tauri::command]
+pub fn api_link_meeting_audio<R: Runtime>(app: AppHandle<R>, meeting_id: String, audio_path: String) -> Result<(), String> {
+    if !Path::new(&audio_path).exists() {
+        return Err(format!("Audio file not found: {}", audio_path));
+    }
+    set_meeting_audio_path(&app, &meeting_id, &audio_path)
+}
+
+// Rebuilds the store entries for a recording copied over from another machine
+#[tauri::command]
+pub fn api_import_meeting_from_metadata<R: Runtime>(app: AppHandle<R>, mmeta_path: String) -> Result<String, String> {
+    let meta = storage::read_metadata_file(&mmeta_path)
+        .map_err(|e| format!("Failed to import meeting metadata: {}", e))?;
+    if meta.meeting_id.is_empty() {
+        return Err(format!("Metadata file {} has no meeting id", mmeta_path));
+    }
+
+    let audio_path = storage::metadata::recording_path(&mmeta_path)
+        .filter(|path| Path::new(path).exists())
+        .ok_or_else(|| format!("No recording found next to {}", mmeta_path))?;
+
+    store::set_value(&app, &format!("meetingMeta_{}", meta.meeting_id), &meta)?;
+    set_meeting_audio_path(&app, &meta.meeting_id, audio_path)?;
+    log_info!("Imported meeting {} ({}) from {}", meta.meeting_id, meta.title, mmeta_path);
+    Ok(meta.meeting_id)
+}
+
+fn import_title(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(|stem| stem.replace(['_', '-'], " ").trim().to_string())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| "Imported meeting".to_string())
+}
+
+// Stored transcripts have no speaker field, so the speaker is kept as a text prefix
+async fn save_imported_transcript(path: &Path, updates: Vec<TranscriptUpdate>) -> Result<String, String> {
+    if updates.is_empty() {
+        return Err(format!("No transcript lines found in {}", path.display()));
+    }
+    let transcripts = updates
+        .into_iter()
+        .enumerate()
+        .map(|(i, update)| Transcript {
+            id: format!("import-{}", i),
+            text: if update.source.is_empty() { update.text } else { format!("{}: {}", update.source, update.text) },
+            timestamp: update.timestamp,
+        })
+        .collect::<Vec<_>>();
+    log_info!("Importing {} transcript lines from {}", transcripts.len(), path.display());
+    save_transcript(import_title(path), transcripts).await
+}
+
+#[tauri::command]
+pub async fn api_import_teams_transcript(docx_path: String) -> Result<String, String> {
+    let path = PathBuf::from(&docx_path);
+    let paragraphs = tauri::async_runtime::spawn_blocking({
+        let path = path.clone();
+        move || transcript::import::read_docx_paragraphs(&path)
+    })
+    .await
+    .map_err(|e| format!("DOCX import task failed: {}", e))?
+    .map_err(|e| format!("Failed to read Teams transcript: {}", e))?;
+    save_imported_transcript(&path, transcript::import::parse_teams_transcript(&paragraphs)).await
+}
+
+#[tauri::command]
+pub async fn api_import_google_meet_transcript(txt_path: String) -> Result<String, String> {
+    let path = PathBuf::from(&txt_path);
+    let contents = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read Google Meet transcript: {}", e))?;
+    save_imported_transcript(&path, transcript::import::parse_google_meet_transcript(&contents)).await
+}
+
+// Writes the samples before and after `split_time_secs` to two sibling WAV files
+fn split_wav_file(path: &Path, split_time_secs: f32) -> anyhow::Result<(PathBuf, PathBuf)> {
+    let mut reader = hound::WavReader::open(path)?;
+    let spec = reader.spec();
+    let split_at = (split_time_secs.max(0.0) * spec.sample_rate as f32) as usize * spec.channels as usize;
+
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("recording");
+    let before_path = path.with_file_name(format!("{}_part1.wav", stem));
+    let after_path = path.with_file_name(format!("{}_part2.wav", stem));
+
+    let mut before = hound::WavWriter::create(&before_path, spec)?;
+    let mut after = hound::WavWriter::create(&after_path, spec)?;
+    match spec.sample_format {
+        hound::SampleFormat::Float => {
+            for (i, sample) in reader.samples::<f32>().enumerate() {
+                if i < split_at { before.write_sample(sample?)? } else { after.write_sample(sample?)? }
+            }
+        }
+        hound::SampleFormat::Int => {
+            for (i, sample) in reader.samples::<i32>().enumerate() {
+                if i < split_at { before.write_sample(sample?)? } else { after.write_sample(sample?)? }
+            }
+        }
+    }
+    before.finalize()?;
+    after.finalize()?;
+
+    Ok((before_path, after_path))
+}
+
+#[tauri::command]
+pub async fn api_split_meeting<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    split_time_secs: f32,
+    title_before: String,
+    title_after: String,
+    delete_original: bool,
+) -> Result<(String, String), String> {
+    let meeting = fetch_meeting(&meeting_id).await?;
+
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+    for transcript in meeting.transcripts {
+        let Some((t0, t1)) = parse_timestamp_range(&transcript.timestamp) else {
+            log_error!("Skipping transcript {} with unparseable timestamp '{}'", transcript.id, transcript.timestamp);
+            continue;
+        };
+        if t0 < split_time_secs {
+            before.push(transcript);
+        } else {
+            // Rebase so the second half lines up with its own audio file
+            after.push(Transcript {
+                timestamp: format!("{:.1} - {:.1}", t0 - split_time_secs, t1 - split_time_secs),
+                ..transcript
+            });
+        }
+    }
+
+    if before.is_empty() || after.is_empty() {
+        return Err(format!("Split at {}s would leave one of the meetings without transcripts", split_time_secs));
+    }
+    log_info!(
+        "Splitting meeting {} at {}s into {} and {} segments",
+        meeting_id, split_time_secs, before.len(), after.len()
+    );
+
+    let before_id = save_transcript(title_before, before).await?;
+    let after_id = save_transcript(title_after, after).await?;
+
+    if let Some(audio_path) = meeting_audio_path(&app, &meeting_id) {
+        let (before_path, after_path) = tauri::async_runtime::spawn_blocking(move || {
+            split_wav_file(Path::new(&audio_path), split_time_secs)
+        })
+        .await
+        .map_err(|e| format!("Audio split task failed: {}", e))?
+        .map_err(|e| format!("Failed to split audio: {}", e))?;
+
+        set_meeting_audio_path(&app, &before_id, &before_path.to_string_lossy())?;
+        set_meeting_audio_path(&app, &after_id, &after_path.to_string_lossy())?;
+    }
+
+    if delete_original {
+        // The halves were written to their own files, so the original recording and sidecar go with the meeting
+        delete_meeting(&app, &meeting_id).await?;
+    }
+
+    analytics::track_feature_used("split_meeting");
+    Ok((before_id, after_id))
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct DenoiseProgress {
+    meeting_id: String,
+    percent: u8,
+}
+
+// Runs spectral subtraction over each channel as 50%-overlapping Hann windows added back together,
+// so window edges don't click, reporting every 10%
+fn denoise_samples<F: FnMut(u8)>(
+    samples: &[f32],
+    channels: usize,
+    noise_power: f32,
+    mut on_progress: F,
+) -> anyhow::Result<Vec<f32>> {
+    let hop = SPECTRAL_WINDOW_SIZE / 2;
+    // Periodic Hann: windows one hop apart sum to exactly 1
+    let window: Vec<f32> = (0..SPECTRAL_WINDOW_SIZE)
+        .map(|n| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * n as f32 / SPECTRAL_WINDOW_SIZE as f32).cos())
+        .collect();
+    // A Hann-windowed frame keeps 3/8 of the noise power the unwindowed estimate assumes
+    let windowed_noise_power = noise_power * 0.375;
+
+    let frames = samples.len() / channels;
+    let mut output = vec![0.0f32; frames * channels];
+    // Leading hop of padding so the first samples are covered by two windows like every other
+    let windows_per_channel = (frames + hop).div_ceil(hop);
+    let total_windows = windows_per_channel * channels;
+    let mut processed = 0;
+    let mut last_reported = 0;
+
+    for channel in 0..channels {
+        let mut padded = vec![0.0f32; hop];
+        padded.extend(samples.iter().skip(channel).step_by(channels).take(frames));
+        padded.resize(windows_per_channel * hop + SPECTRAL_WINDOW_SIZE, 0.0);
+        let mut summed = vec![0.0f32; padded.len()];
+
+        for index in 0..windows_per_channel {
+            let start = index * hop;
+            let frame: Vec<f32> = padded[start..start + SPECTRAL_WINDOW_SIZE]
+                .iter()
+                .zip(&window)
+                .map(|(sample, weight)| sample * weight)
+                .collect();
+            let cleaned = spectral_subtraction(&frame, windowed_noise_power)?;
+            // The inverse FFT is unnormalized
+            for (sum, sample) in summed[start..start + SPECTRAL_WINDOW_SIZE].iter_mut().zip(&cleaned) {
+                *sum += sample / SPECTRAL_WINDOW_SIZE as f32;
+            }
+
+            processed += 1;
+            let percent = (processed * 100 / total_windows) as u8;
+            if percent >= last_reported + 10 {
+                last_reported = percent - percent % 10;
+                on_progress(last_reported);
+            }
+        }
+
+        for (frame, sample) in summed[hop..hop + frames].iter().enumerate() {
+            output[frame * channels + channel] = *sample;
+        }
+    }
+
+    Ok(output)
+}
+
+#[tauri::command]
+pub async fn api_denoise_recording<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    noise_estimate_start_secs: f32,
+    noise_estimate_duration_secs: f32,
+    alpha: f32,
+) -> Result<(), String> {
+    if noise_estimate_duration_secs <= 0.0 || alpha <= 0.0 {
+        return Err("Noise estimate duration and alpha must be positive".to_string());
+    }
+    let audio_path = meeting_audio_path(&app, &meeting_id)
+        .ok_or_else(|| format!("No recording stored for meeting {}", meeting_id))?;
+
+    let progress_app = app.clone();
+    let progress_id = meeting_id.clone();
+    let denoised_path = tauri::async_runtime::spawn_blocking(move || -> Result<PathBuf, String> {
+        let path = Path::new(&audio_path);
+        let (spec, samples) = read_wav_file(path).map_err(|e| format!("Failed to read recording: {}", e))?;
+        let channels = spec.channels.max(1) as usize;
+
+        // Noise window is taken from the first channel
+        let frame_at = |secs: f32| ((secs.max(0.0) * spec.sample_rate as f32) as usize * channels).min(samples.len());
+        let noise_start = frame_at(noise_estimate_start_secs);
+        let noise_end = frame_at(noise_estimate_start_secs + noise_estimate_duration_secs);
+        let noise: Vec<f32> = samples[noise_start..noise_end].iter().step_by(channels).copied().collect();
+        if noise.is_empty() {
+            return Err("Noise estimate window is outside the recording".to_string());
+        }
+        // White noise power spreads evenly, so each FFT bin sees N times the per-sample power
+        let noise_power = alpha * average_noise_spectrum(&noise) * SPECTRAL_WINDOW_SIZE as f32;
+
+        let denoised = denoise_samples(&samples, channels, noise_power, |percent| {
+            let payload = DenoiseProgress { meeting_id: progress_id.clone(), percent };
+            if let Err(e) = progress_app.emit("denoise-progress", payload) {
+                log_error!("Failed to emit denoise progress: {}", e);
+            }
+        })
+        .map_err(|e| format!("Failed to denoise recording: {}", e))?;
+
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("recording");
+        let output_path = path.with_file_name(format!("{}_denoised.wav", stem));
+        write_wav_file(&output_path, &denoised, spec.sample_rate, spec.channels)
+            .map_err(|e| format!("Failed to write denoised recording: {}", e))?;
+        Ok(output_path)
+    })
+    .await
+    .map_err(|e| format!("Denoise task failed: {}", e))??;
+
+    let denoised_path = denoised_path.to_string_lossy().to_string();
+    store::set_value(&app, &format!("denoisedAudioPath_{}", meeting_id), &denoised_path)?;
+    log_info!("Saved denoised recording for meeting {} to {}", meeting_id, denoised_path);
+    analytics::track_feature_used("denoise_recording");
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TrimResult {
+    pub trimmed_start_secs: f32,
+    pub trimmed_end_secs: f32,
+    pub new_duration_secs: f32,
+}
+
+// Keep at least this much audio even if most of it is quiet
+const MIN_TRIMMED_DURATION_SECS: f32 = 1.0;
+
+#[tauri::command]
+pub async fn api_trim_meeting_audio<R: Runtime>(app: AppHandle<R>, meeting_id: String, threshold_db: f32) -> Result<TrimResult, String> {
+    let audio_path = meeting_audio_path(&app, &meeting_id)
+        .ok_or_else(|| format!("No recording stored for meeting {}", meeting_id))?;
+
+    let result = tauri::async_runtime::spawn_blocking(move || -> Result<TrimResult, String> {
+        let path = Path::new(&audio_path);
+        let (spec, samples) = read_wav_file(path).map_err(|e| format!("Failed to read recording: {}", e))?;
+        let channels = spec.channels.max(1) as usize;
+
+        // Detect silence on the channel average, then cut every channel at the same frames
+        let mono: Vec<f32> = samples
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect();
+        let kept = trim_silence(&mono, spec.sample_rate, threshold_db, MIN_TRIMMED_DURATION_SECS);
+        let seconds = |frames: usize| frames as f32 / spec.sample_rate as f32;
+
+        if kept.len() < mono.len() {
+            let tmp_path = path.with_extension("wav.tmp");
+            let end = (kept.end * channels).min(samples.len());
+            write_wav_file(&tmp_path, &samples[kept.start * channels..end], spec.sample_rate, spec.channels)
+                .map_err(|e| format!("Failed to write trimmed recording: {}", e))?;
+            std::fs::rename(&tmp_path, path).map_err(|e| format!("Failed to replace recording: {}", e))?;
+        }
+
+        Ok(TrimResult {
+            trimmed_start_secs: seconds(kept.start),
+            trimmed_end_secs: seconds(mono.len() - kept.end),
+            new_duration_secs: seconds(kept.len()),
+        })
+    })
+    .await
+    .map_err(|e| format!("Trim task failed: {}", e))??;
+
+    if result.trimmed_start_secs > 0.0 || result.trimmed_end_secs > 0.0 {
+        let meeting = fetch_meeting(&meeting_id).await?;
+        let shift = |t: f32| (t - result.trimmed_start_secs).clamp(0.0, result.new_duration_secs);
+        let transcripts = meeting
+            .transcripts
+            .into_iter()
+            .map(|transcript| match parse_timestamp_range(&transcript.timestamp) {
+                Some((t0, t1)) => Transcript {
+                    timestamp: format!("{:.1} - {:.1}", shift(t0), shift(t1)),
+                    ..transcript
+                },
+                None => transcript,
+            })
+            .collect();
+        replace_transcripts(&meeting_id, transcripts).await?;
+    }
+
+    log_info!(
+        "Trimmed {:.2}s from the start and {:.2}s from the end of meeting {}",
+        result.trimmed_start_secs, result.trimmed_end_secs, meeting_id
+    );
+    Ok(result)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RangeAudioStats {
+    pub duration_secs: f32,
+    pub rms_db: f32,
+    pub peak_db: f32,
+    // Sign changes per sample
+    pub zero_crossing_rate: f32,
+    // Fraction of 10 ms frames louder than SPEECH_THRESHOLD_DB
+    pub estimated_speech_ratio: f32,
+}
+
+const SPEECH_FRAME_MS: u32 = 10;
+const SPEECH_THRESHOLD_DB: f32 = -40.0;
+// Reported for digital silence instead of -inf
+const SILENCE_FLOOR_DB: f32 = -100.0;
+
+fn to_db(linear: f32) -> f32 {
+    if linear > 0.0 {
+        (20.0 * linear.log10()).max(SILENCE_FLOOR_DB)
+    } else {
+        SILENCE_FLOOR_DB
+    }
+}
+
+fn range_audio_stats(samples: &[f32], sample_rate: u32) -> RangeAudioStats {
+    let (rms, peak) = rms_and_peak(samples);
+    let zero_crossings = samples
+        .windows(2)
+        .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+        .count();
+
+    let frame_len = ((sample_rate * SPEECH_FRAME_MS) / 1000).max(1) as usize;
+    let frames = samples.chunks(frame_len);
+    let frame_count = frames.len();
+    let speech_frames = frames.filter(|frame| to_db(rms_and_peak(frame).0) > SPEECH_THRESHOLD_DB).count();
+
+    RangeAudioStats {
+        duration_secs: samples.len() as f32 / sample_rate as f32,
+        rms_db: to_db(rms),
+        peak_db: to_db(peak),
+        zero_crossing_rate: if samples.is_empty() { 0.0 } else { zero_crossings as f32 / samples.len() as f32 },
+        estimated_speech_ratio: if frame_count == 0 { 0.0 } else { speech_frames as f32 / frame_count as f32 },
+    }
+}
+
+#[tauri::command]
+pub async fn api_get_audio_stats_range<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    start_secs: f32,
+    end_secs: f32,
+) -> Result<RangeAudioStats, String> {
+    if start_secs < 0.0 || end_secs <= start_secs {
+        return Err(format!("Invalid range {:.2}s - {:.2}s", start_secs, end_secs));
+    }
+    let audio_path = meeting_audio_path(&app, &meeting_id)
+        .ok_or_else(|| format!("No recording found for meeting {}", meeting_id))?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (spec, samples) = read_wav_range(Path::new(&audio_path), start_secs, end_secs)
+            .map_err(|e| format!("Failed to read recording: {}", e))?;
+        let mono = audio_to_mono(&samples, spec.channels, current_downmix_strategy());
+        Ok(range_audio_stats(&mono, spec.sample_rate))
+    })
+    .await
+    .map_err(|e| format!("Audio stats task failed: {}", e))?
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EnergyEnvelope {
+    pub frame_duration_ms: u32,
+    // Empty unless the mic and system tracks were saved separately
+    pub mic_rms_db: Vec<f32>,
+    pub system_rms_db: Vec<f32>,
+    pub mixed_rms_db: Vec<f32>,
+    pub timestamps_secs: Vec<f32>,
+}
+
+const ENVELOPE_BATCH_FRAMES: u32 = 1000;
+
+// Keyed by audio path and resolution; recordings don't change once saved
+static ENERGY_ENVELOPE_CACHE: Lazy<Mutex<HashMap<(String, u32), EnergyEnvelope>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Reads ENVELOPE_BATCH_FRAMES envelope frames at a time so long recordings are never fully in memory
+fn envelope_db(path: &Path, resolution_ms: u32) -> anyhow::Result<Vec<f32>> {
+    let spec = hound::WavReader::open(path)?.spec();
+    let samples_per_frame = ((spec.sample_rate as u64 * resolution_ms as u64) / 1000).max(1) as u32;
+    let mut levels = Vec::new();
+    let mut start = 0u32;
+    loop {
+        let (_, samples) = read_wav_frames(path, start, samples_per_frame.saturating_mul(ENVELOPE_BATCH_FRAMES))?;
+        if samples.is_empty() {
+            break;
+        }
+        let mono = audio_to_mono(&samples, spec.channels, current_downmix_strategy());
+        levels.extend(mono.chunks(samples_per_frame as usize).map(|frame| to_db(rms_and_peak(frame).0)));
+        if mono.len() < (samples_per_frame as usize) * ENVELOPE_BATCH_FRAMES as usize {
+            break;
+        }
+        start = start.saturating_add(samples_per_frame.saturating_mul(ENVELOPE_BATCH_FRAMES));
+    }
+    Ok(levels)
+}
+
+/// Per-frame RMS levels of the recording in dB, for the meeting timeline's energy view.
+#[tauri::command]
+pub async fn api_get_energy_envelope<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    resolution_ms: u32,
+) -> Result<EnergyEnvelope, String> {
+    if !(10..=5000).contains(&resolution_ms) {
+        return Err(format!("Resolution must be between 10 and 5000 ms, got {}", resolution_ms));
+    }
+    let audio_path = meeting_audio_path(&app, &meeting_id)
+        .ok_or_else(|| format!("No recording found for meeting {}", meeting_id))?;
+
+    let cache_key = (audio_path.clone(), resolution_ms);
+    if let Some(envelope) = ENERGY_ENVELOPE_CACHE.lock().ok().and_then(|cache| cache.get(&cache_key).cloned()) {
+        return Ok(envelope);
+    }
+
+    let mixed_rms_db = tauri::async_runtime::spawn_blocking(move || envelope_db(Path::new(&audio_path), resolution_ms))
+        .await
+        .map_err(|e| format!("Energy envelope task failed: {}", e))?
+        .map_err(|e| format!("Failed to read recording: {}", e))?;
+
+    let envelope = EnergyEnvelope {
+        frame_duration_ms: resolution_ms,
+        mic_rms_db: Vec::new(),
+        system_rms_db: Vec::new(),
+        timestamps_secs: (0..mixed_rms_db.len())
+            .map(|i| (i as u64 * resolution_ms as u64) as f32 / 1000.0)
+            .collect(),
+        mixed_rms_db,
+    };
+    if let Ok(mut cache) = ENERGY_ENVELOPE_CACHE.lock() {
+        cache.insert(cache_key, envelope.clone());
+    }
+    Ok(envelope)
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct RetranscriptionProgress {
+    meeting_id: String,
+    completed_chunks: usize,
+    total_chunks: usize,
+}
+
+const RETRANSCRIBE_SAMPLE_RATE: u32 = 16000;
+// Matches the live pipeline's chunk length so results line up with the original transcript
+const RETRANSCRIBE_CHUNK_SECS: f32 = 30.0;
+
+fn parse_engine(name: &str) -> Result<AudioTranscriptionEngine, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| format!("Unknown transcription engine: {}", name))
+}
+
+// Transcribes one 16 kHz mono piece; times in the result are relative to its start
+async fn transcribe_piece(
+    engine: &AudioTranscriptionEngine,
+    use_groq: bool,
+    samples: Vec<f32>,
+) -> Result<Vec<(String, f32, f32)>, String> {
+    let piece_secs = samples.len() as f32 / RETRANSCRIBE_SAMPLE_RATE as f32;
+    if *engine == AudioTranscriptionEngine::Deepgram {
+        let key = std::env::var("DEEPGRAM_API_KEY").unwrap_or_default();
+        let result = deepgram::transcribe_with_deepgram(&key, &samples, "retranscription", RETRANSCRIBE_SAMPLE_RATE, Vec::new())
+            .await
+            .map_err(|e| e.to_string())?;
+        return Ok(match result.speakers {
+            Some(speakers) => speakers.into_iter().map(|s| (s.text, s.start, s.end)).collect(),
+            None => vec![(result.transcript, 0.0, piece_secs)],
+        });
+    }
+    if use_groq {
+        let key = std::env::var("GROQ_API_KEY").unwrap_or_default();
+        let text = groq::transcribe_with_groq(&key, &samples, RETRANSCRIBE_SAMPLE_RATE, Vec::new())
+            .await
+            .map_err(|e| e.to_string())?;
+        return Ok(vec![(text, 0.0, piece_secs)]);
+    }
+
+    let response = crate::send_audio_chunk(samples, &crate::http_client::get_client(), crate::transcription_backend()).await?;
+    Ok(response.segments.into_iter().map(|s| (s.text, s.t0, s.t1)).collect())
+}
+
+/// Re-runs transcription over `start_secs..end_secs` of the stored recording.
+/// Nothing is saved; the frontend merges the result via `api_save_transcript` if it wants it.
+#[tauri::command]
+pub async fn api_retranscribe_range<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    start_secs: f32,
+    end_secs: f32,
+    engine: Option<String>,
+) -> Result<Vec<TranscriptUpdate>, String> {
+    if start_secs < 0.0 || end_secs <= start_secs {
+        return Err(format!("Invalid range {:.2}s - {:.2}s", start_secs, end_secs));
+    }
+    let engine = match engine {
+        Some(name) => parse_engine(&name)?,
+        None => store::get_value::<AudioTranscriptionEngine>(&app, "transcriptionEngine").unwrap_or_default(),
+    };
+    // Same rule as live capture: Turbo goes to Groq only when the user opted in and a key is set
+    let use_groq = engine == AudioTranscriptionEngine::WhisperLargeV3Turbo
+        && store::get_value::<bool>(&app, "useGroqStreaming").unwrap_or(false)
+        && std::env::var("GROQ_API_KEY").map_or(false, |key| !key.trim().is_empty());
+    let audio_path = meeting_audio_path(&app, &meeting_id)
+        .ok_or_else(|| format!("No recording found for meeting {}", meeting_id))?;
+
+    let samples = tauri::async_runtime::spawn_blocking(move || -> Result<Vec<f32>, String> {
+        // Seeks to start_secs * sample_rate so a short range doesn't load the whole recording
+        let (spec, samples) = read_wav_range(Path::new(&audio_path), start_secs, end_secs)
+            .map_err(|e| format!("Failed to read recording: {}", e))?;
+        let mono = audio_to_mono(&samples, spec.channels, current_downmix_strategy());
+        if spec.sample_rate == RETRANSCRIBE_SAMPLE_RATE || mono.is_empty() {
+            Ok(mono)
+        } else {
+            resample(&mono, spec.sample_rate, RETRANSCRIBE_SAMPLE_RATE).map_err(|e| format!("Failed to resample: {}", e))
+        }
+    })
+    .await
+    .map_err(|e| format!("Audio extraction task failed: {}", e))??;
+    if samples.is_empty() {
+        return Err(format!("Range {:.2}s - {:.2}s is outside the recording", start_secs, end_secs));
+    }
+
+    let chunk_len = (RETRANSCRIBE_CHUNK_SECS * RETRANSCRIBE_SAMPLE_RATE as f32) as usize;
+    let total_chunks = samples.len().div_ceil(chunk_len);
+    let mut updates = Vec::new();
+    for (i, piece) in samples.chunks(chunk_len).enumerate() {
+        let offset = start_secs + (i * chunk_len) as f32 / RETRANSCRIBE_SAMPLE_RATE as f32;
+        for (text, t0, t1) in transcribe_piece(&engine, use_groq, piece.to_vec()).await? {
+            let text = text.replace("[BLANK_AUDIO]", "").replace("[AUDIO OUT]", "").trim().to_string();
+            if text.is_empty() {
+                continue;
+            }
+            updates.push(TranscriptUpdate {
+                text,
+                timestamp: format!("{:.1} - {:.1}", offset + t0, offset + t1),
+                source: "Retranscription".to_string(),
+                speaker_id: None,
+            });
+        }
+
+        let progress = RetranscriptionProgress { meeting_id: meeting_id.clone(), completed_chunks: i + 1, total_chunks };
+        if let Err(e) = app.emit("retranscription-progress", progress) {
+            log_error!("Failed to emit retranscription progress: {}", e);
+        }
+    }
+
+    log_info!(
+        "Retranscribed {:.1}s - {:.1}s of meeting {} with {}: {} segments",
+        start_secs, end_secs, meeting_id, engine, updates.len()
+    );
+    Ok(updates)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CompressionResult {
+    pub output_path: String,
+    pub original_bytes: u64,
+    pub new_bytes: u64,
+    // new_bytes / original_bytes
+    pub compression_ratio: f32,
+}
+
+fn recording_format(path: &Path) -> Option<AudioFormat> {
+    match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+        "wav" => Some(AudioFormat::Wav),
+        "flac" => Some(AudioFormat::Flac),
+        _ => None,
+    }
+}
+
+// `quality` is a 0–8 FLAC level and is ignored when converting back to WAV
+#[tauri::command]
+pub async fn api_recompress_meeting_audio<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    format: AudioFormat,
+    quality: u8,
+) -> Result<CompressionResult, String> {
+    let audio_path = meeting_audio_path(&app, &meeting_id)
+        .ok_or_else(|| format!("No recording stored for meeting {}", meeting_id))?;
+    let source_format = recording_format(Path::new(&audio_path))
+        .ok_or_else(|| format!("Unsupported recording format: {}", audio_path))?;
+    if source_format == format {
+        return Err(format!("Recording is already stored as {}", format.extension()));
+    }
+    if format == AudioFormat::Wav && !is_ffmpeg_available() {
+        return Err("ffmpeg was not found; converting FLAC recordings back to WAV requires it".to_string());
+    }
+
+    let source_path = audio_path.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || -> Result<CompressionResult, String> {
+        let path = Path::new(&source_path);
+        let output_path = path.with_extension(format.extension());
+        let original_bytes = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+
+        match format {
+            AudioFormat::Flac => {
+                let (spec, samples) = read_wav_file(path).map_err(|e| format!("Failed to read recording: {}", e))?;
+                let level = FlacCompressionLevel::from_level(quality);
+                let flac = codec::encode_to_flac_with_level(&samples, spec.sample_rate, spec.channels, level)
+                    .map_err(|e| format!("Failed to compress recording: {}", e))?;
+                std::fs::write(&output_path, flac).map_err(|e| format!("Failed to write recording: {}", e))?;
+            }
+            AudioFormat::Wav => {
+                let (sample_rate, channels) = codec::flac_sample_rate(path)
+                    .zip(codec::flac_channels(path))
+                    .ok_or_else(|| format!("Failed to read FLAC header of {}", source_path))?;
+                let samples = decode_audio_file_ffmpeg_interleaved(&source_path, sample_rate, channels)
+                    .map_err(|e| format!("Failed to decode recording: {}", e))?;
+                write_wav_file(&output_path, &samples, sample_rate, channels)
+                    .map_err(|e| format!("Failed to write recording: {}", e))?;
+            }
+        }
+
+        // Keep the sidecar attached to the recording it describes
+        let output = output_path.to_string_lossy().to_string();
+        let old_meta = storage::metadata::metadata_path(&source_path);
+        if Path::new(&old_meta).exists() {
+            std::fs::rename(&old_meta, storage::metadata::metadata_path(&output))
+                .map_err(|e| format!("Failed to move recording metadata: {}", e))?;
+        }
+        std::fs::remove_file(path).map_err(|e| format!("Failed to remove original recording: {}", e))?;
+
+        let new_bytes = std::fs::metadata(&output_path).map(|m| m.len()).unwrap_or(0);
+        Ok(CompressionResult {
+            output_path: output,
+            original_bytes,
+            new_bytes,
+            compression_ratio: if original_bytes > 0 { new_bytes as f32 / original_bytes as f32 } else { 1.0 },
+        })
+    })
+    .await
+    .map_err(|e| format!("Compression task failed: {}", e))??;
+
+    set_meeting_audio_path(&app, &meeting_id, &result.output_path)?;
+    log_info!(
+        "Converted recording for meeting {} to {} ({} -> {} bytes)",
+        meeting_id, format.extension(), result.original_bytes, result.new_bytes
+    );
+    analytics::track_feature_used("recompress_meeting_audio");
+    Ok(result)
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MeetingFilter {
+    pub created_before: Option<String>,
+    pub tag_ids: Option<Vec<String>>,
+    pub title_contains: Option<String>,
+    #[serde(default)]
+    pub has_no_transcript: bool,
+    #[serde(default)]
+    pub has_no_summary: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BulkDeleteResult {
+    pub deleted_count: usize,
+    pub failed_ids: Vec<String>,
+}
+
+// Accepts a full timestamp or a bare YYYY-MM-DD date (midnight UTC)
+fn parse_filter_date(value: &str) -> Result<NaiveDateTime, String> {
+    parse_created_at(value)
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+        .ok_or_else(|| format!("Invalid date: {}", value))
+}
+
+async fn matches_filter<R: Runtime>(app: &AppHandle<R>, meeting: &MeetingDetails, filter: &MeetingFilter, created_before: Option<NaiveDateTime>) -> bool {
+    if let Some(cutoff) = created_before {
+        if !parse_created_at(&meeting.created_at).map_or(false, |created| created < cutoff) {
+            return false;
+        }
+    }
+    if let Some(tag_ids) = &filter.tag_ids {
+        let tags = meeting_tags(app, &meeting.id);
+        if !tag_ids.iter().any(|tag| tags.contains(tag)) {
+            return false;
+        }
+    }
+    if let Some(needle) = &filter.title_contains {
+        if !meeting.title.to_lowercase().contains(&needle.to_lowercase()) {
+            return false;
+        }
+    }
+    if filter.has_no_transcript && !transcript_text(meeting).is_empty() {
+        return false;
+    }
+    // Only ask the backend for summaries when the cheaper checks passed
+    if filter.has_no_summary && !matches!(fetch_summary(&meeting.id).await, Ok(None)) {
+        return false;
+    }
+    true
+}
+
+#[tauri::command]
+pub async fn api_bulk_delete_meetings<R: Runtime>(app: AppHandle<R>, filter: MeetingFilter) -> Result<BulkDeleteResult, String> {
+    let created_before = filter.created_before.as_deref().map(parse_filter_date).transpose()?;
+
+    let mut matching = Vec::new();
+    for summary in fetch_meetings().await? {
+        let meeting = fetch_meeting(&summary.id).await?;
+        if matches_filter(&app, &meeting, &filter, created_before).await {
+            matching.push(meeting.id);
+        }
+    }
+
+    // Keep going past failures so one bad meeting doesn't block the rest
+    let mut result = BulkDeleteResult { deleted_count: 0, failed_ids: Vec::new() };
+    for meeting_id in matching {
+        match api_delete_meeting(app.clone(), meeting_id.clone()).await {
+            Ok(()) => result.deleted_count += 1,
+            Err(e) => {
+                log_error!("Bulk delete failed for meeting {}: {}", meeting_id, e);
+                result.failed_ids.push(meeting_id);
+            }
+        }
+    }
+
+    log_info!("Bulk deleted {} meetings ({} failed)", result.deleted_count, result.failed_ids.len());
+    analytics::track_feature_used("bulk_delete_meetings");
+    Ok(result)
+}
+
+const TRANSCRIBE_SAMPLE_RATE: u32 = 16000;
+const TRANSCRIBE_CHUNK_SECS: usize = 30;
+
+// WAV goes through hound; everything else needs ffmpeg
+fn decode_for_transcription(path: &str) -> Result<Vec<f32>, String> {
+    let is_wav = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map_or(false, |e| e.eq_ignore_ascii_case("wav"));
+
+    if is_wav {
+        let (spec, samples) = read_wav_file(Path::new(path)).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let mono = audio_to_mono(&samples, spec.channels, current_downmix_strategy());
+        if spec.sample_rate == TRANSCRIBE_SAMPLE_RATE {
+            return Ok(mono);
+        }
+        return resample(&mono, spec.sample_rate, TRANSCRIBE_SAMPLE_RATE).map_err(|e| format!("Failed to resample {}: {}", path, e));
+    }
+
+    if !is_ffmpeg_available() {
+        return Err("Transcribing M4A/MP3 files requires ffmpeg; install it and try again".to_string());
+    }
+    decode_audio_file_ffmpeg(path, TRANSCRIBE_SAMPLE_RATE).map_err(|e| e.to_string())
+}
+
+/// Transcribes an audio file with the local transcription server, one 30 s chunk at a time.
+#[tauri::command]
+pub async fn api_transcribe_audio_file(file_path: String) -> Result<Vec<TranscriptUpdate>, String> {
+    let decode_path = file_path.clone();
+    let samples = tauri::async_runtime::spawn_blocking(move || decode_for_transcription(&decode_path))
+        .await
+        .map_err(|e| format!("Decode task failed: {}", e))??;
+
+    let client = crate::http_client::get_client();
+    let chunk_len = TRANSCRIBE_SAMPLE_RATE as usize * TRANSCRIBE_CHUNK_SECS;
+    let mut updates = Vec::new();
+    for (index, chunk) in samples.chunks(chunk_len).enumerate() {
+        let offset = (index * TRANSCRIBE_CHUNK_SECS) as f32;
+        let response = crate::send_audio_chunk(chunk.to_vec(), &client, crate::transcription_backend()).await?;
+        for segment in response.segments {
+            let text = segment.text.replace("[BLANK_AUDIO]", "").trim().to_string();
+            if text.is_empty() {
+                continue;
+            }
+            updates.push(TranscriptUpdate {
+                text,
+                timestamp: format!("{:.1} - {:.1}", offset + segment.t0, offset + segment.t1),
+                source: "Imported Audio".to_string(),
+                speaker_id: None,
+            });
+        }
+    }
+
+    log_info!("Transcribed {} into {} segments", file_path, updates.len());
+    Ok(updates)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RetentionResult {
+    pub deleted_count: usize,
+    pub freed_bytes: u64,
+    pub oldest_remaining: Option<String>,
+}
+
+#[tauri::command]
+pub async fn api_apply_retention_policy<R: Runtime>(app: AppHandle<R>, retention_days: u32) -> Result<RetentionResult, String> {
+    // A zero-day policy would put the cutoff at now and delete every meeting
+    if retention_days < 1 {
+        return Err("Retention period must be at least 1 day".to_string());
+    }
+    let cutoff = chrono::Utc::now().naive_utc() - chrono::Duration::days(retention_days as i64);
+    log_info!("Applying {} day retention policy (cutoff {})", retention_days, cutoff);
+
+    let mut result = RetentionResult { deleted_count: 0, freed_bytes: 0, oldest_remaining: None };
+    let mut oldest: Option<NaiveDateTime> = None;
+
+    for summary in fetch_meetings().await? {
+        let meeting = match fetch_meeting(&summary.id).await {
+            Ok(meeting) => meeting,
+            Err(e) => {
+                log_error!("Skipping meeting {} in retention check: {}", summary.id, e);
+                continue;
+            }
+        };
+        // Meetings with unreadable dates are kept rather than guessed at
+        let Some(created_at) = parse_created_at(&meeting.created_at) else {
+            continue;
+        };
+
+        if created_at < cutoff {
+            match delete_meeting(&app, &meeting.id).await {
+                Ok(freed) => {
+                    result.freed_bytes += freed;
+                    result.deleted_count += 1;
+                }
+                Err(e) => log_error!("Failed to delete meeting {} under retention policy: {}", meeting.id, e),
+            }
+        } else if oldest.map_or(true, |o| created_at < o) {
+            oldest = Some(created_at);
+            result.oldest_remaining = Some(meeting.created_at.clone());
+        }
+    }
+
+    log_info!(
+        "Retention policy deleted {} meetings and freed {} bytes",
+        result.deleted_count, result.freed_bytes
+    );
+    analytics::track_feature_used("retention_policy_applied");
+    Ok(result)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SemanticSearchResult {
+    pub meeting_id: String,
+    pub title: String,
+    pub score: f32,
+}
+
+fn embedding_key(meeting_id: &str) -> String {
+    format!("embedding_{}", meeting_id)
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[tauri::command]
+pub async fn api_compute_meeting_embedding<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    model: llm::EmbeddingModel,
+) -> Result<(), String> {
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let text = transcript_text(&meeting);
+    if text.is_empty() {
+        return Err(format!("Meeting {} has no transcript to embed", meeting_id));
+    }
+
+    let embedding = llm::embed(&model, &text).await?;
+    store::set_value(&app, &embedding_key(&meeting_id), &embedding)?;
+    log_info!("Stored {}-dimension embedding for meeting {}", embedding.len(), meeting_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn api_compute_all_embeddings<R: Runtime>(app: AppHandle<R>, model: llm::EmbeddingModel) -> Result<usize, String> {
+    let mut computed = 0;
+    for meeting in fetch_meetings().await? {
+        match api_compute_meeting_embedding(app.clone(), meeting.id.clone(), model.clone()).await {
+            Ok(()) => computed += 1,
+            Err(e) => log_error!("Failed to embed meeting {}: {}", meeting.id, e),
+        }
+    }
+    Ok(computed)
+}
+
+// Meetings without a stored embedding are left out; they need api_compute_meeting_embedding first
+#[tauri::command]
+pub async fn api_semantic_search<R: Runtime>(
+    app: AppHandle<R>,
+    query: String,
+    top_n: usize,
+    model: llm::EmbeddingModel,
+) -> Result<Vec<SemanticSearchResult>, String> {
+    let query_embedding = llm::embed(&model, &query).await?;
+
+    let mut results: Vec<SemanticSearchResult> = fetch_meetings()
+        .await?
+        .into_iter()
+        .filter_map(|meeting| {
+            let embedding = store::get_value::<Vec<f32>>(&app, &embedding_key(&meeting.id))?;
+            Some(SemanticSearchResult {
+                score: cosine_similarity(&query_embedding, &embedding),
+                meeting_id: meeting.id,
+                title: meeting.title,
+            })
+        })
+        .collect();
+
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    results.truncate(top_n);
+    analytics::track_feature_used("semantic_search");
+    Ok(results)
+}
+
+const SAMPLE_TRANSCRIPT: &str = "Alice: Thanks everyone for joining. Let's start with the release timeline.\n\
+Bob: The beta is on track for Friday, but we still need sign-off from design.\n\
+Alice: Okay, Bob will follow up with design by Wednesday.";
+
+// Renders a template against real data without sending anything to an LLM
+#[tauri::command]
+pub async fn api_preview_prompt_template<R: Runtime>(
+    app: AppHandle<R>,
+    template_id: String,
+    transcript_preview_chars: usize,
+) -> Result<String, String> {
+    let template = templates::load_template(&app, &template_id)
+        .ok_or_else(|| format!("Unknown prompt template: {}", template_id))?;
+
+    // The first meeting that actually has transcript text, falling back to a built-in sample
+    let mut source = None;
+    for summary in fetch_meetings().await.unwrap_or_default() {
+        if let Ok(meeting) = fetch_meeting(&summary.id).await {
+            let text = transcript_text(&meeting);
+            if !text.is_empty() {
+                source = Some((meeting, text));
+                break;
+            }
+        }
+    }
+    let (title, date, text) = match source {
+        Some((meeting, text)) => {
+            let date = parse_created_at(&meeting.created_at)
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or(meeting.created_at);
+            (meeting.title, date, text)
+        }
+        None => (
+            "Sample meeting".to_string(),
+            chrono::Utc::now().format("%Y-%m-%d").to_string(),
+            SAMPLE_TRANSCRIPT.to_string(),
+        ),
+    };
+
+    let mut variables = HashMap::new();
+    variables.insert("transcript", text.chars().take(transcript_preview_chars).collect());
+    variables.insert("meeting_title", title);
+    variables.insert("date", date);
+    Ok(templates::render(&template, &variables))
+}
+
+const MAX_TRANSCRIPT_VERSIONS: u32 = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TranscriptVersion {
+    pub version: u32,
+    pub label: Option<String>,
+    pub content: String,
+    pub saved_at: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TranscriptDiff {
+    pub added_lines: Vec<String>,
+    pub removed_lines: Vec<String>,
+    pub unchanged_lines: Vec<String>,
+    pub similarity_score: f32,
+}
+
+fn transcript_version_key(meeting_id: &str, version: u32) -> String {
+    format!("transcript_v{}_{}", version, meeting_id)
+}
+
+fn load_transcript_version<R: Runtime>(app: &AppHandle<R>, meeting_id: &str, version: u32) -> Result<TranscriptVersion, String> {
+    store::get_value::<TranscriptVersion>(app, &transcript_version_key(meeting_id, version))
+        .ok_or_else(|| format!("Version {} of meeting {} not found", version, meeting_id))
+}
+
+// Returns the store key of the new version
+#[tauri::command]
+pub fn api_save_transcript_version<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    content: String,
+    version_label: Option<String>,
+) -> Result<String, String> {
+    let counter_key = format!("transcriptVersionCounter_{}", meeting_id);
+    let version = store::get_value::<u32>(&app, &counter_key).unwrap_or(0) + 1;
+
+    let key = transcript_version_key(&meeting_id, version);
+    let entry = TranscriptVersion {
+        version,
+        label: version_label,
+        content,
+        saved_at: chrono::Utc::now().to_rfc3339(),
+    };
+    store::set_value(&app, &key, &entry)?;
+    store::set_value(&app, &counter_key, &version)?;
+
+    // Only the newest versions are kept
+    if version > MAX_TRANSCRIPT_VERSIONS {
+        store::delete_value(&app, &transcript_version_key(&meeting_id, version - MAX_TRANSCRIPT_VERSIONS))?;
+    }
+
+    log_info!("Saved transcript version {} for meeting {}", version, meeting_id);
+    Ok(key)
+}
+
+#[tauri::command]
+pub fn api_diff_transcript_versions<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    version_a: u32,
+    version_b: u32,
+) -> Result<TranscriptDiff, String> {
+    let a = load_transcript_version(&app, &meeting_id, version_a)?;
+    let b = load_transcript_version(&app, &meeting_id, version_b)?;
+
+    let mut diff = TranscriptDiff {
+        added_lines: Vec::new(),
+        removed_lines: Vec::new(),
+        unchanged_lines: Vec::new(),
+        similarity_score: 1.0,
+    };
+    // TextDiff uses Myers' algorithm by default
+    for change in similar::TextDiff::from_lines(&a.content, &b.content).iter_all_changes() {
+        let line = change.value().trim_end_matches('\n').to_string();
+        match change.tag() {
+            similar::ChangeTag::Insert => diff.added_lines.push(line),
+            similar::ChangeTag::Delete => diff.removed_lines.push(line),
+            similar::ChangeTag::Equal => diff.unchanged_lines.push(line),
+        }
+    }
+
+    // Unchanged lines form the LCS of the two versions
+    let max_lines = a.content.lines().count().max(b.content.lines().count());
+    if max_lines > 0 {
+        diff.similarity_score = diff.unchanged_lines.len() as f32 / max_lines as f32;
+    }
+    Ok(diff)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AbsoluteTranscriptUpdate {
+    pub text: String,
+    pub wall_clock_start: String,
+    pub wall_clock_end: String,
+    pub source: String,
+}
+
+// Offsets are added to the recording start and shown in the user's local time
+fn wall_clock(start: DateTime<chrono::Utc>, offset_secs: f32) -> String {
+    let offset = chrono::Duration::milliseconds((offset_secs.max(0.0) * 1000.0).round() as i64);
+    (start + offset).with_timezone(&chrono::Local).format("%H:%M:%S").to_string()
+}
+
+/// Transcript lines with "HH:MM:SS" wall-clock times instead of offsets from the recording start.
+#[tauri::command]
+pub async fn api_get_transcript_with_absolute_timestamps<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+) -> Result<Vec<AbsoluteTranscriptUpdate>, String> {
+    let started_at = store::get_value::<String>(&app, &recording_start_key(&meeting_id))
+        .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+        .map(|value| value.with_timezone(&chrono::Utc))
+        .ok_or_else(|| {
+            format!(
+                "Meeting {} has no precise recording start time. Only meetings recorded and saved with this \
+                 version of the app have one; older and imported meetings only know when they were saved.",
+                meeting_id
+            )
+        })?;
+
+    let meeting = fetch_meeting(&meeting_id).await?;
+    Ok(meeting
+        .transcripts
+        .iter()
+        .filter_map(|t| {
+            let (start, end) = parse_timestamp_range(&t.timestamp)?;
+            Some(AbsoluteTranscriptUpdate {
+                text: t.text.clone(),
+                wall_clock_start: wall_clock(started_at, start),
+                wall_clock_end: wall_clock(started_at, end),
+                // Stored transcripts come from the live pipeline, which only transcribes the mix
+                source: "Mixed Audio".to_string(),
+            })
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn api_get_transcript_paragraphs(meeting_id: String, max_gap_secs: f32) -> Result<Vec<TranscriptParagraph>, String> {
+    let meeting = fetch_meeting(&meeting_id).await?;
+    // Stored segments carry no speaker, so only pauses and length split paragraphs
+    let updates: Vec<TranscriptUpdate> = meeting
+        .transcripts
+        .into_iter()
+        .map(|t| TranscriptUpdate { text: t.text, timestamp: t.timestamp, source: String::new(), speaker_id: None })
+        .collect();
+
+    let max_gap_secs = if max_gap_secs > 0.0 { max_gap_secs } else { transcript::formatting::DEFAULT_MAX_GAP_SECS };
+    Ok(transcript::group_into_paragraphs(
+        &updates,
+        max_gap_secs,
+        transcript::formatting::DEFAULT_MAX_PARAGRAPH_SENTENCES,
+    ))
+}
+
+#[tauri::command]
+pub async fn api_get_aggregated_transcript(
+    meeting_id: String,
+    max_gap_secs: f32,
+    max_words: usize,
+) -> Result<Vec<AggregatedTranscript>, String> {
+    if max_words == 0 {
+        return Err("max_words must be at least 1".to_string());
+    }
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let updates: Vec<TranscriptUpdate> = meeting
+        .transcripts
+        .into_iter()
+        .map(|t| TranscriptUpdate { text: t.text, timestamp: t.timestamp, source: String::new(), speaker_id: None })
+        .collect();
+
+    let max_gap_secs = if max_gap_secs > 0.0 { max_gap_secs } else { transcript::formatting::DEFAULT_MAX_GAP_SECS };
+    Ok(transcript::aggregate_transcript_updates(&updates, max_gap_secs, max_words))
+}
+
+// WAV headers carry the length; other formats fall back to the sidecar written at stop time
+fn recording_duration_secs(audio_path: &str) -> Option<f32> {
+    if let Ok(reader) = hound::WavReader::open(audio_path) {
+        let spec = reader.spec();
+        return Some(reader.duration() as f32 / spec.sample_rate as f32);
+    }
+    storage::read_metadata_file(&storage::metadata::metadata_path(audio_path))
+        .ok()
+        .map(|meta| meta.duration_secs)
+}
+
+#[tauri::command]
+pub async fn api_check_transcript_coverage<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    min_gap_secs: Option<f32>,
+) -> Result<TranscriptCoverage, String> {
+    let audio_path = meeting_audio_path(&app, &meeting_id)
+        .ok_or_else(|| format!("No recording stored for meeting {}", meeting_id))?;
+    let total_audio_secs = recording_duration_secs(&audio_path)
+        .ok_or_else(|| format!("Failed to read the duration of {}", audio_path))?;
+
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let updates: Vec<TranscriptUpdate> = meeting
+        .transcripts
+        .into_iter()
+        .map(|t| TranscriptUpdate { text: t.text, timestamp: t.timestamp, source: String::new(), speaker_id: None })
+        .collect();
+
+    let min_gap_secs = min_gap_secs
+        .filter(|secs| *secs > 0.0)
+        .unwrap_or(transcript::coverage::DEFAULT_MIN_GAP_SECS);
+    let coverage = transcript::compute_coverage(&updates, total_audio_secs, min_gap_secs);
+    log_info!(
+        "Transcript for meeting {} covers {:.1}% of the recording with {} gaps",
+        meeting_id, coverage.coverage_percent, coverage.uncovered_gaps.len()
+    );
+    Ok(coverage)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EmailDraft {
+    pub subject: String,
+    pub body_html: String,
+    pub body_plain: String,
+    pub to: Vec<String>,
+}
+
+// Summary sections shown in the email body, in display order; action items get their own list
+const EMAIL_SUMMARY_SECTIONS: &[&str] = &[
+    "SectionSummary",
+    "KeyItemsDecisions",
+    "CriticalDeadlines",
+    "NextSteps",
+    "OtherImportantPoints",
+    "ClosingRemarks",
+];
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Returns (title, block contents) for a summary section, skipping empty ones
+fn summary_section(summary: &serde_json::Value, key: &str) -> Option<(String, Vec<String>)> {
+    let section = summary.get(key)?;
+    let title = section.get("title").and_then(|t| t.as_str()).unwrap_or(key).to_string();
+    let items: Vec<String> = section
+        .get("blocks")?
+        .as_array()?
+        .iter()
+        .filter_map(|block| block.get("content").and_then(|c| c.as_str()))
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty())
+        .collect();
+    (!items.is_empty()).then_some((title, items))
+}
+
+// Only builds the draft; the frontend opens it via mailto: or copies it
+#[tauri::command]
+pub async fn api_generate_email_draft(meeting_id: String, recipient_addresses: Vec<String>) -> Result<EmailDraft, String> {
+    let to: Vec<String> = recipient_addresses
+        .iter()
+        .map(|address| address.trim().to_string())
+        .filter(|address| !address.is_empty())
+        .collect();
+    if let Some(invalid) = to.iter().find(|address| !address.contains('@')) {
+        return Err(format!("Invalid email address: {}", invalid));
+    }
+
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let summary = fetch_summary(&meeting_id).await?.unwrap_or(serde_json::Value::Null);
+
+    let date = parse_created_at(&meeting.created_at)
+        .map(|created_at| created_at.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| meeting.created_at.clone());
+    let subject = format!("Meeting Minutes: {} - {}", meeting.title, date);
+
+    let mut html = format!("<h2>{}</h2>\n<p>{}</p>\n", escape_html(&meeting.title), escape_html(&date));
+    let mut plain = format!("# {}\n{}\n", meeting.title, date);
+
+    for key in EMAIL_SUMMARY_SECTIONS {
+        if let Some((title, items)) = summary_section(&summary, key) {
+            html.push_str(&format!("<h3>{}</h3>\n", escape_html(&title)));
+            plain.push_str(&format!("\n## {}\n", title));
+            for item in items {
+                html.push_str(&format!("<p>{}</p>\n", escape_html(&item)));
+                plain.push_str(&format!("{}\n", item));
+            }
+        }
+    }
+
+    if let Some((title, items)) = summary_section(&summary, "ImmediateActionItems") {
+        html.push_str(&format!("<h3>{}</h3>\n<ul>\n", escape_html(&title)));
+        plain.push_str(&format!("\n## {}\n", title));
+        for item in items {
+            html.push_str(&format!("<li>{}</li>\n", escape_html(&item)));
+            plain.push_str(&format!("- {}\n", item));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    let transcript = transcript_text(&meeting);
+    if !transcript.is_empty() {
+        html.push_str("<details>\n<summary>Transcript</summary>\n");
+        for line in transcript.lines() {
+            html.push_str(&format!("<p>{}</p>\n", escape_html(line)));
+        }
+        html.push_str("</details>\n");
+        plain.push_str(&format!("\n## Transcript\n{}\n", transcript));
+    }
+
+    analytics::track_feature_used("email_draft_generated");
+    Ok(EmailDraft {
+        subject,
+        body_html: html,
+        body_plain: plain,
+        to,
+    })
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct NotionExportResult {
+    pub page_url: String,
+    pub page_id: String,
+}
+
+#[tauri::command]
+pub fn api_save_notion_config<R: Runtime>(app: AppHandle<R>, api_key: String, database_id: String) -> Result<(), String> {
+    let (api_key, database_id) = (api_key.trim().to_string(), database_id.trim().to_string());
+    if api_key.is_empty() || database_id.is_empty() {
+        return Err("Notion API key and database id are required".to_string());
+    }
+    store::set_value(&app, "notionApiKey", &api_key)?;
+    store::set_value(&app, "notionDatabaseId", &database_id)?;
+    log_info!("Saved Notion export configuration");
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn export_to_notion<R: Runtime>(app: AppHandle<R>, meeting_id: String) -> Result<NotionExportResult, String> {
+    let api_key = store::get_value::<String>(&app, "notionApiKey")
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| "Notion is not configured; add an API key first".to_string())?;
+    let database_id = store::get_value::<String>(&app, "notionDatabaseId")
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| "Notion is not configured; add a database id first".to_string())?;
+
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let summary = fetch_summary(&meeting_id)
+        .await?
+        .and_then(|summary| summary_section(&summary, "SectionSummary"))
+        .map(|(_, items)| items.join("\n"))
+        .unwrap_or_default();
+
+    let data = notion_integration::MeetingData {
+        title: meeting.title.clone(),
+        date: parse_created_at(&meeting.created_at)
+            .map(|created_at| created_at.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%d").to_string()),
+        duration_secs: meeting_duration_secs(&meeting),
+        tags: meeting_tags(&app, &meeting_id),
+        summary,
+        sentences: meeting
+            .transcripts
+            .iter()
+            .map(|t| t.text.trim().to_string())
+            .filter(|text| !text.is_empty())
+            .collect(),
+    };
+
+    let page = notion_integration::create_notion_page(&api_key, &database_id, &data)
+        .await
+        .map_err(|e| format!("Failed to export meeting to Notion: {}", e))?;
+    analytics::track_feature_used("export_to_notion");
+    Ok(NotionExportResult { page_url: page.url, page_id: page.id })
+}
+
+fn validate_slack_webhook(webhook_url: &str) -> Result<String, String> {
+    let webhook_url = webhook_url.trim().to_string();
+    if !webhook_url.starts_with("https://hooks.slack.com/") {
+        return Err("Slack webhook URL must start with https://hooks.slack.com/".to_string());
+    }
+    Ok(webhook_url)
+}
+
+#[tauri::command]
+pub fn api_save_slack_config<R: Runtime>(
+    app: AppHandle<R>,
+    webhook_url: String,
+    channel_override: Option<String>,
+) -> Result<(), String> {
+    let webhook_url = validate_slack_webhook(&webhook_url)?;
+    let channel_override = channel_override
+        .map(|channel| channel.trim().to_string())
+        .filter(|channel| !channel.is_empty());
+    store::set_value(&app, "slackWebhookUrl", &webhook_url)?;
+    store::set_value(&app, "slackChannelOverride", &channel_override)?;
+    log_info!("Saved Slack share configuration");
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn api_test_slack_webhook(webhook_url: String) -> Result<bool, String> {
+    let webhook_url = validate_slack_webhook(&webhook_url)?;
+    match slack_integration::post_test_message(&webhook_url).await {
+        Ok(()) => Ok(true),
+        Err(e) => {
+            log_error!("Slack webhook test failed: {}", e);
+            Ok(false)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn api_share_to_slack<R: Runtime>(app: AppHandle<R>, meeting_id: String) -> Result<(), String> {
+    let webhook_url = store::get_value::<String>(&app, "slackWebhookUrl")
+        .filter(|url| !url.is_empty())
+        .ok_or_else(|| "Slack is not configured; add a webhook URL first".to_string())?;
+    let channel = store::get_value::<Option<String>>(&app, "slackChannelOverride").flatten();
+
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let summary = fetch_summary(&meeting_id).await?;
+    // Prefer the structured summary's action items; fall back to the backend summary's section
+    let action_items = store::get_value::<StructuredSummary>(&app, &structured_summary_key(&meeting_id))
+        .map(|structured| structured.action_items.into_iter().map(|item| item.description).collect::<Vec<_>>())
+        .filter(|items| !items.is_empty())
+        .or_else(|| {
+            summary
+                .as_ref()
+                .and_then(|summary| summary_section(summary, "ImmediateActionItems"))
+                .map(|(_, items)| items)
+        })
+        .unwrap_or_default();
+
+    let payload = slack_integration::SlackMeetingPayload {
+        title: meeting.title.clone(),
+        summary: summary
+            .as_ref()
+            .and_then(|summary| summary_section(summary, "SectionSummary"))
+            .map(|(_, items)| items.join("\n"))
+            .unwrap_or_default(),
+        action_items,
+        meeting_url: None,
+    };
+    slack_integration::post_to_slack(&webhook_url, &payload, channel.as_deref())
+        .await
+        .map_err(|e| format!("Failed to share meeting to Slack: {}", e))?;
+    analytics::track_feature_used("slack_share");
+    Ok(())
+}
+
+const CSV_HEADER: &[&str] = &[
+    "meeting_id",
+    "title",
+    "created_at",
+    "duration_secs",
+    "word_count",
+    "summary_length",
+    "transcript_length",
+    "tag_names",
+];
+
+// Every summary block's text, used for length statistics
+fn summary_text(summary: &serde_json::Value) -> String {
+    EMAIL_SUMMARY_SECTIONS
+        .iter()
+        .chain(std::iter::once(&"ImmediateActionItems"))
+        .filter_map(|key| summary_section(summary, key))
+        .flat_map(|(_, items)| items)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn csv_header(include_transcript: bool) -> Vec<&'static str> {
+    let mut header = CSV_HEADER.to_vec();
+    if include_transcript {
+        header.push("transcript");
+    }
+    header
+}
+
+async fn meeting_csv_record<R: Runtime>(app: &AppHandle<R>, meeting: &MeetingDetails, include_transcript: bool) -> Vec<String> {
+    let summary_length = match fetch_summary(&meeting.id).await {
+        Ok(Some(summary)) => summary_text(&summary).chars().count(),
+        _ => 0,
+    };
+    csv_record(meeting, summary_length, &meeting_tag_names(app, &meeting.id), include_transcript)
+}
+
+// Fields are raw text; the csv writer quotes and escapes them
+fn csv_record(meeting: &MeetingDetails, summary_length: usize, tags: &[String], include_transcript: bool) -> Vec<String> {
+    let transcript = transcript_text(meeting);
+    let mut record = vec![
+        meeting.id.clone(),
+        meeting.title.clone(),
+        meeting.created_at.clone(),
+        format!("{:.1}", meeting_duration_secs(meeting)),
+        transcript.split_whitespace().count().to_string(),
+        summary_length.to_string(),
+        transcript.chars().count().to_string(),
+        tags.join(";"),
+    ];
+    if include_transcript {
+        // Keep one physical line per row so naive spreadsheet imports don't split it
+        record.push(transcript.replace("\r\n", "\\n").replace('\n', "\\n"));
+    }
+    record
+}
+
+#[tauri::command]
+pub async fn api_export_meetings_csv<R: Runtime>(app: AppHandle<R>, output_path: String, include_transcript: bool) -> Result<usize, String> {
+    let mut writer = csv::Writer::from_path(&output_path)
+        .map_err(|e| format!("Failed to create {}: {}", output_path, e))?;
+    writer
+        .write_record(csv_header(include_transcript))
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+
+    let mut written = 0;
+    for summary in fetch_meetings().await? {
+        let meeting = match fetch_meeting(&summary.id).await {
+            Ok(meeting) => meeting,
+            Err(e) => {
+                log_error!("Skipping meeting {} in CSV export: {}", summary.id, e);
+                continue;
+            }
+        };
+        writer
+            .write_record(meeting_csv_record(&app, &meeting, include_transcript).await)
+            .map_err(|e| format!("Failed to write CSV: {}", e))?;
+        written += 1;
+    }
+    writer.flush().map_err(|e| format!("Failed to write CSV: {}", e))?;
+
+    analytics::track_feature_used("export_meetings_csv");
+    log_info!("Exported {} meetings to {}", written, output_path);
+    Ok(written)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ExportedFile {
+    // Relative to the export directory
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DataExportManifest {
+    pub total_meetings: usize,
+    pub total_files: usize,
+    pub export_timestamp: String,
+    pub files: Vec<ExportedFile>,
+}
+
+// Webhook URLs (e.g. Slack's) carry their credential in the path
+const SECRET_KEY_MARKERS: &[&str] = &["apikey", "api_key", "token", "password", "secret", "webhook"];
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SECRET_KEY_MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+// Secrets can be nested (e.g. the proxy password), so walk the whole value
+fn redact_secrets(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret_key(key) {
+                    *value = serde_json::Value::String("[REDACTED]".to_string());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+fn meeting_summary_document(meeting: &MeetingDetails, summary: &serde_json::Value) -> String {
+    let mut document = format!("{}\n\n", meeting.title);
+    for key in EMAIL_SUMMARY_SECTIONS.iter().chain(std::iter::once(&"ImmediateActionItems")) {
+        if let Some((title, items)) = summary_section(summary, key) {
+            document.push_str(&format!("{}\n", title));
+            for item in items {
+                document.push_str(&format!("- {}\n", item));
+            }
+            document.push('\n');
+        }
+    }
+    document
+}
+
+fn write_export_file(
+    output_dir: &Path,
+    filename: &str,
+    content_type: &str,
+    contents: &[u8],
+    files: &mut Vec<ExportedFile>,
+) -> Result<(), String> {
+    let path = output_dir.join(filename);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    files.push(ExportedFile {
+        filename: filename.to_string(),
+        content_type: content_type.to_string(),
+        size_bytes: contents.len() as u64,
+    });
+    Ok(())
+}
+
+fn to_pretty_json<T: Serialize>(value: &T) -> Result<Vec<u8>, String> {
+    serde_json::to_vec_pretty(value).map_err(|e| format!("Failed to serialize export: {}", e))
+}
+
+// Everything the app holds about the user, as a plain directory tree
+#[tauri::command]
+pub async fn api_export_all_user_data<R: Runtime>(app: AppHandle<R>, output_dir: String) -> Result<DataExportManifest, String> {
+    let output_dir = PathBuf::from(&output_dir);
+    std::fs::create_dir_all(&output_dir).map_err(|e| format!("Failed to create {}: {}", output_dir.display(), e))?;
+
+    let mut files = Vec::new();
+    let mut meetings = Vec::new();
+    for listed in fetch_meetings().await? {
+        let meeting = match fetch_meeting(&listed.id).await {
+            Ok(meeting) => meeting,
+            Err(e) => {
+                log_error!("Skipping meeting {} in data export: {}", listed.id, e);
+                continue;
+            }
+        };
+
+        let transcript: String = meeting
+            .transcripts
+            .iter()
+            .map(|t| format!("[{}] {}\n", t.timestamp, t.text.trim()))
+            .collect();
+        write_export_file(&output_dir, &format!("transcripts/{}.txt", meeting.id), "text/plain", transcript.as_bytes(), &mut files)?;
+
+        if let Ok(Some(summary)) = fetch_summary(&meeting.id).await {
+            let document = meeting_summary_document(&meeting, &summary);
+            write_export_file(&output_dir, &format!("summaries/{}.txt", meeting.id), "text/plain", document.as_bytes(), &mut files)?;
+        }
+
+        meetings.push(serde_json::json!({
+            "id": meeting.id,
+            "title": meeting.title,
+            "created_at": meeting.created_at,
+            "updated_at": meeting.updated_at,
+            "transcript_segments": meeting.transcripts.len(),
+            "tags": meeting_tags(&app, &meeting.id),
+            "audio_path": meeting_audio_path(&app, &meeting.id),
+        }));
+    }
+    write_export_file(&output_dir, "meetings.json", "application/json", &to_pretty_json(&meetings)?, &mut files)?;
+
+    let events = analytics::local_events()?;
+    write_export_file(&output_dir, "analytics.json", "application/json", &to_pretty_json(&events)?, &mut files)?;
+
+    let mut settings = serde_json::Value::Object(store::entries(&app)?.into_iter().collect());
+    redact_secrets(&mut settings);
+    write_export_file(&output_dir, "settings.json", "application/json", &to_pretty_json(&settings)?, &mut files)?;
+
+    let manifest = DataExportManifest {
+        total_meetings: meetings.len(),
+        total_files: files.len(),
+        export_timestamp: chrono::Utc::now().to_rfc3339(),
+        files,
+    };
+    std::fs::write(output_dir.join("manifest.json"), to_pretty_json(&manifest)?)
+        .map_err(|e| format!("Failed to write manifest: {}", e))?;
+
+    analytics::track_feature_used("export_all_user_data");
+    log_info!(
+        "Exported data for {} meetings ({} files) to {}",
+        manifest.total_meetings, manifest.total_files, output_dir.display()
+    );
+    Ok(manifest)
+}
+
+// Single meeting as CSV text, including the transcript, for copying to the clipboard
+#[tauri::command]
+pub async fn api_export_meeting_csv<R: Runtime>(app: AppHandle<R>, meeting_id: String) -> Result<String, String> {
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let record = meeting_csv_record(&app, &meeting, true).await;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(csv_header(true))
+        .and_then(|_| writer.write_record(&record))
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| format!("CSV output is not valid UTF-8: {}", e))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PodcastConfig {
+    pub feed_title: String,
+    pub host_name: String,
+    pub artwork_path: Option<String>,
+}
+
+// Paragraphs are merged until a chapter is at least this long
+const MIN_CHAPTER_SECS: f32 = 120.0;
+const CHAPTER_TITLE_WORDS: usize = 6;
+
+#[tauri::command]
+pub fn set_podcast_config<R: Runtime>(app: AppHandle<R>, config: PodcastConfig) -> Result<(), String> {
+    if let Some(path) = config.artwork_path.as_deref().filter(|path| !Path::new(path).exists()) {
+        return Err(format!("Artwork not found: {}", path));
+    }
+    store::set_value(&app, "podcastConfig", &config)
+}
+
+// No bookmark support yet, so chapters follow the transcript's paragraph breaks
+fn podcast_chapters(meeting: &MeetingDetails, duration_secs: f32) -> Vec<podcast::Chapter> {
+    let updates: Vec<TranscriptUpdate> = meeting
+        .transcripts
+        .iter()
+        .map(|t| TranscriptUpdate { text: t.text.clone(), timestamp: t.timestamp.clone(), source: String::new(), speaker_id: None })
+        .collect();
+    let paragraphs = transcript::group_into_paragraphs(
+        &updates,
+        transcript::formatting::DEFAULT_MAX_GAP_SECS,
+        transcript::formatting::DEFAULT_MAX_PARAGRAPH_SENTENCES,
+    );
+
+    let mut chapters: Vec<podcast::Chapter> = Vec::new();
+    for paragraph in paragraphs {
+        match chapters.last_mut() {
+            Some(current) if paragraph.start_time - current.start_secs < MIN_CHAPTER_SECS => {
+                current.end_secs = paragraph.end_time;
+            }
+            _ => {
+                let title = paragraph
+                    .sentences
+                    .first()
+                    .map(|s| s.text.split_whitespace().take(CHAPTER_TITLE_WORDS).collect::<Vec<_>>().join(" "))
+                    .unwrap_or_default();
+                chapters.push(podcast::Chapter {
+                    start_secs: paragraph.start_time,
+                    end_secs: paragraph.end_time,
+                    title: format!("Chapter {}: {}", chapters.len() + 1, title),
+                });
+            }
+        }
+    }
+
+    // Chapters must be contiguous and cover the whole episode
+    if let Some(first) = chapters.first_mut() {
+        first.start_secs = 0.0;
+    }
+    for i in 1..chapters.len() {
+        chapters[i - 1].end_secs = chapters[i].start_secs;
+    }
+    if let Some(last) = chapters.last_mut() {
+        last.end_secs = last.end_secs.max(duration_secs);
+    }
+    chapters
+}
+
+#[tauri::command]
+pub async fn api_export_podcast_episode<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    output_path: String,
+    episode_number: u32,
+) -> Result<(), String> {
+    let audio_path = meeting_audio_path(&app, &meeting_id)
+        .ok_or_else(|| format!("No recording stored for meeting {}", meeting_id))?;
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let config = store::get_value::<PodcastConfig>(&app, "podcastConfig").unwrap_or_default();
+
+    let year = parse_created_at(&meeting.created_at)
+        .map(|created_at| created_at.year())
+        .unwrap_or_else(|| chrono::Utc::now().year());
+    let metadata = podcast::EpisodeMetadata {
+        title: meeting.title.clone(),
+        artist: "Meeting Minutes".to_string(),
+        album: format!("Meetings {}", year),
+        album_artist: Some(config.host_name.clone()).filter(|name| !name.is_empty()),
+        show: Some(config.feed_title.clone()).filter(|title| !title.is_empty()),
+        track: episode_number,
+    };
+    let duration_secs = recording_duration_secs(&audio_path).unwrap_or_else(|| meeting_duration_secs(&meeting));
+    let chapters = podcast_chapters(&meeting, duration_secs);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        podcast::export_m4a(
+            Path::new(&audio_path),
+            Path::new(&output_path),
+            &metadata,
+            &chapters,
+            config.artwork_path.as_deref().map(Path::new),
+        )
+    })
+    .await
+    .map_err(|e| format!("Podcast export task failed: {}", e))?
+    .map_err(|e| format!("Failed to export podcast episode: {}", e))?;
+
+    analytics::track_feature_used("export_podcast_episode");
+    Ok(())
+}
+
+const AUTO_TITLE_EXCERPT_CHARS: usize = 500;
+
+#[tauri::command]
+pub async fn api_auto_title_meeting(meeting_id: String, model_provider: String) -> Result<String, String> {
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let excerpt: String = transcript_text(&meeting)
+        .chars()
+        .take(AUTO_TITLE_EXCERPT_CHARS)
+        .collect();
+    if excerpt.trim().is_empty() {
+        return Err("Meeting has no transcript to generate a title from".to_string());
+    }
+
+    let prompt = format!(
+        "Generate a concise 5-7 word meeting title based on this transcript excerpt. \
+         Return only the title, no punctuation at the end.\n\n{}",
+        excerpt
+    );
+    let response = llm::generate(&model_provider, &prompt).await?;
+
+    let title = response
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or_default()
+        .trim()
+        .trim_matches(|c: char| c == '"' || c == '\'')
+        .trim_end_matches(|c: char| c.is_ascii_punctuation())
+        .to_string();
+    if title.is_empty() {
+        return Err("Model returned an empty title".to_string());
+    }
+
+    api_save_meeting_title(meeting_id.clone(), title.clone()).await?;
+    analytics::track_feature_used("auto_title");
+    log_info!("Generated title for meeting {}: {}", meeting_id, title);
+    Ok(title)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ImprovementResult {
+    pub original_length: usize,
+    pub corrected_length: usize,
+    pub correction_count: usize,
+    pub corrected_text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptCorrection {
+    original: String,
+    corrected: String,
+    #[serde(default)]
+    reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptCorrections {
+    corrections: Vec<TranscriptCorrection>,
+}
+
+// Models often wrap JSON in prose or code fences; keep only the outermost object
+fn extract_json_object(response: &str) -> Option<&str> {
+    let start = response.find('{')?;
+    let end = response.rfind('}')?;
+    (end > start).then(|| &response[start..=end])
+}
+
+#[tauri::command]
+pub async fn api_auto_improve_transcript<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    model_provider: String,
+    max_corrections: usize,
+) -> Result<ImprovementResult, String> {
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let original = transcript_text(&meeting);
+    if original.trim().is_empty() {
+        return Err("Meeting has no transcript to improve".to_string());
+    }
+
+    let prompt = format!(
+        "The following meeting transcript was produced by speech recognition. Identify at most {} clear \
+         transcription errors such as homophones or misspelled technical terms. Do not paraphrase, \
+         rephrase or fix grammar. Respond only with JSON of the form \
+         {{\"corrections\": [{{\"original\": \"...\", \"corrected\": \"...\", \"reason\": \"...\"}}]}} \
+         where \"original\" is copied exactly from the transcript.\n\n{}",
+        max_corrections, original
+    );
+    let response = llm::generate(&model_provider, &prompt).await?;
+    let parsed: TranscriptCorrections = extract_json_object(&response)
+        .ok_or_else(|| "Model response did not contain JSON".to_string())
+        .and_then(|json| serde_json::from_str(json).map_err(|e| format!("Failed to parse corrections: {}", e)))?;
+
+    let mut corrected_text = original.clone();
+    let mut correction_count = 0;
+    for correction in parsed.corrections.into_iter().take(max_corrections) {
+        if correction.original.is_empty() || !corrected_text.contains(&correction.original) {
+            continue;
+        }
+        log_info!("Correcting '{}' -> '{}' ({})", correction.original, correction.corrected, correction.reason);
+        corrected_text = corrected_text.replace(&correction.original, &correction.corrected);
+        correction_count += 1;
+    }
+
+    // Keep the raw transcript as version 1 so the improvement lands as version 2 and can be diffed
+    let counter_key = format!("transcriptVersionCounter_{}", meeting_id);
+    if store::get_value::<u32>(&app, &counter_key).unwrap_or(0) == 0 {
+        api_save_transcript_version(app.clone(), meeting_id.clone(), original.clone(), Some("original".to_string()))?;
+    }
+    api_save_transcript_version(app, meeting_id.clone(), corrected_text.clone(), Some("auto-improved".to_string()))?;
+
+    analytics::track_feature_used("auto_improve_transcript");
+    log_info!("Applied {} transcript corrections to meeting {}", correction_count, meeting_id);
+    Ok(ImprovementResult {
+        original_length: original.len(),
+        corrected_length: corrected_text.len(),
+        correction_count,
+        corrected_text,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ActionItem {
+    // Position in the summary ("1", "2", ...); older summaries were stored without one
+    #[serde(default)]
+    pub id: String,
+    pub description: String,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira_issue_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StructuredSummary {
+    pub title: String,
+    pub date: String,
+    pub duration_mins: f32,
+    pub key_topics: Vec<String>,
+    pub decisions: Vec<String>,
+    pub action_items: Vec<ActionItem>,
+    pub next_meeting_date: Option<String>,
+    pub attendee_count_estimate: Option<usize>,
+}
+
+// The part of StructuredSummary the model fills in; title, date and duration come from the meeting
+#[derive(Debug, Deserialize)]
+struct StructuredSummaryContent {
+    key_topics: Vec<String>,
+    decisions: Vec<String>,
+    action_items: Vec<ActionItem>,
+    #[serde(default)]
+    next_meeting_date: Option<String>,
+    #[serde(default)]
+    attendee_count_estimate: Option<usize>,
+}
+
+const STRUCTURED_SUMMARY_SCHEMA: &str = r#"{"key_topics": ["..."], "decisions": ["..."], "action_items": [{"description": "...", "owner": "name or null", "due_date": "YYYY-MM-DD or null", "priority": "High, Medium, Low or null"}], "next_meeting_date": "YYYY-MM-DD or null", "attendee_count_estimate": 3}"#;
+
+fn structured_summary_key(meeting_id: &str) -> String {
+    format!("structuredSummary_{}", meeting_id)
+}
+
+// Checks the shape before deserializing so the error names the field the model got wrong
+fn parse_structured_summary_content(response: &str) -> Result<StructuredSummaryContent, String> {
+    let json = extract_json_object(response).ok_or_else(|| "Model response did not contain a JSON object".to_string())?;
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("Model returned malformed JSON: {}", e))?;
+
+    for field in ["key_topics", "decisions", "action_items"] {
+        match value.get(field) {
+            None => return Err(format!("Structured summary is missing required field \"{}\"", field)),
+            Some(v) if !v.is_array() => return Err(format!("Structured summary field \"{}\" must be an array", field)),
+            _ => {}
+        }
+    }
+    if let Some(items) = value.get("action_items").and_then(|v| v.as_array()) {
+        for (i, item) in items.iter().enumerate() {
+            if item.get("description").and_then(|d| d.as_str()).map_or(true, |d| d.trim().is_empty()) {
+                return Err(format!("Action item {} has no \"description\"", i + 1));
+            }
+        }
+    }
+    serde_json::from_value(value).map_err(|e| format!("Structured summary does not match the schema: {}", e))
+}
+
+#[tauri::command]
+pub async fn api_generate_structured_summary<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    model_provider: String,
+) -> Result<StructuredSummary, String> {
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let text = transcript_text(&meeting);
+    if text.trim().is_empty() {
+        return Err("Meeting has no transcript to summarize".to_string());
+    }
+
+    let prompt = format!(
+        "Summarize this meeting transcript for use by other tools. Respond only with JSON matching \
+         this schema: {}\nUse empty arrays when there is nothing to report and null for unknown values. \
+         Do not invent owners or dates that are not stated in the transcript.\n\n{}",
+        STRUCTURED_SUMMARY_SCHEMA, text
+    );
+    let response = llm::generate(&model_provider, &prompt).await?;
+    let mut content = parse_structured_summary_content(&response)?;
+    assign_action_item_ids(&mut content.action_items);
+
+    let duration_secs = meeting_audio_path(&app, &meeting_id)
+        .and_then(|path| recording_duration_secs(&path))
+        .or_else(|| {
+            meeting
+                .transcripts
+                .iter()
+                .filter_map(|t| parse_timestamp_range(&t.timestamp))
+                .map(|(_, end)| end)
+                .reduce(f32::max)
+        })
+        .unwrap_or(0.0);
+
+    let summary = StructuredSummary {
+        title: meeting.title.clone(),
+        date: meeting.created_at.clone(),
+        duration_mins: duration_secs / 60.0,
+        key_topics: content.key_topics,
+        decisions: content.decisions,
+        action_items: content.action_items,
+        next_meeting_date: content.next_meeting_date,
+        attendee_count_estimate: content.attendee_count_estimate,
+    };
+    store::set_value(&app, &structured_summary_key(&meeting_id), &summary)?;
+
+    analytics::track_feature_used("structured_summary");
+    log_info!(
+        "Generated structured summary for meeting {}: {} topics, {} action items",
+        meeting_id, summary.key_topics.len(), summary.action_items.len()
+    );
+    Ok(summary)
+}
+
+#[tauri::command]
+pub fn api_get_structured_summary<R: Runtime>(app: AppHandle<R>, meeting_id: String) -> Result<Option<StructuredSummary>, String> {
+    Ok(store::get_value::<StructuredSummary>(&app, &structured_summary_key(&meeting_id)))
+}
+
+fn assign_action_item_ids(items: &mut [ActionItem]) {
+    for (i, item) in items.iter_mut().enumerate() {
+        if item.id.is_empty() {
+            item.id = (i + 1).to_string();
+        }
+    }
+}
+
+const JIRA_CONFIG_KEY: &str = "jiraConfig";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct JiraIssueResult {
+    pub action_item_id: String,
+    pub jira_issue_key: String,
+    pub jira_url: String,
+}
+
+#[tauri::command]
+pub fn api_save_jira_config<R: Runtime>(app: AppHandle<R>, config: JiraConfig) -> Result<(), String> {
+    let config = JiraConfig {
+        base_url: config.base_url.trim().trim_end_matches('/').to_string(),
+        email: config.email.trim().to_string(),
+        api_token: config.api_token.trim().to_string(),
+        project_key: config.project_key.trim().to_string(),
+        issue_type: config.issue_type.trim().to_string(),
+    };
+    if config.base_url.is_empty()
+        || config.email.is_empty()
+        || config.api_token.is_empty()
+        || config.project_key.is_empty()
+        || config.issue_type.is_empty()
+    {
+        return Err("JIRA base URL, email, API token, project key and issue type are required".to_string());
+    }
+    store::set_value(&app, JIRA_CONFIG_KEY, &config)?;
+    log_info!("Saved JIRA export configuration for project {}", config.project_key);
+    Ok(())
+}
+
+/// Files every action item from the meeting's structured summary that hasn't been filed yet.
+/// Issue keys are written back to the stored summary after each success so a partial failure
+/// can be retried without duplicating issues.
+#[tauri::command]
+pub async fn api_create_jira_issues<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    jira_config: JiraConfig,
+) -> Result<Vec<JiraIssueResult>, String> {
+    let key = structured_summary_key(&meeting_id);
+    let mut summary = store::get_value::<StructuredSummary>(&app, &key)
+        .ok_or_else(|| "Meeting has no action items; generate a structured summary first".to_string())?;
+    assign_action_item_ids(&mut summary.action_items);
+
+    let context = format!(
+        "From meeting \"{}\" on {}.\nKey topics: {}",
+        summary.title,
+        summary.date,
+        if summary.key_topics.is_empty() { "none recorded".to_string() } else { summary.key_topics.join(", ") }
+    );
+
+    let mut results = Vec::new();
+    for i in 0..summary.action_items.len() {
+        let item = &summary.action_items[i];
+        if item.jira_issue_key.is_some() {
+            continue;
+        }
+        let mut description = format!("{}\n{}", item.description, context);
+        if let Some(due_date) = &item.due_date {
+            description.push_str(&format!("\nDue: {}", due_date));
+        }
+        let issue = jira_integration::JiraIssue {
+            summary: item.description.clone(),
+            description,
+            priority: item.priority.clone(),
+            assignee: item.owner.clone(),
+        };
+        let created = jira_integration::create_issue(&jira_config, &issue)
+            .await
+            .map_err(|e| format!("Failed to create JIRA issue for action item {}: {}", item.id, e))?;
+
+        summary.action_items[i].jira_issue_key = Some(created.key.clone());
+        store::set_value(&app, &key, &summary)?;
+        results.push(JiraIssueResult {
+            action_item_id: summary.action_items[i].id.clone(),
+            jira_issue_key: created.key,
+            jira_url: created.url,
+        });
+    }
+
+    analytics::track_feature_used("export_to_jira");
+    log_info!("Created {} JIRA issues for meeting {}", results.len(), meeting_id);
+    Ok(results)
+}
+
+const DASHBOARD_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+const DASHBOARD_TOP_WORDS: usize = 20;
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "that", "this", "with", "have", "for", "you", "are", "was", "but", "not",
+    "just", "they", "what", "there", "about", "would", "could", "going", "think", "like",
+    "yeah", "know", "will", "from", "can", "all", "our", "we're", "it's", "that's", "i'm",
+];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WordFrequency {
+    pub word: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DashboardData {
+    pub total_meetings: usize,
+    pub total_recording_minutes: f32,
+    pub total_words_transcribed: usize,
+    pub meetings_per_weekday: [usize; 7],
+    pub avg_meeting_duration_mins: f32,
+    pub most_active_hour: u8,
+    pub top_words: Vec<WordFrequency>,
+    pub total_action_items: usize,
+    pub completed_action_items: usize,
+}
+
+static DASHBOARD_CACHE: Lazy<Mutex<Option<(Instant, DashboardData)>>> = Lazy::new(|| Mutex::new(None));
+
+fn invalidate_dashboard_cache() {
+    if let Ok(mut cache) = DASHBOARD_CACHE.lock() {
+        *cache = None;
+    }
+}
+
+// Meeting length is the end of the last transcript segment
+pub fn meeting_duration_secs(meeting: &MeetingDetails) -> f32 {
+    meeting
+        .transcripts
+        .iter()
+        .filter_map(|t| parse_timestamp_range(&t.timestamp))
+        .map(|(_, end)| end)
+        .fold(0.0, f32::max)
+}
+
+#[tauri::command]
+pub async fn api_get_analytics_dashboard() -> Result<DashboardData, String> {
+    if let Ok(cache) = DASHBOARD_CACHE.lock() {
+        if let Some((computed_at, data)) = cache.as_ref() {
+            if computed_at.elapsed() < DASHBOARD_CACHE_TTL {
+                return Ok(data.clone());
+            }
+        }
+    }
+
+    let meetings = fetch_meetings().await?;
+    let mut total_secs = 0.0f32;
+    let mut total_words = 0;
+    let mut meetings_per_weekday = [0usize; 7];
+    let mut meetings_per_hour = [0usize; 24];
+    let mut word_counts: HashMap<String, usize> = HashMap::new();
+    let mut total_action_items = 0;
+
+    for summary in &meetings {
+        let meeting = match fetch_meeting(&summary.id).await {
+            Ok(meeting) => meeting,
+            Err(e) => {
+                log_error!("Skipping meeting {} in dashboard: {}", summary.id, e);
+                continue;
+            }
+        };
+
+        total_secs += meeting_duration_secs(&meeting);
+
+        if let Some(created_at) = parse_created_at(&meeting.created_at) {
+            meetings_per_weekday[created_at.weekday().num_days_from_monday() as usize] += 1;
+            meetings_per_hour[created_at.hour() as usize] += 1;
+        }
+
+        for word in meeting.transcripts.iter().flat_map(|t| t.text.split_whitespace()) {
+            total_words += 1;
+            let word = word
+                .trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                .to_lowercase();
+            if word.len() > 2 && !STOP_WORDS.contains(&word.as_str()) {
+                *word_counts.entry(word).or_insert(0) += 1;
+            }
+        }
+
+        if let Ok(Some(summary_data)) = fetch_summary(&meeting.id).await {
+            total_action_items += summary_data
+                .pointer("/ImmediateActionItems/blocks")
+                .and_then(|blocks| blocks.as_array())
+                .map_or(0, |blocks| blocks.len());
+        }
+    }
+
+    let mut top_words: Vec<WordFrequency> = word_counts
+        .into_iter()
+        .map(|(word, count)| WordFrequency { word, count })
+        .collect();
+    top_words.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+    top_words.truncate(DASHBOARD_TOP_WORDS);
+
+    let most_active_hour = meetings_per_hour
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, count)| **count)
+        .map_or(0, |(hour, _)| hour as u8);
+
+    let total_recording_minutes = total_secs / 60.0;
+    let data = DashboardData {
+        total_meetings: meetings.len(),
+        total_recording_minutes,
+        total_words_transcribed: total_words,
+        meetings_per_weekday,
+        avg_meeting_duration_mins: if meetings.is_empty() {
+            0.0
+        } else {
+            total_recording_minutes / meetings.len() as f32
+        },
+        most_active_hour,
+        top_words,
+        total_action_items,
+        // Summaries do not track completion state yet
+        completed_action_items: 0,
+    };
+
+    if let Ok(mut cache) = DASHBOARD_CACHE.lock() {
+        *cache = Some((Instant::now(), data.clone()));
+    }
+    Ok(data)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RecurringTopic {
+    pub topic: String,
+    pub meeting_count: usize,
+    pub meeting_ids: Vec<String>,
+    pub first_occurrence: String,
+    pub recent_occurrence: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct TopicTrackingProgress {
+    processed: usize,
+    total: usize,
+}
+
+// Runs of capitalized words or numbers, e.g. "Q4 Planning" or "Project Atlas"
+static NOUN_PHRASE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b[A-Z][\w'-]*(?:\s+(?:[A-Z][\w'-]*|\d+))*").unwrap());
+
+const TOPIC_STOP_WORDS: &[&str] = &[
+    "i", "i'm", "i'll", "i've", "a", "an", "the", "so", "and", "but", "or", "if", "we", "you",
+    "it", "it's", "this", "that", "yes", "yeah", "no", "okay", "ok", "well", "um", "uh", "oh",
+    "hi", "hello", "thanks", "right", "now", "then", "also", "just", "what", "how", "why",
+];
+
+fn extract_noun_phrases(text: &str) -> BTreeSet<String> {
+    NOUN_PHRASE_REGEX
+        .find_iter(text)
+        .filter_map(|m| {
+            // Drop sentence-initial filler such as "So Project Atlas" -> "Project Atlas"
+            let words: Vec<&str> = m
+                .as_str()
+                .split_whitespace()
+                .skip_while(|w| TOPIC_STOP_WORDS.contains(&w.to_lowercase().as_str()))
+                .collect();
+            let phrase = words.join(" ");
+            (phrase.len() > 2).then_some(phrase)
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn api_track_topics<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_ids: Vec<String>,
+    min_meetings: usize,
+) -> Result<Vec<RecurringTopic>, String> {
+    let meeting_ids = if meeting_ids.is_empty() {
+        fetch_meetings().await?.into_iter().map(|m| m.id).collect()
+    } else {
+        meeting_ids
+    };
+    let min_meetings = min_meetings.max(1);
+
+    // phrase (lowercased) -> (display form, meetings it appeared in)
+    let mut occurrences: HashMap<String, (String, Vec<(String, String)>)> = HashMap::new();
+    for (index, meeting_id) in meeting_ids.iter().enumerate() {
+        let meeting = match fetch_meeting(meeting_id).await {
+            Ok(meeting) => meeting,
+            Err(e) => {
+                log_error!("Skipping meeting {} in topic tracking: {}", meeting_id, e);
+                continue;
+            }
+        };
+
+        for phrase in extract_noun_phrases(&transcript_text(&meeting)) {
+            let entry = occurrences
+                .entry(phrase.to_lowercase())
+                .or_insert_with(|| (phrase.clone(), Vec::new()));
+            if !entry.1.iter().any(|(id, _)| id == &meeting.id) {
+                entry.1.push((meeting.id.clone(), meeting.created_at.clone()));
+            }
+        }
+
+        // Long meeting lists take a while; let the frontend show progress
+        let progress = TopicTrackingProgress { processed: index + 1, total: meeting_ids.len() };
+        if let Err(e) = app.emit("topic-tracking-progress", progress) {
+            log_error!("Failed to emit topic-tracking-progress event: {}", e);
+        }
+    }
+
+    let mut topics: Vec<RecurringTopic> = occurrences
+        .into_values()
+        .filter(|(_, meetings)| meetings.len() >= min_meetings)
+        .map(|(topic, mut meetings)| {
+            meetings.sort_by(|a, b| a.1.cmp(&b.1));
+            RecurringTopic {
+                topic,
+                meeting_count: meetings.len(),
+                first_occurrence: meetings.first().map(|m| m.1.clone()).unwrap_or_default(),
+                recent_occurrence: meetings.last().map(|m| m.1.clone()).unwrap_or_default(),
+                meeting_ids: meetings.into_iter().map(|m| m.0).collect(),
+            }
+        })
+        .collect();
+    topics.sort_by(|a, b| b.meeting_count.cmp(&a.meeting_count).then_with(|| a.topic.cmp(&b.topic)));
+
+    log_info!("Found {} recurring topics across {} meetings", topics.len(), meeting_ids.len());
+    Ok(topics)
+}
+
+pub const CALENDAR_EVENTS_KEY: &str = "calendarEvents";
+// event id -> meeting id; kept apart from the events so a re-import doesn't drop links
+const CALENDAR_LINKS_KEY: &str = "calendarEventLinks";
+const CALENDAR_MATCH_WINDOW_MINUTES: i64 = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalendarEvent {
+    pub id: String,
+    pub title: String,
+    pub start_iso: String,
+    pub end_iso: String,
+    pub attendees: Vec<String>,
+}
+
+fn parse_event_time(value: &str) -> Option<DateTime<chrono::Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|value| value.with_timezone(&chrono::Utc))
+}
+
+fn calendar_links<R: Runtime>(app: &AppHandle<R>) -> HashMap<String, String> {
+    store::get_value(app, CALENDAR_LINKS_KEY).unwrap_or_default()
+}
+
+// Prefers the precise recording start; older meetings only know when they were saved
+async fn meeting_start<R: Runtime>(app: &AppHandle<R>, meeting_id: &str) -> Option<DateTime<chrono::Utc>> {
+    if let Some(started_at) = store::get_value::<String>(app, &recording_start_key(meeting_id)) {
+        return parse_event_time(&started_at);
+    }
+    let meeting = fetch_meeting(meeting_id).await.ok()?;
+    parse_created_at(&meeting.created_at).map(|created_at| created_at.and_utc())
+}
+
+/// Stores the events (replacing any with the same id) and links each unlinked one to the
+/// recording that started closest to it, if that is within five minutes. Returns how many
+/// events were imported.
+#[tauri::command]
+pub async fn api_import_calendar_events<R: Runtime>(
+    app: AppHandle<R>,
+    events: Vec<CalendarEvent>,
+) -> Result<usize, String> {
+    for event in &events {
+        let (Some(start), Some(end)) = (parse_event_time(&event.start_iso), parse_event_time(&event.end_iso)) else {
+            return Err(format!("Calendar event {} has an invalid start or end time", event.id));
+        };
+        if end < start {
+            return Err(format!("Calendar event {} ends before it starts", event.id));
+        }
+    }
+
+    let mut stored: Vec<CalendarEvent> = store::get_value(&app, CALENDAR_EVENTS_KEY).unwrap_or_default();
+    stored.retain(|existing| !events.iter().any(|event| event.id == existing.id));
+    stored.extend(events.iter().cloned());
+    store::set_value(&app, CALENDAR_EVENTS_KEY, &stored)?;
+
+    let links = calendar_links(&app);
+    let unlinked: Vec<&CalendarEvent> = events.iter().filter(|event| !links.contains_key(&event.id)).collect();
+    if !unlinked.is_empty() {
+        let mut recordings = Vec::new();
+        for meeting in fetch_meetings().await? {
+            if links.values().any(|linked| *linked == meeting.id) {
+                continue;
+            }
+            if let Some(started_at) = meeting_start(&app, &meeting.id).await {
+                recordings.push((meeting.id, started_at));
+            }
+        }
+
+        let window_secs = CALENDAR_MATCH_WINDOW_MINUTES * 60;
+        let mut linked = 0;
+        for event in unlinked {
+            let Some(start) = parse_event_time(&event.start_iso) else { continue };
+            let closest = recordings
+                .iter()
+                .enumerate()
+                .map(|(index, (_, started_at))| (index, (*started_at - start).num_seconds().abs()))
+                .filter(|(_, distance)| *distance <= window_secs)
+                .min_by_key(|(_, distance)| *distance);
+            if let Some((index, _)) = closest {
+                let (meeting_id, _) = recordings.remove(index);
+                api_link_meeting_to_calendar_event(app.clone(), meeting_id, event.id.clone()).await?;
+                linked += 1;
+            }
+        }
+        log_info!("Linked {} imported calendar events to recordings", linked);
+    }
+
+    log_info!("Imported {} calendar events", events.len());
+    Ok(events.len())
+}
+
+/// Links a recording to a calendar event, replacing any earlier link for either of them.
+#[tauri::command]
+pub async fn api_link_meeting_to_calendar_event<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    event_id: String,
+) -> Result<(), String> {
+    let events: Vec<CalendarEvent> = store::get_value(&app, CALENDAR_EVENTS_KEY).unwrap_or_default();
+    if !events.iter().any(|event| event.id == event_id) {
+        return Err(format!("Calendar event {} not found", event_id));
+    }
+
+    let mut links = calendar_links(&app);
+    links.retain(|_, linked| *linked != meeting_id);
+    links.insert(event_id.clone(), meeting_id.clone());
+    store::set_value(&app, CALENDAR_LINKS_KEY, &links)?;
+    log_info!("Linked meeting {} to calendar event {}", meeting_id, event_id);
+    Ok(())
+}
+
+/// Calendar events from the last `since_days` days that have started but have no linked recording.
+#[tauri::command]
+pub async fn api_get_unrecorded_meetings<R: Runtime>(
+    app: AppHandle<R>,
+    since_days: u32,
+) -> Result<Vec<CalendarEvent>, String> {
+    let now = chrono::Utc::now();
+    let cutoff = now - chrono::Duration::days(since_days as i64);
+    let links = calendar_links(&app);
+    let events: Vec<CalendarEvent> = store::get_value(&app, CALENDAR_EVENTS_KEY).unwrap_or_default();
+
+    let mut unrecorded: Vec<(DateTime<chrono::Utc>, CalendarEvent)> = events
+        .into_iter()
+        .filter(|event| !links.contains_key(&event.id))
+        .filter_map(|event| parse_event_time(&event.start_iso).map(|start| (start, event)))
+        .filter(|(start, _)| *start >= cutoff && *start <= now)
+        .collect();
+    unrecorded.sort_by_key(|(start, _)| *start);
+    Ok(unrecorded.into_iter().map(|(_, event)| event).collect())
+}
+
+#[derive(Debug, Serialize)]
+pub struct SortResult {
+    pub total_segments: usize,
+    pub moved_segments: usize,
+    pub was_already_sorted: bool,
+}
+
+// Indices of `transcripts` in start-time order; fails on the first unparseable timestamp
+fn timestamp_order(transcripts: &[Transcript]) -> anyhow::Result<Vec<usize>> {
+    let mut keyed = transcripts
+        .iter()
+        .enumerate()
+        .map(|(index, transcript)| parse_timestamp_seconds(&transcript.timestamp).map(|(t0, _)| (t0, index)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    // Stable, so segments starting at the same time keep their saved order
+    keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+    Ok(keyed.into_iter().map(|(_, index)| index).collect())
+}
+
+/// Puts segments that arrived out of order back in speaking order. Meetings that are
+/// already sorted are left untouched, so running this twice changes nothing.
+#[tauri::command]
+pub async fn api_sort_transcript_by_timestamp(meeting_id: String) -> Result<SortResult, String> {
+    let meeting = fetch_meeting(&meeting_id).await?;
+    let total_segments = meeting.transcripts.len();
+
+    let order = timestamp_order(&meeting.transcripts)
+        .map_err(|e| format!("Cannot sort transcript for meeting {}: {}", meeting_id, e))?;
+    let moved_segments = order
+        .iter()
+        .enumerate()
+        .filter(|(position, original)| position != *original)
+        .count();
+    if moved_segments == 0 {
+        return Ok(SortResult { total_segments, moved_segments, was_already_sorted: true });
+    }
+
+    let sorted = order
+        .into_iter()
+        .map(|index| meeting.transcripts[index].clone())
+        .collect();
+    replace_transcripts(&meeting_id, sorted).await?;
+    log_info!("Reordered {} of {} transcript segments for meeting {}", moved_segments, total_segments, meeting_id);
+    Ok(SortResult { total_segments, moved_segments, was_already_sorted: false })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript(id: &str, timestamp: &str) -> Transcript {
+        Transcript { id: id.to_string(), text: format!("segment {}", id), timestamp: timestamp.to_string() }
+    }
+
+    fn reorder(transcripts: &[Transcript]) -> Vec<Transcript> {
+        timestamp_order(transcripts).unwrap().into_iter().map(|index| transcripts[index].clone()).collect()
+    }
+
+    fn ids(transcripts: &[Transcript]) -> Vec<&str> {
+        transcripts.iter().map(|t| t.id.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_every_timestamp_format_the_app_writes() {
+        assert_eq!(parse_timestamp_seconds("1.5 - 3.0").unwrap(), (1.5, 3.0));
+        assert_eq!(parse_timestamp_seconds("12-14").unwrap(), (12.0, 14.0));
+        assert_eq!(parse_timestamp_seconds("  0.25 -   7.125 ").unwrap(), (0.25, 7.125));
+        assert_eq!(parse_timestamp_seconds("4.0 - 4.0").unwrap(), (4.0, 4.0));
+    }
+
+    #[test]
+    fn rejects_malformed_timestamps() {
+        for timestamp in ["", "1.0", "1.0 -", "- 2.0", "a - b", "00:01 - 00:02", "3.0 - 2.0", "NaN - 1.0", "inf - inf"] {
+            assert!(parse_timestamp_seconds(timestamp).is_err(), "{:?} should not parse", timestamp);
+        }
+    }
+
+    #[test]
+    fn sorting_is_idempotent() {
+        let transcripts = vec![
+            transcript("c", "20.0 - 25.0"),
+            transcript("a", "0.0 - 4.5"),
+            transcript("b", "5.0 - 9.0"),
+            transcript("d", "20.0 - 21.0"),
+        ];
+        let once = reorder(&transcripts);
+        assert_eq!(ids(&once), ["a", "b", "c", "d"]);
+        // A second pass finds nothing to move
+        assert_eq!(timestamp_order(&once).unwrap(), vec![0, 1, 2, 3]);
+        assert_eq!(ids(&reorder(&once)), ids(&once));
+    }
+
+    #[test]
+    fn sorts_mixed_formats_numerically() {
+        // As strings "10" sorts before "9.5"; as numbers it doesn't
+        let transcripts = vec![
+            transcript("late", "10 - 12"),
+            transcript("early", "9.5-10.0"),
+            transcript("first", " 2.25 - 3 "),
+        ];
+        assert_eq!(ids(&reorder(&transcripts)), ["first", "early", "late"]);
+    }
+
+    #[test]
+    fn sorting_fails_on_a_malformed_timestamp() {
+        let transcripts = vec![transcript("ok", "1.0 - 2.0"), transcript("bad", "soon")];
+        let error = timestamp_order(&transcripts).unwrap_err().to_string();
+        assert!(error.contains("soon"), "{}", error);
+    }
+
+    #[test]
+    fn csv_export_escapes_commas_and_quotes() {
+        let meeting = MeetingDetails {
+            id: "m1".to_string(),
+            title: r#"Q3 "planning", part 1"#.to_string(),
+            created_at: "2024-05-01T10:00:00Z".to_string(),
+            updated_at: "2024-05-01T11:00:00Z".to_string(),
+            transcripts: vec![
+                transcript("1", "0.0 - 4.0"),
+                Transcript { id: "2".to_string(), text: "Yes, \"agreed\"\nnext".to_string(), timestamp: "4.0 - 9.0".to_string() },
+            ],
+        };
+        let names = HashMap::from([("t1".to_string(), "Sales, EMEA".to_string())]);
+        let tags = resolve_tag_names(&["t1".to_string(), "q3".to_string()], &names);
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(csv_header(true)).unwrap();
+        writer.write_record(csv_record(&meeting, 42, &tags, true)).unwrap();
+        let output = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+
+        let row = output.lines().nth(1).unwrap();
+        assert!(row.starts_with(r#"m1,"Q3 ""planning"", part 1",2024-05-01T10:00:00Z,"#), "{}", row);
+        assert!(row.contains(r#","Sales, EMEA;q3","#), "{}", row);
+        // The transcript stays on one physical line
+        assert_eq!(output.lines().count(), 2, "{}", output);
+
+        let mut reader = csv::Reader::from_reader(output.as_bytes());
+        let record = reader.records().next().unwrap().unwrap();
+        assert_eq!(&record[1], meeting.title);
+        assert_eq!(&record[7], "Sales, EMEA;q3");
+        assert_eq!(&record[8], r#"segment 1\nYes, "agreed"\nnext"#);
+    }
+
+    #[test]
+    fn redacts_webhook_urls_alongside_other_secrets() {
+        let mut settings = serde_json::json!({
+            "slackWebhookUrl": "https://hooks.slack.com/services/T000/B000/XXXX",
+            "proxy": { "password": "hunter2", "host": "proxy.local" },
+            "theme": "dark",
+        });
+        redact_secrets(&mut settings);
+        assert_eq!(settings["slackWebhookUrl"], "[REDACTED]");
+        assert_eq!(settings["proxy"]["password"], "[REDACTED]");
+        assert_eq!(settings["proxy"]["host"], "proxy.local");
+        assert_eq!(settings["theme"], "dark");
+    }
+
+    #[test]
+    fn denoise_with_no_noise_reconstructs_the_input() {
+        // Zero noise power leaves every bin untouched, so overlap-add must give back the signal exactly
+        let samples: Vec<f32> = (0..10_000)
+            .flat_map(|n| [(n as f32 * 0.013).sin() * 0.5, (n as f32 * 0.007).cos() * 0.25])
+            .collect();
+        let mut reported = Vec::new();
+        let denoised = denoise_samples(&samples, 2, 0.0, |percent| reported.push(percent)).unwrap();
+
+        assert_eq!(denoised.len(), samples.len());
+        let max_error = samples.iter().zip(&denoised).map(|(a, b)| (a - b).abs()).fold(0.0f32, f32::max);
+        assert!(max_error < 1e-4, "max reconstruction error {}", max_error);
+        assert_eq!(reported.last(), Some(&100));
+    }
+}
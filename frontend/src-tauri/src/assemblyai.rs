@@ -0,0 +1,218 @@
+// AssemblyAI real-time transcription over WebSocket, for lower-latency captions than chunked HTTP requests
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+pub const DEFAULT_REALTIME_URL: &str = "wss://api.assemblyai.com/v2/realtime/ws";
+// Overrides the endpoint, e.g. for a regional deployment or a local mock
+pub const REALTIME_URL_KEY: &str = "assemblyAiRealtimeUrl";
+// AssemblyAI recommends 100-450 ms of audio per message
+const FRAME_MS: u32 = 100;
+
+#[derive(Debug, Clone)]
+pub enum AssemblyAiEvent {
+    Partial { text: String, start_secs: f32, end_secs: f32 },
+    Final { text: String, start_secs: f32, end_secs: f32 },
+}
+
+#[derive(Debug, Deserialize)]
+struct RealtimeMessage {
+    message_type: String,
+    #[serde(default)]
+    text: String,
+    // Milliseconds from the start of the session
+    #[serde(default)]
+    audio_start: u64,
+    #[serde(default)]
+    audio_end: u64,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+pub struct AssemblyAiStreamer {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    frame_samples: usize,
+    pending: Vec<f32>,
+}
+
+impl AssemblyAiStreamer {
+    /// Opens a real-time session at `url` for mono audio at `sample_rate` Hz.
+    pub async fn connect(url: &str, api_key: &str, sample_rate: u32) -> Result<Self> {
+        if api_key.trim().is_empty() {
+            return Err(anyhow!("Missing ASSEMBLYAI_API_KEY"));
+        }
+        let mut request = format!("{}?sample_rate={}", url, sample_rate).into_client_request()?;
+        request
+            .headers_mut()
+            .insert("Authorization", api_key.parse().context("Invalid AssemblyAI API key")?);
+        let (socket, _) = tokio_tungstenite::connect_async(request)
+            .await
+            .context("Failed to connect to AssemblyAI")?;
+
+        Ok(Self {
+            socket,
+            frame_samples: (sample_rate * FRAME_MS / 1000).max(1) as usize,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Buffers `samples` and sends every complete 100 ms frame as base64 16-bit PCM.
+    pub async fn send_chunk(&mut self, samples: &[f32]) -> Result<()> {
+        self.pending.extend_from_slice(samples);
+        while self.pending.len() >= self.frame_samples {
+            let frame: Vec<f32> = self.pending.drain(..self.frame_samples).collect();
+            self.send_frame(&frame).await?;
+        }
+        Ok(())
+    }
+
+    async fn send_frame(&mut self, frame: &[f32]) -> Result<()> {
+        let pcm: Vec<u8> = frame
+            .iter()
+            .flat_map(|&sample| ((sample.clamp(-1.0, 1.0) * 32767.0) as i16).to_le_bytes())
+            .collect();
+        let audio_data = base64::engine::general_purpose::STANDARD.encode(pcm);
+        self.socket
+            .send(Message::Text(json!({ "audio_data": audio_data }).to_string()))
+            .await
+            .context("Failed to send audio to AssemblyAI")
+    }
+
+    /// Waits for the next transcript. Returns None once the session has closed.
+    pub async fn next_event(&mut self) -> Result<Option<AssemblyAiEvent>> {
+        while let Some(message) = self.socket.next().await {
+            let text = match message.context("AssemblyAI connection failed")? {
+                Message::Text(text) => text,
+                Message::Close(_) => return Ok(None),
+                _ => continue,
+            };
+            let message: RealtimeMessage =
+                serde_json::from_str(&text).with_context(|| format!("Unexpected AssemblyAI message: {}", text))?;
+            if let Some(error) = message.error {
+                return Err(anyhow!("AssemblyAI error: {}", error));
+            }
+            let (start_secs, end_secs) = (message.audio_start as f32 / 1000.0, message.audio_end as f32 / 1000.0);
+            match message.message_type.as_str() {
+                "PartialTranscript" if !message.text.is_empty() => {
+                    return Ok(Some(AssemblyAiEvent::Partial { text: message.text, start_secs, end_secs }));
+                }
+                "FinalTranscript" if !message.text.is_empty() => {
+                    return Ok(Some(AssemblyAiEvent::Final { text: message.text, start_secs, end_secs }));
+                }
+                "SessionTerminated" => return Ok(None),
+                // SessionBegins and empty transcripts during silence
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    /// Sends any buffered audio, then asks AssemblyAI to finish the session.
+    /// Remaining final transcripts still arrive through `next_event`.
+    pub async fn terminate(&mut self) -> Result<()> {
+        if !self.pending.is_empty() {
+            let frame = std::mem::take(&mut self.pending);
+            self.send_frame(&frame).await?;
+        }
+        self.socket
+            .send(Message::Text(json!({ "terminate_session": true }).to_string()))
+            .await
+            .context("Failed to end AssemblyAI session")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+    #[tokio::test]
+    async fn streams_frames_and_reads_transcripts_from_mock_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/v2/realtime/ws", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut query = String::new();
+            let mut authorization = String::new();
+            let mut socket = tokio_tungstenite::accept_hdr_async(stream, |request: &Request, response: Response| {
+                query = request.uri().query().unwrap_or_default().to_string();
+                authorization = request.headers()["Authorization"].to_str().unwrap().to_string();
+                Ok(response)
+            })
+            .await
+            .unwrap();
+
+            let Some(Ok(Message::Text(frame))) = socket.next().await else { panic!("expected an audio frame") };
+            let frame: serde_json::Value = serde_json::from_str(&frame).unwrap();
+            let pcm = base64::engine::general_purpose::STANDARD
+                .decode(frame["audio_data"].as_str().unwrap())
+                .unwrap();
+
+            for reply in [
+                json!({ "message_type": "SessionBegins" }),
+                json!({ "message_type": "PartialTranscript", "text": "hello", "audio_start": 0, "audio_end": 500 }),
+                json!({ "message_type": "FinalTranscript", "text": "hello world", "audio_start": 0, "audio_end": 1200 }),
+            ] {
+                socket.send(Message::Text(reply.to_string())).await.unwrap();
+            }
+
+            // The buffered remainder is flushed as a short frame ahead of terminate_session
+            let mut trailing_frames = 0;
+            while let Some(Ok(Message::Text(message))) = socket.next().await {
+                if message.contains("terminate_session") {
+                    break;
+                }
+                trailing_frames += 1;
+            }
+            socket
+                .send(Message::Text(json!({ "message_type": "SessionTerminated" }).to_string()))
+                .await
+                .unwrap();
+            (query, authorization, pcm.len(), trailing_frames)
+        });
+
+        let mut streamer = AssemblyAiStreamer::connect(&url, "test-key", 16000).await.unwrap();
+        // One full 100 ms frame plus a remainder that stays buffered until terminate
+        streamer.send_chunk(&vec![0.5; 1700]).await.unwrap();
+
+        match streamer.next_event().await.unwrap() {
+            Some(AssemblyAiEvent::Partial { text, end_secs, .. }) => {
+                assert_eq!(text, "hello");
+                assert_eq!(end_secs, 0.5);
+            }
+            other => panic!("expected a partial transcript, got {:?}", other),
+        }
+        match streamer.next_event().await.unwrap() {
+            Some(AssemblyAiEvent::Final { text, end_secs, .. }) => {
+                assert_eq!(text, "hello world");
+                assert_eq!(end_secs, 1.2);
+            }
+            other => panic!("expected a final transcript, got {:?}", other),
+        }
+
+        streamer.terminate().await.unwrap();
+        assert!(streamer.next_event().await.unwrap().is_none());
+
+        let (query, authorization, pcm_len, trailing_frames) = server.await.unwrap();
+        assert_eq!(query, "sample_rate=16000");
+        assert_eq!(authorization, "test-key");
+        assert_eq!(pcm_len, 1600 * 2);
+        assert_eq!(trailing_frames, 1);
+    }
+
+    #[tokio::test]
+    async fn connect_fails_when_nothing_is_listening() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        drop(listener);
+        assert!(AssemblyAiStreamer::connect(&url, "test-key", 16000).await.is_err());
+    }
+}
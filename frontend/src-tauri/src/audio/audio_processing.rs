@@ -6,15 +6,28 @@ use realfft::RealFftPlanner;
 use rubato::{
     Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction,
 };
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::f64::consts::PI;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering};
 
 use super::encode::encode_single_audio; // Correct path to encode module
 
-pub fn normalize_v2(audio: &[f32]) -> Vec<f32> {
+/// Linear RMS and absolute peak of `audio`; both are 0.0 for an empty slice.
+pub fn rms_and_peak(audio: &[f32]) -> (f32, f32) {
+    if audio.is_empty() {
+        return (0.0, 0.0);
+    }
     let rms = (audio.iter().map(|&x| x * x).sum::<f32>() / audio.len() as f32).sqrt();
     let peak = audio
         .iter()
         .fold(0.0f32, |max, &sample| max.max(sample.abs()));
+    (rms, peak)
+}
+
+pub fn normalize_v2(audio: &[f32]) -> Vec<f32> {
+    let (rms, peak) = rms_and_peak(audio);
 
     // Return the original audio if it's completely silent
     if rms == 0.0 || peak == 0.0 {
@@ -49,9 +62,11 @@ pub fn normalize_v2(audio: &[f32]) -> Vec<f32> {
         .collect()
 }
 
+pub const SPECTRAL_WINDOW_SIZE: usize = 1600; // 16k sample rate - 100ms
+
 pub fn spectral_subtraction(audio: &[f32], d: f32) -> Result<Vec<f32>> {
     let mut real_planner = RealFftPlanner::<f32>::new();
-    let window_size = 1600; // 16k sample rate - 100ms
+    let window_size = SPECTRAL_WINDOW_SIZE;
     let r2c = real_planner.plan_fft_forward(window_size);
 
     let mut y = r2c.make_output_vec();
@@ -106,22 +121,315 @@ pub fn average_noise_spectrum(audio: &[f32]) -> f32 {
     total_sum / audio.len() as f32
 }
 
-pub fn audio_to_mono(audio: &[f32], channels: u16) -> Vec<f32> {
-    let mut mono_samples = Vec::with_capacity(audio.len() / channels as usize);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MonoDownmixStrategy {
+    /// Mean of all channels
+    #[default]
+    AveragePower,
+    LeftOnly,
+    RightOnly,
+    /// Mid `(L+R)/2` blended towards side `(L-R)/2` sample by sample, as the channels drift out of phase
+    MidSide,
+    /// `L-R`, which keeps whatever differs between the channels
+    KaraokeSub,
+}
+
+impl MonoDownmixStrategy {
+    fn to_u8(self) -> u8 {
+        match self {
+            MonoDownmixStrategy::AveragePower => 0,
+            MonoDownmixStrategy::LeftOnly => 1,
+            MonoDownmixStrategy::RightOnly => 2,
+            MonoDownmixStrategy::MidSide => 3,
+            MonoDownmixStrategy::KaraokeSub => 4,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => MonoDownmixStrategy::LeftOnly,
+            2 => MonoDownmixStrategy::RightOnly,
+            3 => MonoDownmixStrategy::MidSide,
+            4 => MonoDownmixStrategy::KaraokeSub,
+            _ => MonoDownmixStrategy::AveragePower,
+        }
+    }
+}
+
+// Per-sample step of the MidSide energy envelopes, a time constant of about 5 ms at 48 kHz
+const MID_SIDE_SMOOTHING: f32 = 1.0 / 256.0;
+
+// Strategy used by the capture callbacks; changes apply to the next audio buffer
+static DOWNMIX_STRATEGY: AtomicU8 = AtomicU8::new(0);
+
+pub fn current_downmix_strategy() -> MonoDownmixStrategy {
+    MonoDownmixStrategy::from_u8(DOWNMIX_STRATEGY.load(Ordering::Relaxed))
+}
+
+pub fn set_current_downmix_strategy(strategy: MonoDownmixStrategy) {
+    DOWNMIX_STRATEGY.store(strategy.to_u8(), Ordering::Relaxed);
+}
+
+#[cfg_attr(feature = "otel-tracing", tracing::instrument(level = "trace", skip(audio), fields(samples = audio.len())))]
+pub fn audio_to_mono(audio: &[f32], channels: u16, strategy: MonoDownmixStrategy) -> Vec<f32> {
+    // Every strategy is a no-op on audio that is already mono
+    if channels <= 1 {
+        return audio.to_vec();
+    }
+    let frames = audio.chunks(channels as usize);
+
+    match strategy {
+        MonoDownmixStrategy::AveragePower => frames
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect(),
+        MonoDownmixStrategy::LeftOnly => frames.map(|frame| frame[0]).collect(),
+        MonoDownmixStrategy::RightOnly => frames
+            .map(|frame| *frame.get(1).unwrap_or(&frame[0]))
+            .collect(),
+        MonoDownmixStrategy::MidSide => {
+            // Out-of-phase content cancels in the mid channel but survives in the side, so each
+            // sample is weighted by the recent energy of both
+            let (mut mid_energy, mut side_energy) = (0.0f32, 0.0f32);
+            frames
+                .map(|frame| {
+                    let left = frame[0];
+                    let right = *frame.get(1).unwrap_or(&left);
+                    let (mid, side) = ((left + right) / 2.0, (left - right) / 2.0);
+                    mid_energy += (mid * mid - mid_energy) * MID_SIDE_SMOOTHING;
+                    side_energy += (side * side - side_energy) * MID_SIDE_SMOOTHING;
+                    let total = mid_energy + side_energy;
+                    let mid_weight = if total > 0.0 { mid_energy / total } else { 1.0 };
+                    mid * mid_weight + side * (1.0 - mid_weight)
+                })
+                .collect()
+        }
+        MonoDownmixStrategy::KaraokeSub => frames
+            .map(|frame| {
+                let left = frame[0];
+                let right = *frame.get(1).unwrap_or(&left);
+                (left - right).clamp(-1.0, 1.0)
+            })
+            .collect(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FilterKind {
+    Highpass,
+    Lowpass,
+}
+
+// One second-order section, run in direct form II transposed
+#[derive(Debug, Clone, Copy)]
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    z1: f64,
+    z2: f64,
+}
+
+impl Biquad {
+    // Bilinear transform of the analog prototype, normalized so a0 == 1
+    fn butterworth(kind: FilterKind, sample_rate: u32, cutoff_hz: f32, q: f64) -> Self {
+        let w0 = 2.0 * PI * cutoff_hz as f64 / sample_rate as f64;
+        let cos_w0 = w0.cos();
+        let alpha = w0.sin() / (2.0 * q);
+        let a0 = 1.0 + alpha;
+
+        let (b0, b1, b2) = match kind {
+            FilterKind::Highpass => ((1.0 + cos_w0) / 2.0, -(1.0 + cos_w0), (1.0 + cos_w0) / 2.0),
+            FilterKind::Lowpass => ((1.0 - cos_w0) / 2.0, 1.0 - cos_w0, (1.0 - cos_w0) / 2.0),
+        };
+
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: -2.0 * cos_w0 / a0,
+            a2: (1.0 - alpha) / a0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    // First-order section used for the odd pole of odd-order filters
+    fn first_order(kind: FilterKind, sample_rate: u32, cutoff_hz: f32) -> Self {
+        let k = (PI * cutoff_hz as f64 / sample_rate as f64).tan();
+        let norm = 1.0 / (1.0 + k);
+        let (b0, b1) = match kind {
+            FilterKind::Highpass => (norm, -norm),
+            FilterKind::Lowpass => (k * norm, k * norm),
+        };
+
+        Self { b0, b1, b2: 0.0, a1: (k - 1.0) * norm, a2: 0.0, z1: 0.0, z2: 0.0 }
+    }
+
+    // RBJ cookbook shelf with slope 1; half of `gain_db` is reached at `freq_hz`
+    fn shelf(high: bool, sample_rate: u32, freq_hz: f32, gain_db: f32) -> Self {
+        let a = 10f64.powf(gain_db as f64 / 40.0);
+        let w0 = 2.0 * PI * freq_hz as f64 / sample_rate as f64;
+        let cos_w0 = w0.cos();
+        let alpha = w0.sin() / 2.0 * 2f64.sqrt();
+        let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+        // The low shelf is the high shelf with the sign of cos(w0) flipped
+        let sign = if high { 1.0 } else { -1.0 };
+
+        let b0 = a * ((a + 1.0) + sign * (a - 1.0) * cos_w0 + two_sqrt_a_alpha);
+        let b1 = -2.0 * sign * a * ((a - 1.0) + sign * (a + 1.0) * cos_w0);
+        let b2 = a * ((a + 1.0) + sign * (a - 1.0) * cos_w0 - two_sqrt_a_alpha);
+        let a0 = (a + 1.0) - sign * (a - 1.0) * cos_w0 + two_sqrt_a_alpha;
+        let a1 = 2.0 * sign * ((a - 1.0) - sign * (a + 1.0) * cos_w0);
+        let a2 = (a + 1.0) - sign * (a - 1.0) * cos_w0 - two_sqrt_a_alpha;
+
+        Self { b0: b0 / a0, b1: b1 / a0, b2: b2 / a0, a1: a1 / a0, a2: a2 / a0, z1: 0.0, z2: 0.0 }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0 * x + self.z1;
+        self.z1 = self.b1 * x - self.a1 * y + self.z2;
+        self.z2 = self.b2 * x - self.a2 * y;
+        y
+    }
+}
+
+/// Butterworth filter that keeps its state between calls, so consecutive chunks of one stream
+/// are filtered as if they were a single signal.
+#[derive(Debug, Clone)]
+pub struct ButterworthFilter {
+    sections: Vec<Biquad>,
+    cutoff_hz: f32,
+}
+
+impl ButterworthFilter {
+    fn new(kind: FilterKind, sample_rate: u32, cutoff_hz: f32, order: usize) -> Self {
+        let order = order.max(1);
+        // Keep the cutoff strictly inside (0, Nyquist) so the coefficients stay finite
+        let clamped_hz = cutoff_hz.clamp(1.0, sample_rate as f32 * 0.49);
+
+        // An order-N Butterworth is N/2 biquads with Q_k = 1 / (2 sin((2k+1)π / 2N)), plus a
+        // first-order section when N is odd
+        let mut sections: Vec<Biquad> = (0..order / 2)
+            .map(|k| {
+                let q = 1.0 / (2.0 * ((2 * k + 1) as f64 * PI / (2 * order) as f64).sin());
+                Biquad::butterworth(kind, sample_rate, clamped_hz, q)
+            })
+            .collect();
+        if order % 2 == 1 {
+            sections.push(Biquad::first_order(kind, sample_rate, clamped_hz));
+        }
+        Self { sections, cutoff_hz }
+    }
+
+    pub fn highpass(sample_rate: u32, cutoff_hz: f32, order: usize) -> Self {
+        Self::new(FilterKind::Highpass, sample_rate, cutoff_hz, order)
+    }
+
+    pub fn lowpass(sample_rate: u32, cutoff_hz: f32, order: usize) -> Self {
+        Self::new(FilterKind::Lowpass, sample_rate, cutoff_hz, order)
+    }
+
+    /// The cutoff this filter was built for, before clamping to the sample rate.
+    pub fn cutoff_hz(&self) -> f32 {
+        self.cutoff_hz
+    }
+
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        samples
+            .iter()
+            .map(|&sample| {
+                self.sections
+                    .iter_mut()
+                    .fold(sample as f64, |x, section| section.process(x)) as f32
+            })
+            .collect()
+    }
+}
+
+fn apply_butterworth(
+    samples: &[f32],
+    sample_rate: u32,
+    cutoff_hz: f32,
+    order: usize,
+    kind: FilterKind,
+) -> Vec<f32> {
+    ButterworthFilter::new(kind, sample_rate, cutoff_hz, order).process(samples)
+}
 
-    // Iterate over the audio slice in chunks, each containing `channels` samples
-    for chunk in audio.chunks(channels as usize) {
-        // Sum the samples from all channels in the current chunk
-        let sum: f32 = chunk.iter().sum();
+pub fn apply_highpass_filter(samples: &[f32], sample_rate: u32, cutoff_hz: f32, order: usize) -> Vec<f32> {
+    apply_butterworth(samples, sample_rate, cutoff_hz, order, FilterKind::Highpass)
+}
 
-        // Calculate the averagechannelsono sample
-        let mono_sample = sum / channels as f32;
+pub fn apply_lowpass_filter(samples: &[f32], sample_rate: u32, cutoff_hz: f32, order: usize) -> Vec<f32> {
+    apply_butterworth(samples, sample_rate, cutoff_hz, order, FilterKind::Lowpass)
+}
 
-        // Store the computed mono sample
-        mono_samples.push(mono_sample);
+fn apply_shelf(samples: &[f32], sample_rate: u32, shelf_freq_hz: f32, gain_db: f32, high: bool) -> Vec<f32> {
+    if gain_db == 0.0 {
+        return samples.to_vec();
     }
+    let shelf_freq_hz = shelf_freq_hz.clamp(1.0, sample_rate as f32 * 0.49);
+    let mut section = Biquad::shelf(high, sample_rate, shelf_freq_hz, gain_db);
+    samples.iter().map(|&sample| section.process(sample as f64) as f32).collect()
+}
+
+/// Boosts or cuts everything above `shelf_freq_hz` by `gain_db`, e.g. -6 dB at 8 kHz to tame sibilance.
+pub fn apply_high_shelf_filter(samples: &[f32], sample_rate: u32, shelf_freq_hz: f32, gain_db: f32) -> Vec<f32> {
+    apply_shelf(samples, sample_rate, shelf_freq_hz, gain_db, true)
+}
+
+/// Boosts or cuts everything below `shelf_freq_hz` by `gain_db`.
+pub fn apply_low_shelf_filter(samples: &[f32], sample_rate: u32, shelf_freq_hz: f32, gain_db: f32) -> Vec<f32> {
+    apply_shelf(samples, sample_rate, shelf_freq_hz, gain_db, false)
+}
 
-    mono_samples
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EqShelfConfig {
+    pub high_shelf_freq: f32,
+    pub high_shelf_gain_db: f32,
+    pub low_shelf_freq: f32,
+    pub low_shelf_gain_db: f32,
+}
+
+// Shelf EQ applied to each chunk before transcription, after the highpass
+static EQ_SHELF: std::sync::Mutex<Option<EqShelfConfig>> = std::sync::Mutex::new(None);
+
+pub fn eq_shelf() -> Option<EqShelfConfig> {
+    EQ_SHELF.lock().ok().and_then(|config| *config)
+}
+
+// Both gains at 0 dB turns the EQ off
+pub fn set_eq_shelf(config: EqShelfConfig) {
+    let active = config.high_shelf_gain_db != 0.0 || config.low_shelf_gain_db != 0.0;
+    if let Ok(mut guard) = EQ_SHELF.lock() {
+        *guard = active.then_some(config);
+    }
+}
+
+pub fn apply_eq_shelf(samples: &[f32], sample_rate: u32, config: &EqShelfConfig) -> Vec<f32> {
+    let low = apply_low_shelf_filter(samples, sample_rate, config.low_shelf_freq, config.low_shelf_gain_db);
+    apply_high_shelf_filter(&low, sample_rate, config.high_shelf_freq, config.high_shelf_gain_db)
+}
+
+pub const DEFAULT_HIGHPASS_CUTOFF_HZ: f32 = 100.0;
+// 48 dB/octave, so 50 Hz mains hum ends up more than 40 dB down at the default cutoff
+pub const HIGHPASS_ORDER: usize = 8;
+
+// Highpass applied to each chunk before transcription
+static HIGHPASS_ENABLED: AtomicBool = AtomicBool::new(false);
+static HIGHPASS_CUTOFF_HZ: AtomicU32 = AtomicU32::new(0x42C8_0000); // 100.0f32
+
+pub fn highpass_cutoff() -> Option<f32> {
+    HIGHPASS_ENABLED
+        .load(Ordering::Relaxed)
+        .then(|| f32::from_bits(HIGHPASS_CUTOFF_HZ.load(Ordering::Relaxed)))
+}
+
+pub fn set_highpass(enabled: bool, cutoff_hz: f32) {
+    HIGHPASS_CUTOFF_HZ.store(cutoff_hz.to_bits(), Ordering::Relaxed);
+    HIGHPASS_ENABLED.store(enabled, Ordering::Relaxed);
 }
 
 pub fn resample(input: &[f32], from_sample_rate: u32, to_sample_rate: u32) -> Result<Vec<f32>> {
@@ -149,6 +457,260 @@ pub fn resample(input: &[f32], from_sample_rate: u32, to_sample_rate: u32) -> Re
     Ok(waves_out.into_iter().next().unwrap())
 }
 
+const SILENCE_FRAME_MS: u32 = 20;
+
+/// Trims leading and trailing frames quieter than `threshold_db` (dBFS RMS).
+///
+/// Returns the range of samples to keep, always within `0..samples.len()`.
+/// Nothing is trimmed if the result would be shorter than `min_duration_secs`.
+pub fn trim_silence(samples: &[f32], sample_rate: u32, threshold_db: f32, min_duration_secs: f32) -> Range<usize> {
+    let frame_len = ((sample_rate * SILENCE_FRAME_MS) / 1000).max(1) as usize;
+    let threshold = 10f32.powf(threshold_db / 20.0);
+    let is_active = |frame: &[f32]| {
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+        rms >= threshold
+    };
+
+    let frames: Vec<&[f32]> = samples.chunks(frame_len).collect();
+    let (Some(first), Some(last)) = (
+        frames.iter().position(|f| is_active(f)),
+        frames.iter().rposition(|f| is_active(f)),
+    ) else {
+        // All silence: leave it for the user to decide
+        return 0..samples.len();
+    };
+
+    let start = first * frame_len;
+    let end = ((last + 1) * frame_len).min(samples.len());
+    if ((end - start) as f32 / sample_rate as f32) < min_duration_secs {
+        return 0..samples.len();
+    }
+
+    start..end
+}
+
+/// Number of 20 ms frames whose RMS reaches `threshold_db` (dBFS).
+pub fn count_speech_frames(samples: &[f32], sample_rate: u32, threshold_db: f32) -> usize {
+    let frame_len = ((sample_rate * SILENCE_FRAME_MS) / 1000).max(1) as usize;
+    let threshold = 10f32.powf(threshold_db / 20.0);
+    samples
+        .chunks(frame_len)
+        .filter(|frame| (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt() >= threshold)
+        .count()
+}
+
+/// Energy-based voice activity check: true if any 20 ms frame is louder than `threshold_db`.
+pub fn vad_is_speech(samples: &[f32], sample_rate: u32, threshold_db: f32) -> bool {
+    count_speech_frames(samples, sample_rate, threshold_db) > 0
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct VadConfig {
+    pub threshold_db: f32,
+    // 20 ms frames above the threshold needed before a chunk counts as speech
+    pub min_speech_frames: usize,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self { threshold_db: -50.0, min_speech_frames: 3 }
+    }
+}
+
+// Gate applied to each chunk before transcription; capture loads it from the store
+static VAD: std::sync::Mutex<Option<VadConfig>> = std::sync::Mutex::new(None);
+
+pub fn vad_config() -> VadConfig {
+    VAD.lock().ok().and_then(|config| *config).unwrap_or_default()
+}
+
+pub fn set_vad_config(config: VadConfig) {
+    if let Ok(mut guard) = VAD.lock() {
+        *guard = Some(config);
+    }
+}
+
+/// Applies `config` to a whole chunk; a chunk with fewer loud frames than required is treated as silence.
+pub fn chunk_has_speech(samples: &[f32], sample_rate: u32, config: &VadConfig) -> bool {
+    if config.min_speech_frames <= 1 {
+        return vad_is_speech(samples, sample_rate, config.threshold_db);
+    }
+    count_speech_frames(samples, sample_rate, config.threshold_db) >= config.min_speech_frames
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SilenceRemovalConfig {
+    pub enabled: bool,
+    pub threshold_db: f32,
+    pub min_silence_ms: u32,
+    pub padding_ms: u32,
+}
+
+impl Default for SilenceRemovalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_db: -45.0,
+            min_silence_ms: 700,
+            padding_ms: 200,
+        }
+    }
+}
+
+// Silence removal applied to each chunk before transcription; None until capture loads it
+static SILENCE_REMOVAL: std::sync::Mutex<Option<SilenceRemovalConfig>> = std::sync::Mutex::new(None);
+
+pub fn current_silence_removal() -> Option<SilenceRemovalConfig> {
+    SILENCE_REMOVAL
+        .lock()
+        .ok()
+        .and_then(|config| config.clone())
+        .filter(|config| config.enabled)
+}
+
+pub fn set_current_silence_removal(config: SilenceRemovalConfig) {
+    if let Ok(mut guard) = SILENCE_REMOVAL.lock() {
+        *guard = Some(config);
+    }
+}
+
+/// Drops silent stretches of at least `min_silence_ms`, keeping `padding_ms` either side of speech.
+///
+/// Returns the kept samples and, for each kept run, `(output_secs, original_secs)` of its first
+/// sample so times reported against the output can be mapped back with [`map_to_original_time`].
+pub fn remove_silence_segments(
+    samples: &[f32],
+    sample_rate: u32,
+    config: &SilenceRemovalConfig,
+) -> (Vec<f32>, Vec<(f32, f32)>) {
+    let frame_len = ((sample_rate * SILENCE_FRAME_MS) / 1000).max(1) as usize;
+    let threshold = 10f32.powf(config.threshold_db / 20.0);
+    let min_silence_frames = (config.min_silence_ms / SILENCE_FRAME_MS).max(1) as usize;
+    let padding = (sample_rate as u64 * config.padding_ms as u64 / 1000) as usize;
+
+    let active: Vec<bool> = samples
+        .chunks(frame_len)
+        .map(|frame| (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt() >= threshold)
+        .collect();
+
+    // Sample ranges of silent runs long enough to cut, shrunk by the padding on both sides
+    let mut cuts = Vec::new();
+    let mut frame = 0;
+    while frame < active.len() {
+        if active[frame] {
+            frame += 1;
+            continue;
+        }
+        let run_start = frame;
+        while frame < active.len() && !active[frame] {
+            frame += 1;
+        }
+        if frame - run_start < min_silence_frames {
+            continue;
+        }
+        // Leading and trailing silence only needs padding on the side facing speech
+        let start = if run_start == 0 { 0 } else { run_start * frame_len + padding };
+        let end = if frame == active.len() {
+            samples.len()
+        } else {
+            (frame * frame_len).saturating_sub(padding)
+        };
+        if start < end {
+            cuts.push((start, end));
+        }
+    }
+
+    let mut output = Vec::with_capacity(samples.len());
+    let mut mapping = Vec::new();
+    let mut keep_from = 0;
+    for (start, end) in cuts.into_iter().chain(std::iter::once((samples.len(), samples.len()))) {
+        if start > keep_from {
+            mapping.push((
+                output.len() as f32 / sample_rate as f32,
+                keep_from as f32 / sample_rate as f32,
+            ));
+            output.extend_from_slice(&samples[keep_from..start]);
+        }
+        keep_from = end;
+    }
+    (output, mapping)
+}
+
+/// Maps a time in the output of [`remove_silence_segments`] back to the original audio.
+pub fn map_to_original_time(mapping: &[(f32, f32)], output_secs: f32) -> f32 {
+    mapping
+        .iter()
+        .rev()
+        .find(|(output_start, _)| *output_start <= output_secs)
+        .map(|(output_start, original_start)| original_start + (output_secs - output_start))
+        .unwrap_or(output_secs)
+}
+
+// Reads any PCM or float WAV as interleaved f32 samples in [-1.0, 1.0]
+pub fn read_wav_file(path: &Path) -> Result<(hound::WavSpec, Vec<f32>)> {
+    let mut reader = hound::WavReader::open(path)?;
+    let spec = reader.spec();
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<Vec<_>, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|s| s as f32 / scale))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+    };
+    Ok((spec, samples))
+}
+
+/// Reads the frames between `start_secs` and `end_secs` without loading the rest of the file.
+pub fn read_wav_range(path: &Path, start_secs: f32, end_secs: f32) -> Result<(hound::WavSpec, Vec<f32>)> {
+    let spec = hound::WavReader::open(path)?.spec();
+    let start_frame = (start_secs.max(0.0) * spec.sample_rate as f32) as u32;
+    let end_frame = ((end_secs.max(0.0) * spec.sample_rate as f32) as u32).max(start_frame);
+    read_wav_frames(path, start_frame, end_frame - start_frame)
+}
+
+/// Reads up to `frame_count` frames starting at `start_frame`; fewer come back near the end of the file.
+pub fn read_wav_frames(path: &Path, start_frame: u32, frame_count: u32) -> Result<(hound::WavSpec, Vec<f32>)> {
+    let mut reader = hound::WavReader::open(path)?;
+    let spec = reader.spec();
+    let total_frames = reader.duration();
+    let start_frame = start_frame.min(total_frames);
+    let end_frame = start_frame.saturating_add(frame_count).min(total_frames);
+    reader.seek(start_frame)?;
+
+    let count = (end_frame - start_frame) as usize * spec.channels as usize;
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().take(count).collect::<Result<Vec<_>, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .take(count)
+                .map(|s| s.map(|s| s as f32 / scale))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+    };
+    Ok((spec, samples))
+}
+
+// Writes interleaved f32 samples as a 32-bit float WAV
+pub fn write_wav_file(path: &Path, samples: &[f32], sample_rate: u32, channels: u16) -> Result<()> {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut writer = hound::WavWriter::create(path, spec)?;
+    for &sample in samples {
+        writer.write_sample(sample)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
 pub fn write_audio_to_file(
     audio: &[f32],
     sample_rate: u32,
@@ -175,3 +737,169 @@ pub fn write_audio_to_file(
     }
     Ok(file_path_clone)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interleave(left: &[f32], right: &[f32]) -> Vec<f32> {
+        left.iter().zip(right).flat_map(|(&l, &r)| [l, r]).collect()
+    }
+
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+            assert!((a - e).abs() < 1e-3, "sample {}: {} != {}", i, a, e);
+        }
+    }
+
+    #[test]
+    fn mono_input_passes_through_every_strategy() {
+        let input = [0.1, -0.2, 0.3];
+        for strategy in [
+            MonoDownmixStrategy::AveragePower,
+            MonoDownmixStrategy::LeftOnly,
+            MonoDownmixStrategy::RightOnly,
+            MonoDownmixStrategy::MidSide,
+            MonoDownmixStrategy::KaraokeSub,
+        ] {
+            assert_eq!(audio_to_mono(&input, 1, strategy), input.to_vec());
+        }
+    }
+
+    #[test]
+    fn simple_strategies_pick_or_combine_channels() {
+        let stereo = interleave(&[0.5, 0.2, -0.4], &[0.1, -0.2, 0.8]);
+        assert_close(&audio_to_mono(&stereo, 2, MonoDownmixStrategy::AveragePower), &[0.3, 0.0, 0.2]);
+        assert_close(&audio_to_mono(&stereo, 2, MonoDownmixStrategy::LeftOnly), &[0.5, 0.2, -0.4]);
+        assert_close(&audio_to_mono(&stereo, 2, MonoDownmixStrategy::RightOnly), &[0.1, -0.2, 0.8]);
+        // Clamped to full scale
+        assert_close(&audio_to_mono(&stereo, 2, MonoDownmixStrategy::KaraokeSub), &[0.4, 0.4, -1.0]);
+    }
+
+    #[test]
+    fn mid_side_follows_phase_within_one_buffer() {
+        let signal = tone(440.0, 48_000, 9_600);
+        let inverted: Vec<f32> = signal.iter().map(|s| -s).collect();
+        // First half in phase, second half out of phase
+        let right: Vec<f32> = signal[..4_800].iter().chain(&inverted[4_800..]).copied().collect();
+        let mono = audio_to_mono(&interleave(&signal, &right), 2, MonoDownmixStrategy::MidSide);
+
+        // Away from the switch, in-phase audio comes through as mid and out-of-phase as side
+        assert_close(&mono[1_000..4_800], &signal[1_000..4_800]);
+        assert_close(&mono[7_000..], &signal[7_000..]);
+    }
+
+    #[test]
+    fn mid_side_blends_partly_correlated_channels() {
+        let left = tone(440.0, 48_000, 4_800);
+        let right = vec![0.0; left.len()];
+        let mono = audio_to_mono(&interleave(&left, &right), 2, MonoDownmixStrategy::MidSide);
+        // Mid and side are equal here, so the blend is half of the left channel
+        let expected: Vec<f32> = left.iter().map(|s| s / 2.0).collect();
+        assert_close(&mono[1_000..], &expected[1_000..]);
+    }
+
+    fn tone(freq: f32, sample_rate: u32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|n| (2.0 * std::f32::consts::PI * freq * n as f32 / sample_rate as f32).sin())
+            .collect()
+    }
+
+    // Amplitude of the `freq` component, by correlating against sine and cosine
+    fn amplitude_at(samples: &[f32], freq: f32, sample_rate: u32) -> f32 {
+        let (mut re, mut im) = (0.0f64, 0.0f64);
+        for (n, &sample) in samples.iter().enumerate() {
+            let phase = 2.0 * std::f64::consts::PI * freq as f64 * n as f64 / sample_rate as f64;
+            re += sample as f64 * phase.cos();
+            im += sample as f64 * phase.sin();
+        }
+        (2.0 * (re * re + im * im).sqrt() / samples.len() as f64) as f32
+    }
+
+    fn gain_db(output: &[f32], input: &[f32], freq: f32, sample_rate: u32) -> f32 {
+        20.0 * (amplitude_at(output, freq, sample_rate) / amplitude_at(input, freq, sample_rate)).log10()
+    }
+
+    #[test]
+    fn highpass_removes_hum_across_chunks() {
+        let sample_rate = 48_000;
+        // Whole seconds so both tones complete whole cycles in the measured span
+        let hum = tone(50.0, sample_rate, sample_rate as usize * 3);
+        let speech = tone(1000.0, sample_rate, hum.len());
+        let input: Vec<f32> = hum.iter().zip(&speech).map(|(h, s)| 0.5 * h + 0.5 * s).collect();
+
+        let mut filter = ButterworthFilter::highpass(sample_rate, DEFAULT_HIGHPASS_CUTOFF_HZ, HIGHPASS_ORDER);
+        let output: Vec<f32> = input.chunks(4_800).flat_map(|chunk| filter.process(chunk)).collect();
+
+        // Skip the first second while the filter settles
+        let settled = sample_rate as usize..output.len();
+        let hum_db = gain_db(&output[settled.clone()], &input[settled.clone()], 50.0, sample_rate);
+        let speech_db = gain_db(&output[settled.clone()], &input[settled], 1000.0, sample_rate);
+        assert!(hum_db < -40.0, "50 Hz only attenuated by {:.1} dB", hum_db);
+        assert!(speech_db.abs() < 0.5, "1 kHz changed by {:.2} dB", speech_db);
+    }
+
+    #[test]
+    fn eq_high_shelf_sets_gain_above_shelf_frequency() {
+        let sample_rate = 48_000;
+        let eq = EqShelfConfig { high_shelf_freq: 4_000.0, high_shelf_gain_db: -6.0, low_shelf_freq: 200.0, low_shelf_gain_db: 0.0 };
+        let treble = tone(10_000.0, sample_rate, sample_rate as usize);
+        let bass = tone(300.0, sample_rate, sample_rate as usize);
+
+        // Skip the first 100 ms while the filter settles
+        let settled = 4_800..treble.len();
+        let treble_db = gain_db(&apply_eq_shelf(&treble, sample_rate, &eq)[settled.clone()], &treble[settled.clone()], 10_000.0, sample_rate);
+        let bass_db = gain_db(&apply_eq_shelf(&bass, sample_rate, &eq)[settled.clone()], &bass[settled], 300.0, sample_rate);
+        assert!((treble_db + 6.0).abs() < 0.5, "10 kHz gain was {:.2} dB", treble_db);
+        assert!(bass_db.abs() < 0.5, "300 Hz gain was {:.2} dB", bass_db);
+    }
+
+    #[test]
+    fn eq_low_shelf_boosts_below_shelf_frequency() {
+        let sample_rate = 48_000;
+        let eq = EqShelfConfig { high_shelf_freq: 8_000.0, high_shelf_gain_db: 0.0, low_shelf_freq: 400.0, low_shelf_gain_db: 4.0 };
+        let bass = tone(60.0, sample_rate, sample_rate as usize);
+        let settled = 9_600..bass.len();
+        let bass_db = gain_db(&apply_eq_shelf(&bass, sample_rate, &eq)[settled.clone()], &bass[settled], 60.0, sample_rate);
+        assert!((bass_db - 4.0).abs() < 0.5, "60 Hz gain was {:.2} dB", bass_db);
+    }
+
+    #[test]
+    fn odd_order_highpass_is_3db_down_at_cutoff() {
+        let sample_rate = 48_000;
+        for order in [3, 5] {
+            let input = tone(1_000.0, sample_rate, sample_rate as usize);
+            let output = apply_highpass_filter(&input, sample_rate, 1_000.0, order);
+            let settled = 4_800..input.len();
+            let cutoff_db = gain_db(&output[settled.clone()], &input[settled], 1_000.0, sample_rate);
+            assert!((cutoff_db + 3.01).abs() < 0.1, "order {} gain at cutoff was {:.2} dB", order, cutoff_db);
+        }
+    }
+
+    #[test]
+    fn trim_silence_keeps_the_loud_middle_within_bounds() {
+        let sample_rate = 16_000;
+        // 0.5 s silence, 1.5 s tone, then a 0.3 s tail that isn't a whole number of frames
+        let mut samples = vec![0.0; 8_000];
+        samples.extend(tone(440.0, sample_rate, 24_000));
+        samples.extend(vec![0.0; 4_810]);
+
+        let kept = trim_silence(&samples, sample_rate, -40.0, 1.0);
+        assert_eq!(kept.start, 8_000);
+        assert!(kept.end <= samples.len());
+        assert!((32_000..=32_320).contains(&kept.end), "kept {:?}", kept);
+
+        assert_eq!(trim_silence(&samples, sample_rate, -40.0, 2.0), 0..samples.len());
+        assert_eq!(trim_silence(&[0.0; 1_000], sample_rate, -40.0, 0.0), 0..1_000);
+    }
+
+    #[test]
+    fn chunked_filtering_matches_one_pass() {
+        let input = tone(80.0, 16_000, 8_000);
+        let whole = apply_highpass_filter(&input, 16_000, 100.0, HIGHPASS_ORDER);
+        let mut filter = ButterworthFilter::highpass(16_000, 100.0, HIGHPASS_ORDER);
+        let chunked: Vec<f32> = input.chunks(1_000).flat_map(|chunk| filter.process(chunk)).collect();
+        assert_eq!(whole, chunked);
+    }
+}
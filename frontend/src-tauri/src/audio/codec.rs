@@ -0,0 +1,136 @@
+// Lossless FLAC encoding for stored recordings; speech typically shrinks to 30–50% of the WAV size
+use anyhow::{anyhow, Result};
+use flacenc::component::BitRepr;
+use flacenc::error::Verify;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+const FLAC_BITS_PER_SAMPLE: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    Wav,
+    Flac,
+}
+
+impl AudioFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Flac => "flac",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FlacCompressionLevel {
+    Fast,
+    #[default]
+    Default,
+    Best,
+}
+
+impl FlacCompressionLevel {
+    /// Maps a libFLAC-style 0–8 level onto the nearest preset.
+    pub fn from_level(level: u8) -> Self {
+        match level {
+            0..=2 => FlacCompressionLevel::Fast,
+            3..=6 => FlacCompressionLevel::Default,
+            _ => FlacCompressionLevel::Best,
+        }
+    }
+
+    pub fn level(&self) -> u8 {
+        match self {
+            FlacCompressionLevel::Fast => 1,
+            FlacCompressionLevel::Default => 5,
+            FlacCompressionLevel::Best => 8,
+        }
+    }
+
+    // Mirrors the block size and LPC order libFLAC uses at levels 1, 5 and 8
+    fn encoder_config(&self) -> flacenc::config::Encoder {
+        let mut config = flacenc::config::Encoder::default();
+        match self {
+            FlacCompressionLevel::Fast => {
+                config.block_size = 1152;
+                config.subframe_coding.use_lpc = false;
+            }
+            FlacCompressionLevel::Default => {
+                config.block_size = 4096;
+                config.subframe_coding.qlpc.lpc_order = 8;
+            }
+            FlacCompressionLevel::Best => {
+                config.block_size = 4096;
+                config.subframe_coding.qlpc.lpc_order = 12;
+            }
+        }
+        config
+    }
+}
+
+/// Encodes interleaved f32 samples as 16-bit FLAC with the default compression level.
+pub fn encode_to_flac(samples: &[f32], sample_rate: u32, channels: u16) -> Result<Vec<u8>> {
+    encode_to_flac_with_level(samples, sample_rate, channels, FlacCompressionLevel::default())
+}
+
+pub fn encode_to_flac_with_level(
+    samples: &[f32],
+    sample_rate: u32,
+    channels: u16,
+    level: FlacCompressionLevel,
+) -> Result<Vec<u8>> {
+    if channels == 0 {
+        return Err(anyhow!("Cannot encode FLAC with zero channels"));
+    }
+
+    let scale = ((1 << (FLAC_BITS_PER_SAMPLE - 1)) - 1) as f32;
+    let pcm: Vec<i32> = samples
+        .iter()
+        .map(|&sample| (sample.clamp(-1.0, 1.0) * scale) as i32)
+        .collect();
+
+    let config = level
+        .encoder_config()
+        .into_verified()
+        .map_err(|(_, e)| anyhow!("Invalid FLAC encoder config: {:?}", e))?;
+    let block_size = config.block_size;
+    let source = flacenc::source::MemSource::from_samples(
+        &pcm,
+        channels as usize,
+        FLAC_BITS_PER_SAMPLE,
+        sample_rate as usize,
+    );
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, block_size)
+        .map_err(|e| anyhow!("FLAC encoding failed: {:?}", e))?;
+
+    let mut sink = flacenc::bitsink::ByteSink::new();
+    stream
+        .write(&mut sink)
+        .map_err(|e| anyhow!("Failed to serialize FLAC stream: {:?}", e))?;
+    Ok(sink.as_slice().to_vec())
+}
+
+// "fLaC", 4-byte block header, then 10 bytes of block/frame sizes before the 20-bit rate
+// and the 3-bit channel count
+fn read_streaminfo(path: &Path) -> Option<[u8; 21]> {
+    let mut bytes = [0u8; 21];
+    File::open(path).ok()?.read_exact(&mut bytes).ok()?;
+    (&bytes[..4] == b"fLaC").then_some(bytes)
+}
+
+/// Reads the sample rate from a FLAC file's STREAMINFO block.
+pub fn flac_sample_rate(path: &Path) -> Option<u32> {
+    let bytes = read_streaminfo(path)?;
+    let rate = ((bytes[18] as u32) << 12) | ((bytes[19] as u32) << 4) | ((bytes[20] as u32) >> 4);
+    (rate > 0).then_some(rate)
+}
+
+/// Reads the channel count from a FLAC file's STREAMINFO block.
+pub fn flac_channels(path: &Path) -> Option<u16> {
+    let bytes = read_streaminfo(path)?;
+    Some(((bytes[20] >> 1) & 0x07) as u16 + 1)
+}
@@ -1,4 +1,5 @@
-use super::audio_processing::audio_to_mono; 
+use super::audio_processing::{audio_to_mono, current_downmix_strategy};
+use super::noise_suppression::suppress_or_passthrough;
 use anyhow::{anyhow, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::StreamError;
@@ -9,24 +10,164 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
 use std::{fmt, thread};
-use tokio::sync::{broadcast, oneshot};
+use tokio::sync::{broadcast, oneshot, watch};
 lazy_static! {
     pub static ref LAST_AUDIO_CAPTURE: AtomicU64 = AtomicU64::new(
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
-            .as_secs()
+            .as_millis() as u64
     );
 }
 
-#[derive(Clone, Debug, PartialEq)]
+// Host tried before the platform default when opening devices; None means platform default only
+static PREFERRED_HOST: std::sync::Mutex<Option<cpal::HostId>> = std::sync::Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioHostInfo {
+    pub id: String,
+    pub name: String,
+    pub is_default: bool,
+    pub input_device_count: usize,
+    pub output_device_count: usize,
+}
+
+/// Every host API cpal was built with on this platform, with device counts.
+/// Hosts that fail to initialize (e.g. PulseAudio not running) report zero devices.
+pub fn list_audio_hosts() -> Vec<AudioHostInfo> {
+    let default_id = cpal::default_host().id();
+    cpal::available_hosts()
+        .into_iter()
+        .map(|id| {
+            let (input_device_count, output_device_count) = match cpal::host_from_id(id) {
+                Ok(host) => (
+                    host.input_devices().map(|d| d.count()).unwrap_or(0),
+                    host.output_devices().map(|d| d.count()).unwrap_or(0),
+                ),
+                Err(e) => {
+                    warn!("Audio host {} unavailable: {}", id.name(), e);
+                    (0, 0)
+                }
+            };
+            AudioHostInfo {
+                id: id.name().to_lowercase(),
+                name: id.name().to_string(),
+                is_default: id == default_id,
+                input_device_count,
+                output_device_count,
+            }
+        })
+        .collect()
+}
+
+/// Resolves an id from [`list_audio_hosts`] (case-insensitive) to an available host.
+pub fn parse_host_id(host_id: &str) -> Option<cpal::HostId> {
+    cpal::available_hosts()
+        .into_iter()
+        .find(|id| id.name().eq_ignore_ascii_case(host_id.trim()))
+}
+
+pub fn set_preferred_host(host: Option<cpal::HostId>) {
+    if let Ok(mut guard) = PREFERRED_HOST.lock() {
+        *guard = host;
+    }
+}
+
+fn preferred_host() -> Option<cpal::HostId> {
+    PREFERRED_HOST.lock().ok().and_then(|host| *host)
+}
+
+// Outputs are captured through loopback, so check output devices first and then
+// input-side monitors (PulseAudio monitor sources, ScreenCaptureKit)
+fn find_in_host(host: &cpal::Host, audio_device: &AudioDevice) -> Option<(cpal::Device, cpal::SupportedStreamConfig)> {
+    let named = |device: &cpal::Device| device.name().map_or(false, |name| name == audio_device.name);
+    match audio_device.device_type {
+        DeviceType::Input => host
+            .input_devices()
+            .ok()?
+            .find(named)
+            .and_then(|device| device.default_input_config().ok().map(|config| (device, config))),
+        DeviceType::Output => host
+            .output_devices()
+            .ok()
+            .and_then(|mut devices| devices.find(named))
+            .and_then(|device| device.default_output_config().ok().map(|config| (device, config)))
+            .or_else(|| {
+                host.input_devices()
+                    .ok()?
+                    .find(named)
+                    .and_then(|device| device.default_input_config().ok().map(|config| (device, config)))
+            }),
+    }
+}
+
+/// Failures opening, running or stopping a capture stream, typed so callers can pick a recovery path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AudioError {
+    DeviceNotFound { name: String },
+    PermissionDenied { device: String },
+    ConfigurationError { device: String, reason: String },
+    StreamError { device: String, error: String },
+    UnsupportedFormat { format: String },
+    DeviceDisconnected { name: String },
+}
+
+impl AudioError {
+    // cpal reports OS permission failures as generic backend errors, so sort them by message
+    fn configuration(device: &str, reason: impl fmt::Display) -> Self {
+        let reason = reason.to_string();
+        let lower = reason.to_lowercase();
+        if lower.contains("permission denied") || lower.contains("access denied") {
+            AudioError::PermissionDenied { device: device.to_string() }
+        } else if lower.contains("no longer available") || lower.contains("no longer valid") {
+            AudioError::DeviceDisconnected { name: device.to_string() }
+        } else {
+            AudioError::ConfigurationError { device: device.to_string(), reason }
+        }
+    }
+}
+
+impl fmt::Display for AudioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioError::DeviceNotFound { name } => write!(f, "Audio device not found: {}", name),
+            AudioError::PermissionDenied { device } => write!(f, "Permission denied for audio device {}", device),
+            AudioError::ConfigurationError { device, reason } => {
+                write!(f, "Failed to configure audio device {}: {}", device, reason)
+            }
+            AudioError::StreamError { device, error } => write!(f, "Audio stream error on {}: {}", device, error),
+            AudioError::UnsupportedFormat { format } => write!(f, "Unsupported sample format: {}", format),
+            AudioError::DeviceDisconnected { name } => write!(f, "Audio device disconnected: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for AudioError {}
+
+impl From<AudioError> for String {
+    fn from(error: AudioError) -> Self {
+        error.to_string()
+    }
+}
+
+// Milliseconds since the Unix epoch, the unit LAST_AUDIO_CAPTURE is kept in
+pub fn unix_time_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum AudioTranscriptionEngine {
     Deepgram,
     WhisperTiny,
     WhisperDistilLargeV3,
     WhisperLargeV3Turbo,
     WhisperLargeV3,
+    AssemblyAI,
 }
 
 impl fmt::Display for AudioTranscriptionEngine {
@@ -37,6 +178,7 @@ impl fmt::Display for AudioTranscriptionEngine {
             AudioTranscriptionEngine::WhisperDistilLargeV3 => write!(f, "WhisperLarge"),
             AudioTranscriptionEngine::WhisperLargeV3Turbo => write!(f, "WhisperLargeV3Turbo"),
             AudioTranscriptionEngine::WhisperLargeV3 => write!(f, "WhisperLargeV3"),
+            AudioTranscriptionEngine::AssemblyAI => write!(f, "AssemblyAI"),
         }
     }
 }
@@ -232,6 +374,80 @@ fn configure_linux_audio(host: &cpal::Host) -> Result<Vec<AudioDevice>> {
     Ok(devices)
 }
 
+#[cfg(target_os = "linux")]
+const PIPEWIRE_CACHE_TTL: Duration = Duration::from_secs(10);
+#[cfg(target_os = "linux")]
+const PIPEWIRE_LIST_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[cfg(target_os = "linux")]
+static PIPEWIRE_DEVICES: once_cell::sync::Lazy<std::sync::Mutex<Option<(std::time::Instant, Vec<AudioDevice>)>>> =
+    once_cell::sync::Lazy::new(|| std::sync::Mutex::new(None));
+
+// PipeWire-native sources that cpal's ALSA/Pulse hosts don't surface
+#[cfg(target_os = "linux")]
+async fn list_pipewire_devices() -> Result<Vec<AudioDevice>> {
+    if let Ok(cache) = PIPEWIRE_DEVICES.lock() {
+        if let Some((listed_at, devices)) = cache.as_ref() {
+            if listed_at.elapsed() < PIPEWIRE_CACHE_TTL {
+                return Ok(devices.clone());
+            }
+        }
+    }
+
+    // Async so a slow or stuck pw-cli doesn't tie up a runtime worker
+    let output = tokio::process::Command::new("pw-cli")
+        .args(["list-objects", "Node"])
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(PIPEWIRE_LIST_TIMEOUT, output)
+        .await
+        .map_err(|_| anyhow!("pw-cli did not respond within {:?}", PIPEWIRE_LIST_TIMEOUT))??;
+    if !output.status.success() {
+        return Err(anyhow!("pw-cli exited with {}", output.status));
+    }
+    let devices = parse_pipewire_nodes(&String::from_utf8_lossy(&output.stdout));
+
+    if let Ok(mut cache) = PIPEWIRE_DEVICES.lock() {
+        *cache = Some((std::time::Instant::now(), devices.clone()));
+    }
+    Ok(devices)
+}
+
+// Objects start with an `id N, type ...` line followed by indented `key = "value"` properties
+#[cfg(target_os = "linux")]
+fn parse_pipewire_nodes(output: &str) -> Vec<AudioDevice> {
+    let mut devices = Vec::new();
+    let mut node_name: Option<String> = None;
+    let mut is_source = false;
+
+    let mut finish_node = |name: Option<String>, is_source: bool| {
+        if let (Some(name), true) = (name, is_source) {
+            devices.push(AudioDevice::new(name, DeviceType::Input));
+        }
+    };
+
+    for line in output.lines() {
+        let line = line.trim();
+        if line.starts_with("id ") {
+            finish_node(node_name.take(), is_source);
+            is_source = false;
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"');
+        match key.trim() {
+            "node.name" => node_name = Some(value.to_string()),
+            "media.class" => is_source = value == "Audio/Source",
+            _ => {}
+        }
+    }
+    finish_node(node_name, is_source);
+
+    devices
+}
+
 pub async fn list_audio_devices() -> Result<Vec<AudioDevice>> {
     let host = cpal::default_host();
     let mut devices = Vec::new();
@@ -244,7 +460,23 @@ pub async fn list_audio_devices() -> Result<Vec<AudioDevice>> {
 
     #[cfg(target_os = "linux")]
     {
+        let pipewire_devices = list_pipewire_devices().await.unwrap_or_else(|e| {
+            debug!("PipeWire device enumeration unavailable: {}", e);
+            Vec::new()
+        });
         devices = configure_linux_audio(&host)?;
+        // Nodes are opened through pipewire-pulse, so only list the ones the Pulse host can see
+        let pulse_host = cpal::host_from_id(cpal::HostId::Pulse).ok();
+        for device in pipewire_devices {
+            if devices.iter().any(|d| d.name == device.name) {
+                continue;
+            }
+            if pulse_host.as_ref().is_some_and(|host| find_in_host(host, &device).is_some()) {
+                devices.push(device);
+            } else {
+                debug!("Skipping PipeWire node {}: not available through the Pulse host", device.name);
+            }
+        }
     }
 
     #[cfg(target_os = "macos")]
@@ -294,15 +526,25 @@ pub async fn list_audio_devices() -> Result<Vec<AudioDevice>> {
     Ok(devices)
 }
 
-pub fn default_input_device() -> Result<AudioDevice> {
+pub fn default_input_device() -> Result<AudioDevice, AudioError> {
     let host = cpal::default_host();
     let device = host
         .default_input_device()
-        .ok_or_else(|| anyhow!("No default input device found"))?;
-    Ok(AudioDevice::new(device.name()?, DeviceType::Input))
+        .ok_or_else(|| AudioError::DeviceNotFound { name: "default input".to_string() })?;
+    let name = device.name().map_err(|e| AudioError::configuration("default input", e))?;
+    Ok(AudioDevice::new(name, DeviceType::Input))
 }
 
-pub fn default_output_device() -> Result<AudioDevice> {
+// Default output of `host`; every platform falls back to this
+fn default_output_of(host: &cpal::Host) -> Result<AudioDevice, AudioError> {
+    let device = host
+        .default_output_device()
+        .ok_or_else(|| AudioError::DeviceNotFound { name: "default output".to_string() })?;
+    let name = device.name().map_err(|e| AudioError::configuration("default output", e))?;
+    Ok(AudioDevice::new(name, DeviceType::Output))
+}
+
+pub fn default_output_device() -> Result<AudioDevice, AudioError> {
     #[cfg(target_os = "macos")]
     {
         // ! see https://github.com/RustAudio/cpal/pull/894
@@ -313,11 +555,7 @@ pub fn default_output_device() -> Result<AudioDevice> {
                 }
             }
         }
-        let host = cpal::default_host();
-        let device = host
-            .default_output_device()
-            .ok_or_else(|| anyhow!("No default output device found"))?;
-        return Ok(AudioDevice::new(device.name()?, DeviceType::Output));
+        return default_output_of(&cpal::default_host());
     }
 
     #[cfg(target_os = "windows")]
@@ -331,20 +569,12 @@ pub fn default_output_device() -> Result<AudioDevice> {
             }
         }
         // Fallback to default host if WASAPI fails
-        let host = cpal::default_host();
-        let device = host
-            .default_output_device()
-            .ok_or_else(|| anyhow!("No default output device found"))?;
-        return Ok(AudioDevice::new(device.name()?, DeviceType::Output));
+        return default_output_of(&cpal::default_host());
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
-        let host = cpal::default_host();
-        let device = host
-            .default_output_device()
-            .ok_or_else(|| anyhow!("No default output device found"))?;
-        return Ok(AudioDevice::new(device.name()?, DeviceType::Output));
+        return default_output_of(&cpal::default_host());
     }
 }
 
@@ -378,10 +608,266 @@ pub fn trigger_audio_permission() -> Result<()> {
     Ok(())
 }
 
+// AVAudioSession only exists on iOS; the macOS equivalent of keeping a recording alive in the
+// background is a user-initiated, latency-critical NSProcessInfo activity, which opts the
+// process out of App Nap and timer coalescing while minimized
+#[cfg(target_os = "macos")]
+static RECORDING_ACTIVITY: std::sync::Mutex<Option<usize>> = std::sync::Mutex::new(None);
+
+#[cfg(target_os = "macos")]
+pub fn configure_audio_session_for_recording() -> Result<()> {
+    use objc::runtime::Object;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    // NSActivityUserInitiated | NSActivityLatencyCritical
+    const ACTIVITY_OPTIONS: u64 = 0x00FF_FFFF | 0xFF_0000_0000;
+
+    let mut activity_slot = RECORDING_ACTIVITY.lock().map_err(|_| anyhow!("Recording activity lock poisoned"))?;
+    if activity_slot.is_some() {
+        return Ok(());
+    }
+
+    let activity = unsafe {
+        let process_info: *mut Object = msg_send![class!(NSProcessInfo), processInfo];
+        let reason: *mut Object = msg_send![class!(NSString), stringWithUTF8String: c"Recording meeting audio".as_ptr()];
+        let activity: *mut Object = msg_send![process_info, beginActivityWithOptions: ACTIVITY_OPTIONS reason: reason];
+        if activity.is_null() {
+            return Err(anyhow!("Failed to begin recording activity"));
+        }
+        // The token is autoreleased; keep it until restore_audio_session
+        let _: *mut Object = msg_send![activity, retain];
+        activity
+    };
+
+    *activity_slot = Some(activity as usize);
+    info!("Began background recording activity");
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub fn restore_audio_session() -> Result<()> {
+    use objc::runtime::Object;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    let activity = RECORDING_ACTIVITY
+        .lock()
+        .map_err(|_| anyhow!("Recording activity lock poisoned"))?
+        .take();
+    if let Some(activity) = activity {
+        unsafe {
+            let activity = activity as *mut Object;
+            let process_info: *mut Object = msg_send![class!(NSProcessInfo), processInfo];
+            let _: () = msg_send![process_info, endActivity: activity];
+            let _: () = msg_send![activity, release];
+        }
+        info!("Ended background recording activity");
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum MicrophonePermissionStatus {
+    Authorized,
+    Denied,
+    Restricted,
+    NotDetermined,
+}
+
+#[cfg(target_os = "macos")]
+pub fn get_microphone_permission_status() -> MicrophonePermissionStatus {
+    use objc2_av_foundation::{AVAuthorizationStatus, AVCaptureDevice, AVMediaTypeAudio};
+
+    let Some(media_type) = (unsafe { AVMediaTypeAudio }) else {
+        return MicrophonePermissionStatus::NotDetermined;
+    };
+    match unsafe { AVCaptureDevice::authorizationStatusForMediaType(media_type) } {
+        AVAuthorizationStatus::Authorized => MicrophonePermissionStatus::Authorized,
+        AVAuthorizationStatus::Denied => MicrophonePermissionStatus::Denied,
+        AVAuthorizationStatus::Restricted => MicrophonePermissionStatus::Restricted,
+        _ => MicrophonePermissionStatus::NotDetermined,
+    }
+}
+
+/// Shows the system prompt and resolves once the user answers it.
+#[cfg(target_os = "macos")]
+pub async fn request_microphone_permission() -> Result<MicrophonePermissionStatus> {
+    use objc2::runtime::Bool;
+    use objc2_av_foundation::{AVCaptureDevice, AVMediaTypeAudio};
+
+    let media_type = unsafe { AVMediaTypeAudio }.ok_or_else(|| anyhow!("AVMediaTypeAudio is unavailable"))?;
+    let (tx, rx) = oneshot::channel::<bool>();
+    {
+        // The completion handler is a plain Fn block, so the sender is taken out on first call
+        let tx = std::sync::Mutex::new(Some(tx));
+        let handler = block2::RcBlock::new(move |granted: Bool| {
+            if let Some(tx) = tx.lock().ok().and_then(|mut tx| tx.take()) {
+                let _ = tx.send(granted.as_bool());
+            }
+        });
+        unsafe { AVCaptureDevice::requestAccessForMediaType_completionHandler(media_type, &handler) };
+    }
+
+    let granted = rx.await.map_err(|_| anyhow!("Microphone permission request was dropped"))?;
+    info!("Microphone access {}", if granted { "granted" } else { "denied" });
+    Ok(get_microphone_permission_status())
+}
+
+const PERMISSION_CHECK_STEPS: u8 = 3;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionCheckProgress {
+    pub step: u8,
+    pub total: u8,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionStatus {
+    pub microphone_granted: bool,
+    pub system_audio_available: bool,
+    // Descriptions of the steps that did not finish in time
+    pub timed_out_steps: Vec<String>,
+}
+
+// Runs one blocking step with a deadline; None means it timed out (the thread is left to finish)
+async fn run_permission_step<R, T, F>(
+    app: &AppHandle<R>,
+    step: u8,
+    description: &str,
+    timeout: Duration,
+    f: F,
+) -> Option<Result<T>>
+where
+    R: Runtime,
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    let progress = PermissionCheckProgress {
+        step,
+        total: PERMISSION_CHECK_STEPS,
+        description: description.to_string(),
+    };
+    if let Err(e) = app.emit("permission-check-progress", &progress) {
+        warn!("Failed to emit permission progress: {}", e);
+    }
+
+    match tokio::time::timeout(timeout, tokio::task::spawn_blocking(f)).await {
+        Ok(Ok(result)) => Some(result),
+        Ok(Err(e)) => Some(Err(anyhow!("Permission step panicked: {}", e))),
+        Err(_) => {
+            warn!("Permission check step '{}' timed out after {:?}", description, timeout);
+            if let Err(e) = app.emit("permission-check-timeout", &progress) {
+                warn!("Failed to emit permission timeout: {}", e);
+            }
+            None
+        }
+    }
+}
+
+/// Same as `trigger_audio_permission`, but reports each potentially blocking call to the
+/// frontend and gives up on a step after `timeout_secs` instead of hanging.
+pub async fn trigger_audio_permission_with_progress<R: Runtime>(
+    app: AppHandle<R>,
+    timeout_secs: u32,
+) -> Result<PermissionStatus> {
+    let timeout = Duration::from_secs(timeout_secs.max(1) as u64);
+    let mut status = PermissionStatus {
+        microphone_granted: false,
+        system_audio_available: false,
+        timed_out_steps: Vec::new(),
+    };
+
+    // ScreenCaptureKit host creation hangs while Screen Recording permission is pending
+    let description = "Creating audio hosts";
+    match run_permission_step(&app, 1, description, timeout, || {
+        let _host = cpal::default_host();
+        #[cfg(target_os = "macos")]
+        return Ok(cpal::host_from_id(cpal::HostId::ScreenCaptureKit).is_ok());
+        #[cfg(not(target_os = "macos"))]
+        Ok(true)
+    })
+    .await
+    {
+        Some(result) => status.system_audio_available = result?,
+        None => status.timed_out_steps.push(description.to_string()),
+    }
+
+    let description = "Enumerating input devices";
+    match run_permission_step(&app, 2, description, timeout, || {
+        Ok(cpal::default_host().default_input_device().is_some())
+    })
+    .await
+    {
+        Some(result) if !result? => return Err(anyhow!("No default input device found")),
+        Some(_) => {}
+        None => status.timed_out_steps.push(description.to_string()),
+    }
+
+    let description = "Requesting microphone access";
+    match run_permission_step(&app, 3, description, timeout, || {
+        trigger_audio_permission()?;
+        Ok(())
+    })
+    .await
+    {
+        Some(result) => {
+            result?;
+            status.microphone_granted = true;
+        }
+        None => status.timed_out_steps.push(description.to_string()),
+    }
+
+    Ok(status)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AudioStreamConfig {
+    // Chunks held by the broadcast channel before slow subscribers start lagging
+    pub broadcast_capacity: usize,
+    // Audio collected in the device callback before it is broadcast; 0 forwards every callback
+    pub sample_buffer_pre_fill_ms: u32,
+}
+
+impl Default for AudioStreamConfig {
+    fn default() -> Self {
+        Self { broadcast_capacity: 1000, sample_buffer_pre_fill_ms: 0 }
+    }
+}
+
+// Batches device callbacks into chunks of at least `min_samples` before broadcasting
+struct ChunkForwarder {
+    tx: broadcast::Sender<Vec<f32>>,
+    pending: Vec<f32>,
+    min_samples: usize,
+}
+
+impl ChunkForwarder {
+    fn new(tx: broadcast::Sender<Vec<f32>>, sample_rate: u32, pre_fill_ms: u32) -> Self {
+        let min_samples = (sample_rate as u64 * pre_fill_ms as u64 / 1000) as usize;
+        Self { tx, pending: Vec::with_capacity(min_samples), min_samples }
+    }
+
+    fn push(&mut self, mono: Vec<f32>) {
+        let chunk = if self.min_samples == 0 {
+            mono
+        } else {
+            self.pending.extend(mono);
+            if self.pending.len() < self.min_samples {
+                return;
+            }
+            std::mem::replace(&mut self.pending, Vec::with_capacity(self.min_samples))
+        };
+        if let Err(e) = self.tx.send(chunk) {
+            error!("Failed to send audio data: {}", e);
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct AudioStream {
     pub device: Arc<AudioDevice>,
     pub device_config: cpal::SupportedStreamConfig,
+    pub stream_config: AudioStreamConfig,
     transmitter: Arc<tokio::sync::broadcast::Sender<Vec<f32>>>,
     stream_control: mpsc::Sender<StreamControl>,
     stream_thread: Option<Arc<tokio::sync::Mutex<Option<thread::JoinHandle<()>>>>>,
@@ -396,20 +882,21 @@ impl AudioStream {
     pub async fn from_device(
         device: Arc<AudioDevice>,
         is_running: Arc<AtomicBool>,
-    ) -> Result<Self> {
+        stream_config: AudioStreamConfig,
+    ) -> Result<Self, AudioError> {
         info!("Initializing audio stream for device: {}", device.to_string());
-        let (tx, _) = broadcast::channel::<Vec<f32>>(1000);
+        let (tx, _) = broadcast::channel::<Vec<f32>>(stream_config.broadcast_capacity);
         let tx_clone = tx.clone();
         
         // Get device and config with improved error handling
         let (cpal_audio_device, config) = match get_device_and_config(&device).await {
-            Ok((device, config)) => {
-                info!("Successfully got device and config for: {}", device.name()?);
-                (device, config)
+            Ok((cpal_device, config)) => {
+                info!("Successfully got device and config for: {}", device.name);
+                (cpal_device, config)
             },
             Err(e) => {
                 error!("Failed to get device and config: {}", e);
-                return Err(anyhow!("Failed to initialize audio device: {}", e));
+                return Err(e);
             }
         };
         
@@ -422,7 +909,7 @@ impl AudioStream {
                     
                     // On Windows, we might still be able to use the device with our custom config
                     #[cfg(not(target_os = "windows"))]
-                    return Err(anyhow!("Failed to get default input config: {}", e));
+                    return Err(AudioError::configuration(&device.name, e));
                     
                     #[cfg(target_os = "windows")]
                     {
@@ -433,7 +920,10 @@ impl AudioStream {
                                 let count = configs.count();
                                 if count == 0 {
                                     error!("No supported input configurations available for this device");
-                                    return Err(anyhow!("No supported input configurations available for device: {}", device.name));
+                                    return Err(AudioError::ConfigurationError {
+                                        device: device.name.clone(),
+                                        reason: "no supported input configurations".to_string(),
+                                    });
                                 }
                                 info!("Device has {} supported input configurations", count);
                             },
@@ -451,12 +941,21 @@ impl AudioStream {
         let channels = config.channels();
         info!("Audio config - Sample rate: {}, Channels: {}, Format: {:?}", 
             config.sample_rate().0, channels, config.sample_format());
+        // The capture thread can only report failures to the log, so reject formats it can't read up front
+        let sample_format = config.sample_format();
+        if !matches!(
+            sample_format,
+            cpal::SampleFormat::F32 | cpal::SampleFormat::I16 | cpal::SampleFormat::I32 | cpal::SampleFormat::I8
+        ) {
+            return Err(AudioError::UnsupportedFormat { format: sample_format.to_string() });
+        }
 
         let is_running_weak_2 = Arc::downgrade(&is_running);
         let is_disconnected = Arc::new(AtomicBool::new(false));
         let device_clone = device.clone();
         let config_clone = config.clone();
         let (stream_control_tx, stream_control_rx) = mpsc::channel();
+        let mut forwarder = ChunkForwarder::new(tx, config.sample_rate().0, stream_config.sample_buffer_pre_fill_ms);
 
         let is_disconnected_clone = is_disconnected.clone();
         let stream_control_tx_clone = stream_control_tx.clone();
@@ -465,6 +964,9 @@ impl AudioStream {
             let device_name = device.to_string();
             let device_name_clone = device_name.clone();  // Clone for the closure
             let config = config_clone;
+            // Only mic audio is denoised; system audio is left as the app produced it
+            let denoise = device.device_type == DeviceType::Input;
+            let sample_rate = config.sample_rate().0;
             info!("Starting audio stream thread for device: {}", device_name);
             let error_callback = move |err: StreamError| {
                 if err
@@ -502,11 +1004,11 @@ impl AudioStream {
                     match cpal_audio_device.build_input_stream(
                         &config.into(),
                         move |data: &[f32], _: &_| {
-                            let mono = audio_to_mono(data, channels);
+                            let mono = audio_to_mono(data, channels, current_downmix_strategy());
+                            let mono = if denoise { suppress_or_passthrough(mono, sample_rate) } else { mono };
                             debug!("Received audio chunk: {} samples", mono.len());
-                            if let Err(e) = tx.send(mono) {
-                                error!("Failed to send audio data: {}", e);
-                            }
+                            LAST_AUDIO_CAPTURE.store(unix_time_ms(), Ordering::Relaxed);
+                            forwarder.push(mono);
                         },
                         error_callback.clone(),
                         None,
@@ -522,11 +1024,11 @@ impl AudioStream {
                     match cpal_audio_device.build_input_stream(
                         &config.into(),
                         move |data: &[i16], _: &_| {
-                            let mono = audio_to_mono(bytemuck::cast_slice(data), channels);
+                            let mono = audio_to_mono(bytemuck::cast_slice(data), channels, current_downmix_strategy());
+                            let mono = if denoise { suppress_or_passthrough(mono, sample_rate) } else { mono };
                             debug!("Received audio chunk: {} samples", mono.len());
-                            if let Err(e) = tx.send(mono) {
-                                error!("Failed to send audio data: {}", e);
-                            }
+                            LAST_AUDIO_CAPTURE.store(unix_time_ms(), Ordering::Relaxed);
+                            forwarder.push(mono);
                         },
                         error_callback.clone(),
                         None,
@@ -542,11 +1044,11 @@ impl AudioStream {
                     match cpal_audio_device.build_input_stream(
                         &config.into(),
                         move |data: &[i32], _: &_| {
-                            let mono = audio_to_mono(bytemuck::cast_slice(data), channels);
+                            let mono = audio_to_mono(bytemuck::cast_slice(data), channels, current_downmix_strategy());
+                            let mono = if denoise { suppress_or_passthrough(mono, sample_rate) } else { mono };
                             debug!("Received audio chunk: {} samples", mono.len());
-                            if let Err(e) = tx.send(mono) {
-                                error!("Failed to send audio data: {}", e);
-                            }
+                            LAST_AUDIO_CAPTURE.store(unix_time_ms(), Ordering::Relaxed);
+                            forwarder.push(mono);
                         },
                         error_callback.clone(),
                         None,
@@ -562,11 +1064,11 @@ impl AudioStream {
                     match cpal_audio_device.build_input_stream(
                         &config.into(),
                         move |data: &[i8], _: &_| {
-                            let mono = audio_to_mono(bytemuck::cast_slice(data), channels);
+                            let mono = audio_to_mono(bytemuck::cast_slice(data), channels, current_downmix_strategy());
+                            let mono = if denoise { suppress_or_passthrough(mono, sample_rate) } else { mono };
                             debug!("Received audio chunk: {} samples", mono.len());
-                            if let Err(e) = tx.send(mono) {
-                                error!("Failed to send audio data: {}", e);
-                            }
+                            LAST_AUDIO_CAPTURE.store(unix_time_ms(), Ordering::Relaxed);
+                            forwarder.push(mono);
                         },
                         error_callback.clone(),
                         None,
@@ -613,6 +1115,7 @@ impl AudioStream {
         Ok(AudioStream {
             device,
             device_config: config,
+            stream_config,
             transmitter: Arc::new(tx_clone),
             stream_control: stream_control_tx,
             stream_thread: Some(stream_thread),
@@ -620,17 +1123,57 @@ impl AudioStream {
         })
     }
 
+    /// Wraps a channel fed by something other than a capture device, e.g. the mock audio
+    /// used by the integration tests. `stop` behaves as it does for a device stream.
+    #[cfg(feature = "integration-tests")]
+    #[doc(hidden)]
+    pub fn from_sender(device: Arc<AudioDevice>, sample_rate: u32, transmitter: broadcast::Sender<Vec<f32>>) -> Self {
+        let device_config = cpal::SupportedStreamConfig::new(
+            1,
+            cpal::SampleRate(sample_rate),
+            cpal::SupportedBufferSize::Unknown,
+            cpal::SampleFormat::F32,
+        );
+        let (stream_control_tx, stream_control_rx) = mpsc::channel();
+        let stream_thread = thread::spawn(move || {
+            if let Ok(StreamControl::Stop(response)) = stream_control_rx.recv() {
+                response.send(()).ok();
+            }
+        });
+
+        AudioStream {
+            device,
+            device_config,
+            stream_config: AudioStreamConfig::default(),
+            transmitter: Arc::new(transmitter),
+            stream_control: stream_control_tx,
+            stream_thread: Some(Arc::new(tokio::sync::Mutex::new(Some(stream_thread)))),
+            is_disconnected: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
     pub async fn subscribe(&self) -> broadcast::Receiver<Vec<f32>> {
         self.transmitter.subscribe()
     }
 
-    pub async fn stop(&self) -> Result<()> {
-        // Mark as disconnected first
-        self.is_disconnected.store(true, Ordering::Release);
-        
+    pub fn is_disconnected(&self) -> bool {
+        self.is_disconnected.load(Ordering::Relaxed)
+    }
+
+    pub async fn stop(&self) -> Result<(), AudioError> {
+        // A stream whose device vanished has already torn itself down
+        let was_disconnected = self.is_disconnected.swap(true, Ordering::AcqRel);
+        let stream_error = |error: String| AudioError::StreamError { device: self.device.name.clone(), error };
+
         // Send stop signal and wait for confirmation
         let (tx, _rx) = oneshot::channel();
-        self.stream_control.send(StreamControl::Stop(tx))?;
+        if self.stream_control.send(StreamControl::Stop(tx)).is_err() {
+            return Err(if was_disconnected {
+                AudioError::DeviceDisconnected { name: self.device.name.clone() }
+            } else {
+                stream_error("stream thread is no longer running".to_string())
+            });
+        }
 
         // Wait for thread to finish
         if let Some(thread_arc) = &self.stream_thread {
@@ -638,25 +1181,108 @@ impl AudioStream {
             let thread_handle = tokio::task::spawn_blocking(move || {
                 let mut thread_guard = thread_arc.blocking_lock();
                 if let Some(join_handle) = thread_guard.take() {
-                    join_handle
-                        .join()
-                        .map_err(|_| anyhow!("failed to join stream thread"))
+                    join_handle.join().map_err(|_| "failed to join stream thread".to_string())
                 } else {
                     Ok(())
                 }
             });
 
-            thread_handle.await??;
+            thread_handle
+                .await
+                .map_err(|e| stream_error(e.to_string()))?
+                .map_err(stream_error)?;
         }
 
         Ok(())
     }
 }
 
+const RECONNECT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Watches a stream for device removal and rebuilds it once the same device shows up again.
+///
+/// New streams are published through a `watch` channel; consumers wait on it with
+/// [`await_stream_reconnect`]. The `on_reconnect` callback lets the caller swap any
+/// references it keeps to the old stream.
+pub struct StreamReconnector {
+    updates: watch::Receiver<Arc<AudioStream>>,
+}
+
+impl StreamReconnector {
+    pub fn spawn<F>(stream: Arc<AudioStream>, is_running: Arc<AtomicBool>, on_reconnect: F) -> Self
+    where
+        F: Fn(Arc<AudioStream>) + Send + Sync + 'static,
+    {
+        let (tx, rx) = watch::channel(stream.clone());
+
+        tokio::spawn(async move {
+            let mut current = stream;
+            while is_running.load(Ordering::SeqCst) {
+                tokio::time::sleep(RECONNECT_POLL_INTERVAL).await;
+                if !current.is_disconnected() || !is_running.load(Ordering::SeqCst) {
+                    continue;
+                }
+
+                let devices = match list_audio_devices().await {
+                    Ok(devices) => devices,
+                    Err(e) => {
+                        warn!("Failed to list audio devices while waiting for reconnect: {}", e);
+                        continue;
+                    }
+                };
+                if !devices.iter().any(|d| d.name == current.device.name) {
+                    debug!("Waiting for device {} to reappear", current.device);
+                    continue;
+                }
+
+                info!("Device {} is available again, reconnecting", current.device);
+                match AudioStream::from_device(current.device.clone(), is_running.clone(), current.stream_config).await {
+                    Ok(new_stream) => {
+                        let new_stream = Arc::new(new_stream);
+                        on_reconnect(new_stream.clone());
+                        if tx.send(new_stream.clone()).is_err() {
+                            debug!("No listeners left for reconnected stream");
+                        }
+                        current = new_stream;
+                    }
+                    Err(e @ AudioError::PermissionDenied { .. }) => {
+                        error!("Giving up reconnecting {}: {}", current.device, e);
+                        break;
+                    }
+                    Err(e) => warn!("Failed to reconnect device {}: {}", current.device, e),
+                }
+            }
+            debug!("Stream reconnector for {} stopped", current.device);
+        });
+
+        Self { updates: rx }
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<Arc<AudioStream>> {
+        self.updates.clone()
+    }
+}
+
+/// Waits until a reconnector publishes a new stream.
+/// Returns `None` if recording stops or the reconnector goes away first.
+pub async fn await_stream_reconnect(
+    updates: &mut watch::Receiver<Arc<AudioStream>>,
+    is_running: &AtomicBool,
+) -> Option<Arc<AudioStream>> {
+    while is_running.load(Ordering::SeqCst) {
+        match tokio::time::timeout(Duration::from_millis(500), updates.changed()).await {
+            Ok(Ok(())) => return Some(updates.borrow_and_update().clone()),
+            Ok(Err(_)) => return None,
+            Err(_) => continue,
+        }
+    }
+    None
+}
+
 #[cfg(target_os = "windows")]
-fn get_windows_device(audio_device: &AudioDevice) -> Result<(cpal::Device, cpal::SupportedStreamConfig)> {
+fn get_windows_device(audio_device: &AudioDevice) -> Result<(cpal::Device, cpal::SupportedStreamConfig), AudioError> {
     let wasapi_host = cpal::host_from_id(cpal::HostId::Wasapi)
-        .map_err(|e| anyhow!("Failed to create WASAPI host: {}", e))?;
+        .map_err(|e| AudioError::configuration(&audio_device.name, format!("WASAPI host unavailable: {}", e)))?;
 
     // Extract the base device name without the (input) or (output) suffix
     let base_name = if audio_device.name.ends_with(" (input)") {
@@ -671,7 +1297,7 @@ fn get_windows_device(audio_device: &AudioDevice) -> Result<(cpal::Device, cpal:
 
     match audio_device.device_type {
         DeviceType::Input => {
-            for device in wasapi_host.input_devices()? {
+            for device in wasapi_host.input_devices().map_err(|e| AudioError::configuration(&audio_device.name, e))? {
                 if let Ok(name) = device.name() {
                     info!("Checking input device: {}", name);
                     // Check if the device name contains our base name
@@ -722,7 +1348,10 @@ fn get_windows_device(audio_device: &AudioDevice) -> Result<(cpal::Device, cpal:
                                     warn!("Could not enumerate supported configurations for device: {}", name);
                                 }
                                 
-                                return Err(anyhow!("No compatible input configuration found for device: {}", name));
+                                return Err(AudioError::ConfigurationError {
+                                    device: name,
+                                    reason: "no compatible input configuration".to_string(),
+                                });
                             }
                         }
                     }
@@ -745,7 +1374,7 @@ fn get_windows_device(audio_device: &AudioDevice) -> Result<(cpal::Device, cpal:
             }
         }
         DeviceType::Output => {
-            for device in wasapi_host.output_devices()? {
+            for device in wasapi_host.output_devices().map_err(|e| AudioError::configuration(&audio_device.name, e))? {
                 if let Ok(name) = device.name() {
                     info!("Checking output device: {}", name);
                     // Check if the device name contains our base name
@@ -813,12 +1442,26 @@ fn get_windows_device(audio_device: &AudioDevice) -> Result<(cpal::Device, cpal:
         }
     }
 
-    Err(anyhow!("Device not found or no compatible configuration available: {}", audio_device.name))
+    Err(AudioError::DeviceNotFound { name: audio_device.name.clone() })
 }
 
 pub async fn get_device_and_config(
     audio_device: &AudioDevice,
-) -> Result<(cpal::Device, cpal::SupportedStreamConfig)> {
+) -> Result<(cpal::Device, cpal::SupportedStreamConfig), AudioError> {
+    let config_error = |e: &dyn fmt::Display| AudioError::configuration(&audio_device.name, e);
+    if let Some(host_id) = preferred_host() {
+        match cpal::host_from_id(host_id) {
+            Ok(host) => {
+                if let Some(found) = find_in_host(&host, audio_device) {
+                    info!("Using {} from preferred host {}", audio_device.name, host_id.name());
+                    return Ok(found);
+                }
+                debug!("{} not found on preferred host {}, falling back", audio_device.name, host_id.name());
+            }
+            Err(e) => warn!("Preferred audio host {} unavailable: {}", host_id.name(), e),
+        }
+    }
+
     #[cfg(target_os = "windows")]
     {
         return get_windows_device(audio_device);
@@ -830,27 +1473,37 @@ pub async fn get_device_and_config(
         
         match audio_device.device_type {
             DeviceType::Input => {
-                for device in host.input_devices()? {
+                for device in host.input_devices().map_err(|e| config_error(&e))? {
                     if let Ok(name) = device.name() {
                         if name == audio_device.name {
                             let default_config = device
                                 .default_input_config()
-                                .map_err(|e| anyhow!("Failed to get default input config: {}", e))?;
+                                .map_err(|e| config_error(&e))?;
                             return Ok((device, default_config));
                         }
                     }
                 }
+
+                #[cfg(target_os = "linux")]
+                {
+                    // PipeWire nodes listed from pw-cli are opened through pipewire-pulse
+                    if let Ok(pulse_host) = cpal::host_from_id(cpal::HostId::Pulse) {
+                        if let Some(found) = find_in_host(&pulse_host, audio_device) {
+                            return Ok(found);
+                        }
+                    }
+                }
             }
             DeviceType::Output => {
                 #[cfg(target_os = "macos")]
                 {
                     if let Ok(host) = cpal::host_from_id(cpal::HostId::ScreenCaptureKit) {
-                        for device in host.input_devices()? {
+                        for device in host.input_devices().map_err(|e| config_error(&e))? {
                             if let Ok(name) = device.name() {
                                 if name == audio_device.name {
                                     let default_config = device
                                         .default_input_config()
-                                        .map_err(|e| anyhow!("Failed to get default input config: {}", e))?;
+                                        .map_err(|e| config_error(&e))?;
                                     return Ok((device, default_config));
                                 }
                             }
@@ -862,12 +1515,12 @@ pub async fn get_device_and_config(
                 {
                     // For Linux, we use PulseAudio monitor sources for system audio
                     if let Ok(pulse_host) = cpal::host_from_id(cpal::HostId::Pulse) {
-                        for device in pulse_host.input_devices()? {
+                        for device in pulse_host.input_devices().map_err(|e| config_error(&e))? {
                             if let Ok(name) = device.name() {
                                 if name == audio_device.name {
                                     let default_config = device
                                         .default_input_config()
-                                        .map_err(|e| anyhow!("Failed to get default input config: {}", e))?;
+                                        .map_err(|e| config_error(&e))?;
                                     return Ok((device, default_config));
                                 }
                             }
@@ -877,6 +1530,6 @@ pub async fn get_device_and_config(
             }
         }
         
-        Err(anyhow!("Device not found: {}", audio_device.name))
+        Err(AudioError::DeviceNotFound { name: audio_device.name.clone() })
     }
 }
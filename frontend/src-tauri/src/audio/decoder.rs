@@ -0,0 +1,47 @@
+// Decodes compressed audio (M4A, MP3, ...) to f32 samples by piping it through ffmpeg
+use anyhow::{anyhow, Result};
+use log::debug;
+use std::process::{Command, Stdio};
+
+use super::ffmpeg::find_ffmpeg_path;
+
+const FFMPEG_MISSING_MESSAGE: &str = "ffmpeg was not found. Install it with `brew install ffmpeg` (macOS), \
+`sudo apt install ffmpeg` (Debian/Ubuntu) or `winget install ffmpeg` (Windows), then restart the app.";
+
+pub fn decode_audio_file_ffmpeg(path: &str, target_sample_rate: u32) -> Result<Vec<f32>> {
+    decode_audio_file_ffmpeg_interleaved(path, target_sample_rate, 1)
+}
+
+/// Like `decode_audio_file_ffmpeg`, but keeps `channels` channels interleaved.
+pub fn decode_audio_file_ffmpeg_interleaved(path: &str, target_sample_rate: u32, channels: u16) -> Result<Vec<f32>> {
+    let ffmpeg = find_ffmpeg_path().ok_or_else(|| anyhow!(FFMPEG_MISSING_MESSAGE))?;
+    debug!("Decoding {} with {:?} at {} Hz, {} channel(s)", path, ffmpeg, target_sample_rate, channels);
+
+    let output = Command::new(ffmpeg)
+        .args(["-nostdin", "-i", path, "-f", "f32le", "-ac"])
+        .arg(channels.max(1).to_string())
+        .arg("-ar")
+        .arg(target_sample_rate.to_string())
+        .arg("pipe:1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last_line = stderr.lines().last().unwrap_or_default();
+        return Err(anyhow!("ffmpeg failed to decode {}: {}", path, last_line));
+    }
+
+    Ok(output
+        .stdout
+        .chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .collect())
+}
+
+#[tauri::command]
+pub fn is_ffmpeg_available() -> bool {
+    find_ffmpeg_path().is_some()
+}
@@ -0,0 +1,173 @@
+// Detects devices another application has opened in WASAPI exclusive mode, which blocks capture
+use log::{error, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+#[cfg(target_os = "windows")]
+use super::core::{default_input_device, default_output_device};
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExclusiveModeStatus {
+    pub mic_exclusive: bool,
+    pub system_exclusive: bool,
+    pub conflicting_apps: Vec<String>,
+}
+
+#[cfg(target_os = "windows")]
+mod wasapi {
+    use std::collections::BTreeSet;
+    use windows::core::{Interface, Result, PWSTR};
+    use windows::Win32::Devices::FunctionDiscovery::PKEY_Device_FriendlyName;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::Media::Audio::{
+        eCapture, eRender, AudioSessionStateActive, EDataFlow, IAudioClient, IAudioSessionControl2,
+        IAudioSessionManager2, IMMDevice, IMMDeviceEnumerator, MMDeviceEnumerator, AUDCLNT_E_DEVICE_IN_USE,
+        AUDCLNT_E_EXCLUSIVE_MODE_ALREADY_SET, AUDCLNT_SHAREMODE_SHARED, DEVICE_STATE_ACTIVE, WAVEFORMATEX,
+    };
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoTaskMemFree, CLSCTX_ALL, COINIT_MULTITHREADED, STGM_READ,
+    };
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    fn init_com() {
+        // S_FALSE / RPC_E_CHANGED_MODE just mean COM is already set up on this thread
+        let _ = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) };
+    }
+
+    /// Finds an active endpoint by the friendly name cpal reports for it.
+    pub fn find_device(device_name: &str) -> Result<Option<IMMDevice>> {
+        init_com();
+        let enumerator: IMMDeviceEnumerator = unsafe { CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)? };
+        for flow in [eCapture, eRender] as [EDataFlow; 2] {
+            let devices = unsafe { enumerator.EnumAudioEndpoints(flow, DEVICE_STATE_ACTIVE)? };
+            for i in 0..unsafe { devices.GetCount()? } {
+                let device = unsafe { devices.Item(i)? };
+                let properties = unsafe { device.OpenPropertyStore(STGM_READ)? };
+                let name = unsafe { properties.GetValue(&PKEY_Device_FriendlyName)? }.to_string();
+                if name == device_name {
+                    return Ok(Some(device));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// True when a shared-mode client can't use the device because someone holds it exclusively.
+    pub fn is_exclusive(device: &IMMDevice) -> Result<bool> {
+        let client: IAudioClient = unsafe { device.Activate(CLSCTX_ALL, None)? };
+        let format = unsafe { client.GetMixFormat()? };
+        let mut closest: *mut WAVEFORMATEX = std::ptr::null_mut();
+        let hr = unsafe { client.IsFormatSupported(AUDCLNT_SHAREMODE_SHARED, format, Some(&mut closest)) };
+        unsafe {
+            CoTaskMemFree(Some(format as _));
+            if !closest.is_null() {
+                CoTaskMemFree(Some(closest as _));
+            }
+        }
+        Ok(hr == AUDCLNT_E_EXCLUSIVE_MODE_ALREADY_SET || hr == AUDCLNT_E_DEVICE_IN_USE)
+    }
+
+    fn process_name(pid: u32) -> Option<String> {
+        let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()? };
+        let mut buffer = [0u16; 260];
+        let mut len = buffer.len() as u32;
+        let result = unsafe { QueryFullProcessImageNameW(handle, PROCESS_NAME_WIN32, PWSTR(buffer.as_mut_ptr()), &mut len) };
+        let _ = unsafe { CloseHandle(handle) };
+        result.ok()?;
+        let path = String::from_utf16_lossy(&buffer[..len as usize]);
+        path.rsplit('\\').next().map(str::to_string)
+    }
+
+    /// Executable names of other processes with an active audio session on the device.
+    pub fn active_session_apps(device: &IMMDevice) -> Result<BTreeSet<String>> {
+        let manager: IAudioSessionManager2 = unsafe { device.Activate(CLSCTX_ALL, None)? };
+        let sessions = unsafe { manager.GetSessionEnumerator()? };
+        let own_pid = std::process::id();
+        let mut apps = BTreeSet::new();
+        for i in 0..unsafe { sessions.GetCount()? } {
+            let session = unsafe { sessions.GetSession(i)? };
+            if unsafe { session.GetState()? } != AudioSessionStateActive {
+                continue;
+            }
+            let pid = unsafe { session.cast::<IAudioSessionControl2>()?.GetProcessId()? };
+            if pid == 0 || pid == own_pid {
+                continue;
+            }
+            if let Some(name) = process_name(pid) {
+                apps.insert(name);
+            }
+        }
+        Ok(apps)
+    }
+}
+
+/// Whether `device_name` is held in exclusive mode by another application.
+#[cfg(target_os = "windows")]
+pub fn detect_wasapi_exclusive_mode(device_name: &str) -> bool {
+    match wasapi::find_device(device_name).and_then(|device| device.map(|d| wasapi::is_exclusive(&d)).transpose()) {
+        Ok(exclusive) => exclusive.unwrap_or(false),
+        Err(e) => {
+            warn!("Failed to check exclusive mode for {}: {}", device_name, e);
+            false
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn conflicting_apps(device_name: &str) -> Vec<String> {
+    match wasapi::find_device(device_name) {
+        Ok(Some(device)) => wasapi::active_session_apps(&device)
+            .map(|apps| apps.into_iter().collect())
+            .unwrap_or_else(|e| {
+                warn!("Failed to list audio sessions for {}: {}", device_name, e);
+                Vec::new()
+            }),
+        Ok(None) => Vec::new(),
+        Err(e) => {
+            warn!("Failed to find {}: {}", device_name, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Checks the default mic and system devices; other platforms have no exclusive mode.
+pub fn check_exclusive_mode() -> ExclusiveModeStatus {
+    #[cfg(target_os = "windows")]
+    {
+        let mut status = ExclusiveModeStatus::default();
+        let devices = [
+            (default_input_device(), &mut status.mic_exclusive),
+            (default_output_device(), &mut status.system_exclusive),
+        ];
+        let mut apps = std::collections::BTreeSet::new();
+        for (device, exclusive) in devices {
+            let Ok(device) = device else {
+                continue;
+            };
+            *exclusive = detect_wasapi_exclusive_mode(&device.name);
+            if *exclusive {
+                apps.extend(conflicting_apps(&device.name));
+            }
+        }
+        status.conflicting_apps = apps.into_iter().collect();
+        status
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        ExclusiveModeStatus::default()
+    }
+}
+
+pub fn check_and_notify<R: Runtime>(app: &AppHandle<R>) -> ExclusiveModeStatus {
+    let status = check_exclusive_mode();
+    if status.mic_exclusive || status.system_exclusive {
+        warn!("Audio device held in exclusive mode: {:?}", status);
+        if let Err(e) = app.emit("exclusive-mode-conflict", &status) {
+            error!("Failed to emit exclusive-mode-conflict event: {}", e);
+        }
+    }
+    status
+}
@@ -0,0 +1,175 @@
+// Warns when the mic or system audio goes quiet mid-recording, e.g. after a hardware mute
+use log::{error, info};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use super::audio_processing::rms_and_peak;
+use super::core::unix_time_ms;
+use super::ring_buffer::RingBuffer;
+
+const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+const SILENT_DB: f32 = -60.0;
+// Hysteresis so a level hovering around the threshold doesn't re-arm the warning
+const RECOVERED_DB: f32 = -50.0;
+const SILENT_CHECKS_BEFORE_WARNING: u32 = 10;
+const ACKNOWLEDGE_SUPPRESS_MS: u64 = 30_000;
+
+pub const MIC_WARNING_EVENT: &str = "microphone-silent-warning";
+pub const SYSTEM_WARNING_EVENT: &str = "system-audio-silent-warning";
+
+// Unix ms until which each warning stays quiet after the user acknowledged it
+static MIC_ACK_UNTIL: AtomicU64 = AtomicU64::new(0);
+static SYSTEM_ACK_UNTIL: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Serialize)]
+struct SilenceWarning {
+    level_db: f32,
+    silent_secs: f32,
+}
+
+/// Silences `warning_type` ("microphone" or "system", or the event name) for 30 seconds.
+pub fn acknowledge(warning_type: &str) -> Result<(), String> {
+    let ack = match warning_type {
+        "microphone" | "mic" | MIC_WARNING_EVENT => &MIC_ACK_UNTIL,
+        "system" | "system-audio" | SYSTEM_WARNING_EVENT => &SYSTEM_ACK_UNTIL,
+        other => return Err(format!("Unknown audio warning type: {}", other)),
+    };
+    ack.store(unix_time_ms() + ACKNOWLEDGE_SUPPRESS_MS, Ordering::Relaxed);
+    info!("Acknowledged {} warning", warning_type);
+    Ok(())
+}
+
+struct SourceMonitor {
+    event: &'static str,
+    ack_until: &'static AtomicU64,
+    buffer: Arc<Mutex<RingBuffer<f32>>>,
+    last_total: u64,
+    silent_checks: u32,
+    // Unix ms of the last warning while the source has stayed silent
+    warned_at: Option<u64>,
+}
+
+impl SourceMonitor {
+    fn new(event: &'static str, ack_until: &'static AtomicU64, buffer: Arc<Mutex<RingBuffer<f32>>>) -> Self {
+        ack_until.store(0, Ordering::Relaxed);
+        Self { event, ack_until, buffer, last_total: 0, silent_checks: 0, warned_at: None }
+    }
+
+    // Level of what arrived since the last check; no new audio counts as silence
+    fn level_db(&mut self, window: usize) -> f32 {
+        let Ok(buffer) = self.buffer.lock() else {
+            return f32::NEG_INFINITY;
+        };
+        let new_samples = buffer.total_pushed().saturating_sub(self.last_total) as usize;
+        self.last_total = buffer.total_pushed();
+        if new_samples == 0 {
+            return f32::NEG_INFINITY;
+        }
+        let tail = buffer.tail(new_samples.min(window));
+        let (rms, _) = rms_and_peak(&tail);
+        if rms > 0.0 { 20.0 * rms.log10() } else { f32::NEG_INFINITY }
+    }
+
+    // Paused audio is silent by design, so the count starts over on resume
+    fn pause(&mut self) {
+        self.silent_checks = 0;
+        if let Ok(buffer) = self.buffer.lock() {
+            self.last_total = buffer.total_pushed();
+        }
+    }
+
+    fn check<R: Runtime>(&mut self, app: &AppHandle<R>, window: usize) {
+        let level_db = self.level_db(window);
+        if level_db > RECOVERED_DB {
+            if self.warned_at.take().is_some() {
+                info!("{} cleared, level back to {:.1} dB", self.event, level_db);
+            }
+            self.silent_checks = 0;
+            return;
+        }
+        if level_db >= SILENT_DB {
+            self.silent_checks = 0;
+            return;
+        }
+
+        self.silent_checks += 1;
+        if self.silent_checks <= SILENT_CHECKS_BEFORE_WARNING {
+            return;
+        }
+        // Once per silent stretch, plus again after an acknowledgement runs out
+        let now = unix_time_ms();
+        let ack_until = self.ack_until.load(Ordering::Relaxed);
+        let should_emit = match self.warned_at {
+            None => now >= ack_until,
+            Some(warned_at) => ack_until > warned_at && now >= ack_until,
+        };
+        if !should_emit {
+            return;
+        }
+
+        self.warned_at = Some(now);
+        let warning = SilenceWarning {
+            level_db: level_db.max(-120.0),
+            silent_secs: self.silent_checks as f32 * CHECK_INTERVAL.as_secs_f32(),
+        };
+        info!("Emitting {}: {:?}", self.event, warning);
+        if let Err(e) = app.emit(self.event, warning) {
+            error!("Failed to emit {}: {}", self.event, e);
+        }
+    }
+}
+
+/// Polls the recording buffers every 500 ms until `is_running` is cleared, skipping checks while `is_paused` is set.
+pub fn spawn<R: Runtime>(
+    app: AppHandle<R>,
+    mic_buffer: Arc<Mutex<RingBuffer<f32>>>,
+    system_buffer: Arc<Mutex<RingBuffer<f32>>>,
+    sample_rate: u32,
+    is_running: Arc<AtomicBool>,
+    is_paused: &'static AtomicBool,
+) {
+    let window = (sample_rate as u64 * CHECK_INTERVAL.as_millis() as u64 / 1000) as usize;
+    tokio::spawn(async move {
+        let mut sources = [
+            SourceMonitor::new(MIC_WARNING_EVENT, &MIC_ACK_UNTIL, mic_buffer),
+            SourceMonitor::new(SYSTEM_WARNING_EVENT, &SYSTEM_ACK_UNTIL, system_buffer),
+        ];
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        while is_running.load(Ordering::SeqCst) {
+            interval.tick().await;
+            let paused = is_paused.load(Ordering::SeqCst);
+            for source in sources.iter_mut() {
+                if paused {
+                    source.pause();
+                } else {
+                    source.check(&app, window);
+                }
+            }
+        }
+        info!("Audio level monitor stopped");
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pause_resets_silence_count_and_skips_paused_audio() {
+        static ACK: AtomicU64 = AtomicU64::new(0);
+        let buffer = Arc::new(Mutex::new(RingBuffer::new(1_000)));
+        let mut monitor = SourceMonitor::new(MIC_WARNING_EVENT, &ACK, buffer.clone());
+        monitor.silent_checks = SILENT_CHECKS_BEFORE_WARNING;
+        buffer.lock().unwrap().push_slice(&[0.0; 100]);
+
+        monitor.pause();
+        assert_eq!(monitor.silent_checks, 0);
+        // Nothing pushed while paused counts towards the first check after resume
+        assert_eq!(monitor.level_db(100), f32::NEG_INFINITY);
+        buffer.lock().unwrap().push_slice(&[0.5; 100]);
+        assert!(monitor.level_db(100) > RECOVERED_DB);
+    }
+}
@@ -0,0 +1,200 @@
+// Pre-meeting check that both capture paths deliver usable audio
+use anyhow::{anyhow, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use log::{info, warn};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Runtime};
+use tokio::sync::broadcast;
+
+use super::core::{default_input_device, default_output_device, AudioStream, AudioStreamConfig};
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+const NOISE_WINDOW_MS: u32 = 50;
+const CLICK_MS: u32 = 5;
+// Only a mic peak this soon after the click counts as the click coming back
+const MAX_LATENCY_MS: u32 = 500;
+const SILENCE_DB: f32 = -120.0;
+
+const MIN_MIC_PEAK_DB: f32 = -40.0;
+const MAX_NOISE_FLOOR_DB: f32 = -50.0;
+const MIN_SYSTEM_PEAK_DB: f32 = -60.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoopbackTestResult {
+    pub mic_detected: bool,
+    pub system_audio_detected: bool,
+    pub mic_peak_db: f32,
+    pub system_peak_db: f32,
+    pub estimated_latency_ms: Option<u32>,
+    pub noise_floor_db: f32,
+    pub test_passed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LoopbackProgress {
+    elapsed_ms: u64,
+    total_ms: u64,
+    mic_peak_db: f32,
+    system_peak_db: f32,
+}
+
+fn to_db(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        SILENCE_DB
+    } else {
+        (20.0 * amplitude.log10()).max(SILENCE_DB)
+    }
+}
+
+fn peak_db(samples: &[f32]) -> f32 {
+    to_db(samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs())))
+}
+
+// 10th percentile of short-window RMS, so speech during the test doesn't raise the floor
+fn noise_floor_db(samples: &[f32], sample_rate: u32) -> f32 {
+    let window = (sample_rate * NOISE_WINDOW_MS / 1000).max(1) as usize;
+    let mut levels: Vec<f32> = samples
+        .chunks(window)
+        .filter(|chunk| chunk.len() == window)
+        .map(|chunk| to_db((chunk.iter().map(|s| s * s).sum::<f32>() / chunk.len() as f32).sqrt()))
+        .collect();
+    if levels.is_empty() {
+        return SILENCE_DB;
+    }
+    levels.sort_by(|a, b| a.total_cmp(b));
+    levels[levels.len() / 10]
+}
+
+fn drain(receiver: &mut broadcast::Receiver<Vec<f32>>, into: &mut Vec<f32>) {
+    loop {
+        match receiver.try_recv() {
+            Ok(chunk) => into.extend(chunk),
+            Err(broadcast::error::TryRecvError::Lagged(skipped)) => warn!("Loopback test skipped {} chunks", skipped),
+            Err(_) => break,
+        }
+    }
+}
+
+/// Plays a short click on the default output device and returns when it started.
+/// Blocks for the length of the playback; cpal streams are not Send on every platform.
+fn play_click() -> Result<Instant> {
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| anyhow!("No default output device found"))?;
+    let config = device.default_output_config()?;
+    if config.sample_format() != cpal::SampleFormat::F32 {
+        return Err(anyhow!("Unsupported output sample format: {}", config.sample_format()));
+    }
+
+    let channels = config.channels() as usize;
+    let click_frames = (config.sample_rate().0 * CLICK_MS / 1000) as usize;
+    let frames_played = Arc::new(AtomicUsize::new(0));
+    let played = frames_played.clone();
+    let stream = device.build_output_stream(
+        &config.into(),
+        move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+            for frame in data.chunks_mut(channels) {
+                let index = played.fetch_add(1, Ordering::Relaxed);
+                let value = if index < click_frames { 0.8 } else { 0.0 };
+                frame.iter_mut().for_each(|sample| *sample = value);
+            }
+        },
+        |err| warn!("Loopback click stream error: {}", err),
+        None,
+    )?;
+    stream.play()?;
+    let started = Instant::now();
+    std::thread::sleep(Duration::from_millis(200));
+    drop(stream);
+    Ok(started)
+}
+
+// Offset from where the click should appear in the mic capture to the first loud sample after it
+fn estimate_latency_ms(mic: &[f32], sample_rate: u32, mic_started: Instant, click_at: Instant, threshold_db: f32) -> Option<u32> {
+    let expected = (click_at.checked_duration_since(mic_started)?.as_secs_f64() * sample_rate as f64) as usize;
+    let window = (sample_rate * MAX_LATENCY_MS / 1000) as usize;
+    let threshold = 10f32.powf(threshold_db / 20.0);
+    mic.get(expected..mic.len().min(expected + window))?
+        .iter()
+        .position(|sample| sample.abs() >= threshold)
+        .map(|offset| (offset as u64 * 1000 / sample_rate as u64) as u32)
+}
+
+pub async fn run_loopback_test<R: Runtime>(app: &AppHandle<R>, duration_secs: u32) -> Result<LoopbackTestResult> {
+    let total = Duration::from_secs(duration_secs.max(1) as u64);
+    let is_running = Arc::new(AtomicBool::new(true));
+    let mic_stream = AudioStream::from_device(Arc::new(default_input_device()?), is_running.clone(), AudioStreamConfig::default()).await?;
+    let system_stream = AudioStream::from_device(Arc::new(default_output_device()?), is_running.clone(), AudioStreamConfig::default()).await?;
+    let mic_rate = mic_stream.device_config.sample_rate().0;
+    let mut mic_receiver = mic_stream.subscribe().await;
+    let mut system_receiver = system_stream.subscribe().await;
+
+    let mut mic: Vec<f32> = Vec::new();
+    let mut system: Vec<f32> = Vec::new();
+    let mut mic_started: Option<Instant> = None;
+    let started = Instant::now();
+
+    // The click goes out halfway through so the noise floor is measured on both sides of it
+    let click = tokio::spawn(async move {
+        tokio::time::sleep(total / 2).await;
+        tokio::task::spawn_blocking(play_click).await
+    });
+
+    while started.elapsed() < total {
+        tokio::time::sleep(PROGRESS_INTERVAL).await;
+        drain(&mut mic_receiver, &mut mic);
+        drain(&mut system_receiver, &mut system);
+        if mic_started.is_none() && !mic.is_empty() {
+            // Backdate by what already arrived so sample indices line up with wall-clock time
+            mic_started = Some(Instant::now() - Duration::from_secs_f64(mic.len() as f64 / mic_rate as f64));
+        }
+
+        let progress = LoopbackProgress {
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            total_ms: total.as_millis() as u64,
+            mic_peak_db: peak_db(&mic),
+            system_peak_db: peak_db(&system),
+        };
+        if let Err(e) = app.emit("loopback-test-progress", progress) {
+            warn!("Failed to emit loopback progress: {}", e);
+        }
+    }
+
+    is_running.store(false, Ordering::SeqCst);
+    for stream in [&mic_stream, &system_stream] {
+        if let Err(e) = stream.stop().await {
+            warn!("Failed to stop loopback test stream: {}", e);
+        }
+    }
+
+    let mic_peak_db = peak_db(&mic);
+    let system_peak_db = peak_db(&system);
+    let noise_floor_db = noise_floor_db(&mic, mic_rate);
+    let estimated_latency_ms = match (click.await, mic_started) {
+        (Ok(Ok(Ok(click_at))), Some(mic_started)) => {
+            estimate_latency_ms(&mic, mic_rate, mic_started, click_at, (noise_floor_db + 20.0).max(MIN_MIC_PEAK_DB))
+        }
+        (Ok(Ok(Err(e))), _) => {
+            warn!("Skipping latency estimate: {}", e);
+            None
+        }
+        _ => None,
+    };
+
+    let result = LoopbackTestResult {
+        mic_detected: !mic.is_empty(),
+        system_audio_detected: !system.is_empty(),
+        mic_peak_db,
+        system_peak_db,
+        estimated_latency_ms,
+        noise_floor_db,
+        test_passed: mic_peak_db > MIN_MIC_PEAK_DB
+            && noise_floor_db < MAX_NOISE_FLOOR_DB
+            && system_peak_db > MIN_SYSTEM_PEAK_DB,
+    };
+    info!("Audio loopback test finished: {:?}", result);
+    Ok(result)
+}
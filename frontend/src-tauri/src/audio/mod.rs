@@ -1,14 +1,26 @@
 // src/audio/mod.rs
 pub mod core;
 pub mod audio_processing;
+pub mod codec;
+pub mod decoder;
 pub mod encode;
+pub mod exclusive_mode;
 pub mod ffmpeg;
+pub mod level_monitor;
+pub mod loopback;
+pub mod noise_suppression;
+pub mod podcast;
+pub mod resample;
+pub mod ring_buffer;
+pub mod spectrum;
+pub mod sync;
+pub mod wav_repair;
 
 pub use core::{
     default_input_device, default_output_device, get_device_and_config, list_audio_devices,
-    parse_audio_device, trigger_audio_permission,
-    AudioDevice, AudioStream, AudioTranscriptionEngine, DeviceControl, DeviceType,
-    LAST_AUDIO_CAPTURE,
+    parse_audio_device, trigger_audio_permission, await_stream_reconnect,
+    AudioDevice, AudioError, AudioHostInfo, AudioStream, AudioStreamConfig, AudioTranscriptionEngine, DeviceControl, DeviceType,
+    StreamReconnector, LAST_AUDIO_CAPTURE,
 };
 pub use encode::{
     encode_single_audio, AudioInput
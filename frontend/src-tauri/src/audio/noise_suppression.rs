@@ -0,0 +1,136 @@
+// Optional model-based noise suppression applied to the mic stream as it is captured
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use nnnoiseless::{DenoiseState, RnnModel};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+// RNNoise works on 10 ms frames at 48 kHz, with samples in i16 range
+const RNNOISE_SAMPLE_RATE: u32 = 48_000;
+const I16_SCALE: f32 = 32_768.0;
+
+pub static NOISE_SUPPRESSOR: Lazy<Mutex<Option<Box<dyn NoiseSuppressor>>>> = Lazy::new(|| Mutex::new(None));
+// Keeps the capture callback from logging the same failure on every buffer
+static SKIP_LOGGED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NoiseSuppressorType {
+    RNNoise,
+    DeepFilter,
+    Custom,
+}
+
+pub trait NoiseSuppressor: Send {
+    fn kind(&self) -> NoiseSuppressorType;
+    /// Returns exactly `samples.len()` denoised samples.
+    fn process(&mut self, samples: &[f32], sample_rate: u32) -> Result<Vec<f32>>;
+}
+
+struct RnnoiseSuppressor {
+    state: Box<DenoiseState<'static>>,
+    input: Vec<f32>,
+    // Primed with one frame of silence so every call can return as many samples as it was given
+    output: VecDeque<f32>,
+}
+
+impl RnnoiseSuppressor {
+    fn new(model: RnnModel) -> Self {
+        Self {
+            state: DenoiseState::with_model(model),
+            input: Vec::with_capacity(DenoiseState::FRAME_SIZE * 2),
+            output: std::iter::repeat(0.0).take(DenoiseState::FRAME_SIZE).collect(),
+        }
+    }
+}
+
+impl NoiseSuppressor for RnnoiseSuppressor {
+    fn kind(&self) -> NoiseSuppressorType {
+        NoiseSuppressorType::RNNoise
+    }
+
+    fn process(&mut self, samples: &[f32], sample_rate: u32) -> Result<Vec<f32>> {
+        if sample_rate != RNNOISE_SAMPLE_RATE {
+            return Err(anyhow!("RNNoise needs {} Hz audio, got {} Hz", RNNOISE_SAMPLE_RATE, sample_rate));
+        }
+        self.input.extend(samples.iter().map(|s| s * I16_SCALE));
+
+        let mut frame_out = [0.0f32; DenoiseState::FRAME_SIZE];
+        let full_frames = self.input.len() / DenoiseState::FRAME_SIZE;
+        for frame in self.input.chunks_exact(DenoiseState::FRAME_SIZE) {
+            self.state.process_frame(&mut frame_out, frame);
+            self.output.extend(frame_out.iter().map(|s| s / I16_SCALE));
+        }
+        self.input.drain(..full_frames * DenoiseState::FRAME_SIZE);
+
+        Ok(self.output.drain(..samples.len()).collect())
+    }
+}
+
+/// Loads `model_path` and makes it the active suppressor, replacing any previous one.
+pub fn load_model(model_path: &str, model_type: NoiseSuppressorType) -> Result<()> {
+    let path = Path::new(model_path);
+    if !path.is_file() {
+        return Err(anyhow!("Noise suppression model not found: {}", model_path));
+    }
+
+    let suppressor: Box<dyn NoiseSuppressor> = match model_type {
+        NoiseSuppressorType::RNNoise => {
+            let bytes = std::fs::read(path)?;
+            let model = RnnModel::from_bytes(&bytes)
+                .ok_or_else(|| anyhow!("{} is not a valid RNNoise model", model_path))?;
+            Box::new(RnnoiseSuppressor::new(model))
+        }
+        NoiseSuppressorType::DeepFilter | NoiseSuppressorType::Custom => {
+            return Err(anyhow!("{:?} noise suppression models are not supported yet", model_type));
+        }
+    };
+
+    *NOISE_SUPPRESSOR.lock().map_err(|_| anyhow!("Noise suppressor state poisoned"))? = Some(suppressor);
+    SKIP_LOGGED.store(false, Ordering::Relaxed);
+    info!("Loaded {:?} noise suppression model from {}", model_type, model_path);
+    Ok(())
+}
+
+pub fn unload_model() -> Result<()> {
+    let previous = NOISE_SUPPRESSOR
+        .lock()
+        .map_err(|_| anyhow!("Noise suppressor state poisoned"))?
+        .take();
+    if let Some(suppressor) = previous {
+        info!("Unloaded {:?} noise suppression model", suppressor.kind());
+    }
+    Ok(())
+}
+
+pub fn is_active() -> bool {
+    NOISE_SUPPRESSOR.lock().map(|suppressor| suppressor.is_some()).unwrap_or(false)
+}
+
+/// Denoises mono samples with the loaded model, or returns them unchanged when none is loaded.
+pub fn apply_noise_suppression(samples: &[f32], sample_rate: u32) -> Result<Vec<f32>> {
+    // Called from the capture callback; never wait on a model being swapped in
+    let Ok(mut suppressor) = NOISE_SUPPRESSOR.try_lock() else {
+        return Ok(samples.to_vec());
+    };
+    match suppressor.as_mut() {
+        Some(suppressor) => suppressor.process(samples, sample_rate),
+        None => Ok(samples.to_vec()),
+    }
+}
+
+// Capture must keep flowing, so a failing model falls back to the raw audio
+pub(crate) fn suppress_or_passthrough(samples: Vec<f32>, sample_rate: u32) -> Vec<f32> {
+    match apply_noise_suppression(&samples, sample_rate) {
+        Ok(denoised) => denoised,
+        Err(e) => {
+            if !SKIP_LOGGED.swap(true, Ordering::Relaxed) {
+                warn!("Noise suppression skipped: {}", e);
+            }
+            samples
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// Builds chaptered M4A podcast episodes from stored recordings with ffmpeg
+use anyhow::{anyhow, Result};
+use log::{debug, info};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use super::ffmpeg::find_ffmpeg_path;
+
+// Used as cover art when the user hasn't configured any
+const APP_ICON_PNG: &[u8] = include_bytes!("../../icons/128x128@2x.png");
+const AAC_BITRATE: &str = "128k";
+
+#[derive(Debug, Clone)]
+pub struct Chapter {
+    pub start_secs: f32,
+    pub end_secs: f32,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EpisodeMetadata {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub album_artist: Option<String>,
+    pub show: Option<String>,
+    pub track: u32,
+}
+
+// ffmetadata treats these as syntax, so they must be backslash-escaped in values
+fn escape_ffmetadata(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn ffmetadata(chapters: &[Chapter]) -> String {
+    let mut contents = String::from(";FFMETADATA1\n");
+    for chapter in chapters {
+        contents.push_str(&format!(
+            "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            (chapter.start_secs * 1000.0) as u64,
+            (chapter.end_secs * 1000.0) as u64,
+            escape_ffmetadata(&chapter.title),
+        ));
+    }
+    contents
+}
+
+/// Encodes `input` to AAC in an M4A container with tags, chapters and cover art.
+/// Falls back to the app icon when `artwork` is None.
+pub fn export_m4a(
+    input: &Path,
+    output: &Path,
+    metadata: &EpisodeMetadata,
+    chapters: &[Chapter],
+    artwork: Option<&Path>,
+) -> Result<()> {
+    let ffmpeg = find_ffmpeg_path().ok_or_else(|| anyhow!("ffmpeg was not found; install it to export podcast episodes"))?;
+
+    let work_dir = std::env::temp_dir().join(format!("meeting-podcast-{:016x}", rand::random::<u64>()));
+    fs::create_dir_all(&work_dir)?;
+    let result = (|| {
+        let metadata_path = work_dir.join("chapters.txt");
+        fs::write(&metadata_path, ffmetadata(chapters))?;
+        let artwork_path: PathBuf = match artwork {
+            Some(path) => path.to_path_buf(),
+            None => {
+                let path = work_dir.join("cover.png");
+                fs::write(&path, APP_ICON_PNG)?;
+                path
+            }
+        };
+
+        let mut command = Command::new(&ffmpeg);
+        command
+            .args(["-nostdin", "-y", "-i"])
+            .arg(input)
+            .arg("-i")
+            .arg(&metadata_path)
+            .arg("-i")
+            .arg(&artwork_path)
+            .args(["-map", "0:a", "-map", "2:v", "-map_metadata", "1", "-map_chapters", "1"])
+            .args(["-c:a", "aac", "-b:a", AAC_BITRATE, "-c:v", "png", "-disposition:v", "attached_pic"])
+            .args(["-metadata", &format!("title={}", metadata.title)])
+            .args(["-metadata", &format!("artist={}", metadata.artist)])
+            .args(["-metadata", &format!("album={}", metadata.album)])
+            .args(["-metadata", &format!("track={}", metadata.track)]);
+        if let Some(album_artist) = &metadata.album_artist {
+            command.args(["-metadata", &format!("album_artist={}", album_artist)]);
+        }
+        if let Some(show) = &metadata.show {
+            command.args(["-metadata", &format!("show={}", show)]);
+        }
+        command.args(["-f", "ipod"]).arg(output);
+        debug!("Running {:?}", command);
+
+        let result = command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::piped()).output()?;
+        if !result.status.success() {
+            let stderr = String::from_utf8_lossy(&result.stderr);
+            return Err(anyhow!("ffmpeg failed: {}", stderr.lines().last().unwrap_or_default()));
+        }
+        Ok(())
+    })();
+    let _ = fs::remove_dir_all(&work_dir);
+
+    result?;
+    info!("Exported podcast episode with {} chapters to {:?}", chapters.len(), output);
+    Ok(())
+}
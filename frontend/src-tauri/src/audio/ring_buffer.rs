@@ -0,0 +1,125 @@
+// Fixed-capacity sample buffer so long recordings don't hold the whole session in RAM
+use std::collections::VecDeque;
+
+/// Keeps the last 10 minutes, whatever the capture rate. At 16 kHz that is 9,600,000 samples;
+/// capture usually runs at the device rate (often 48 kHz), where a fixed sample count would
+/// only hold about 3 minutes.
+pub const DEFAULT_CAPACITY_SECS: u32 = 600;
+
+pub const BUFFER_SECONDS_KEY: &str = "audioBufferSeconds";
+
+#[derive(Debug, Clone)]
+pub struct RingBuffer<T> {
+    samples: VecDeque<T>,
+    capacity: usize,
+    // Keeps counting after the buffer is full, so readers can tell how much arrived since they last looked
+    total_pushed: u64,
+}
+
+impl<T: Copy> RingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            // Grows as audio arrives; short recordings shouldn't reserve the whole window up front
+            samples: VecDeque::new(),
+            capacity: capacity.max(1),
+            total_pushed: 0,
+        }
+    }
+
+    /// Room for `seconds` of audio at `sample_rate`.
+    pub fn with_duration(seconds: u32, sample_rate: u32) -> Self {
+        Self::new(seconds as usize * sample_rate as usize)
+    }
+
+    /// Appends `data`, dropping the oldest samples once `capacity` is exceeded.
+    pub fn push_slice(&mut self, data: &[T]) {
+        self.total_pushed += data.len() as u64;
+        // Only the newest `capacity` samples of an oversized slice could survive anyway
+        let data = &data[data.len().saturating_sub(self.capacity)..];
+        let overflow = (self.samples.len() + data.len()).saturating_sub(self.capacity);
+        self.samples.drain(..overflow);
+        self.samples.extend(data.iter().copied());
+    }
+
+    /// Takes everything currently buffered, oldest first, leaving the buffer empty.
+    pub fn drain_all(&mut self) -> Vec<T> {
+        self.samples.drain(..).collect()
+    }
+
+    /// Copies the newest `len` samples without removing them.
+    pub fn tail(&self, len: usize) -> Vec<T> {
+        self.samples
+            .range(self.samples.len().saturating_sub(len)..)
+            .copied()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn total_pushed(&self) -> u64 {
+        self.total_pushed
+    }
+
+    /// True once samples have been dropped to stay within capacity.
+    pub fn overflowed(&self) -> bool {
+        self.total_pushed > self.capacity as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_drops_oldest_samples() {
+        let mut buffer = RingBuffer::new(4);
+        buffer.push_slice(&[1, 2, 3]);
+        buffer.push_slice(&[4, 5, 6]);
+        assert_eq!(buffer.drain_all(), vec![3, 4, 5, 6]);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.total_pushed(), 6);
+        assert!(buffer.overflowed());
+    }
+
+    #[test]
+    fn length_never_exceeds_capacity() {
+        let mut buffer = RingBuffer::new(5);
+        for len in [0, 1, 3, 5, 2, 7, 4] {
+            let data: Vec<u32> = (0..len).collect();
+            buffer.push_slice(&data);
+            assert!(buffer.len() <= buffer.capacity(), "len {} after pushing {}", buffer.len(), len);
+        }
+    }
+
+    #[test]
+    fn oversized_slice_keeps_its_tail() {
+        let mut buffer = RingBuffer::new(3);
+        buffer.push_slice(&[9]);
+        buffer.push_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.tail(2), vec![4, 5]);
+        assert_eq!(buffer.drain_all(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn default_holds_9_6_million_samples_at_16khz() {
+        assert_eq!(RingBuffer::<f32>::with_duration(DEFAULT_CAPACITY_SECS, 16_000).capacity(), 9_600_000);
+    }
+
+    #[test]
+    fn capacity_scales_with_sample_rate() {
+        let buffer = RingBuffer::<f32>::with_duration(DEFAULT_CAPACITY_SECS, 48_000);
+        assert_eq!(buffer.capacity(), 600 * 48_000);
+        assert!(!buffer.overflowed());
+    }
+}
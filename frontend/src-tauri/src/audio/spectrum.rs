@@ -0,0 +1,70 @@
+// Log-spaced band levels for the live equalizer display
+use realfft::RealFftPlanner;
+use serde::Serialize;
+
+const MIN_FREQ_HZ: f32 = 20.0;
+const MAX_FREQ_HZ: f32 = 20_000.0;
+const FLOOR_DB: f32 = -120.0;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SpectrumSnapshot {
+    pub mic_bands: Vec<f32>,
+    pub system_bands: Vec<f32>,
+    pub frequency_centers_hz: Vec<f32>,
+}
+
+/// Edges of `bands` log-spaced bands from 20 Hz up to 20 kHz or Nyquist, whichever is lower.
+fn band_edges(bands: usize, sample_rate: u32) -> Vec<f32> {
+    let max_freq = MAX_FREQ_HZ.min(sample_rate as f32 / 2.0);
+    let ratio = max_freq / MIN_FREQ_HZ;
+    (0..=bands)
+        .map(|i| MIN_FREQ_HZ * ratio.powf(i as f32 / bands as f32))
+        .collect()
+}
+
+/// Geometric centre of each band, matching [`band_levels_db`].
+pub fn band_centers(bands: usize, sample_rate: u32) -> Vec<f32> {
+    band_edges(bands, sample_rate)
+        .windows(2)
+        .map(|edge| (edge[0] * edge[1]).sqrt())
+        .collect()
+}
+
+/// Hann-windowed FFT of `samples`, averaged into `bands` log-spaced bands in dBFS.
+/// A sine at full scale reads about 0 dB; silence or missing audio reads -120 dB.
+pub fn band_levels_db(samples: &[f32], sample_rate: u32, bands: usize) -> Vec<f32> {
+    if samples.len() < 2 || bands == 0 {
+        return vec![FLOOR_DB; bands];
+    }
+    let n = samples.len();
+    let window: Vec<f32> = (0..n)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (n - 1) as f32).cos())
+        .collect();
+    let window_sum: f32 = window.iter().sum();
+    let mut input: Vec<f32> = samples.iter().zip(&window).map(|(s, w)| s * w).collect();
+
+    let fft = RealFftPlanner::<f32>::new().plan_fft_forward(n);
+    let mut spectrum = fft.make_output_vec();
+    if fft.process(&mut input, &mut spectrum).is_err() {
+        return vec![FLOOR_DB; bands];
+    }
+    // One-sided amplitude, corrected for the window's coherent gain
+    let amplitudes: Vec<f32> = spectrum.iter().map(|bin| 2.0 * bin.norm() / window_sum).collect();
+    let bin_hz = sample_rate as f32 / n as f32;
+
+    band_edges(bands, sample_rate)
+        .windows(2)
+        .map(|edge| {
+            let first = (edge[0] / bin_hz).ceil() as usize;
+            let last = ((edge[1] / bin_hz).floor() as usize).min(amplitudes.len() - 1);
+            // Short windows leave low bands narrower than a bin; use the bin at the centre
+            let amplitude = if first <= last {
+                amplitudes[first..=last].iter().sum::<f32>() / (last - first + 1) as f32
+            } else {
+                let centre = ((edge[0] * edge[1]).sqrt() / bin_hz).round() as usize;
+                amplitudes[centre.min(amplitudes.len() - 1)]
+            };
+            if amplitude > 0.0 { (20.0 * amplitude.log10()).max(FLOOR_DB) } else { FLOOR_DB }
+        })
+        .collect()
+}
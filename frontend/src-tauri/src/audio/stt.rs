@@ -83,7 +83,7 @@ pub async fn stt(
         let api_key = deepgram_api_key.unwrap_or_default();
 
         match transcribe_with_deepgram(&api_key, audio, device, sample_rate, languages.clone()).await {
-            Ok(t) => Ok(t),
+            Ok(t) => Ok(t.transcript),
             Err(e) => {
                 error!("device: {}, deepgram transcription failed, falling back to Whisper: {:?}", device, e);
                 // Fallback to Whisper
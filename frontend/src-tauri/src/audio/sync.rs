@@ -0,0 +1,221 @@
+// Compensates for the mic clock drifting against wall-clock time during long recordings
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Instant;
+
+// Drift estimates are noisy until enough audio has arrived
+const WARMUP_SECS: u64 = 5;
+// Real hardware clocks stay well within ±1000 ppm; anything beyond is delivery jitter
+const MAX_CORRECTION: f32 = 0.001;
+// Weight of each new estimate in the smoothed correction factor
+const SMOOTHING: f32 = 0.05;
+
+pub const MIXING_CONFIG_KEY: &str = "mixingConfig";
+pub const MAX_MIX_GAIN: f32 = 2.0;
+// Normalization aims for about -20 dBFS RMS without boosting more than 20 dB
+const NORMALIZE_TARGET_RMS: f32 = 0.1;
+const NORMALIZE_MAX_GAIN: f32 = 10.0;
+// Below this a chunk is treated as silence and leaves the gain alone
+const NORMALIZE_SILENCE_RMS: f32 = 1e-4;
+const NORMALIZE_SMOOTHING: f32 = 0.1;
+
+/// Mix weights for the mic and system streams, persisted under "mixingConfig".
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct MixingConfig {
+    pub mic_gain: f32,
+    pub system_gain: f32,
+    pub normalize_mic: bool,
+    pub normalize_system: bool,
+}
+
+impl Default for MixingConfig {
+    fn default() -> Self {
+        Self { mic_gain: 0.7, system_gain: 0.3, normalize_mic: false, normalize_system: false }
+    }
+}
+
+impl MixingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, gain) in [("Mic", self.mic_gain), ("System", self.system_gain)] {
+            if !(0.0..=MAX_MIX_GAIN).contains(&gain) {
+                return Err(format!("{} gain must be between 0.0 and {}", name, MAX_MIX_GAIN));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Brings a stream towards a steady loudness; the gain moves gradually so speech doesn't pump
+struct Normalizer {
+    gain: f32,
+}
+
+impl Normalizer {
+    fn new() -> Self {
+        Self { gain: 1.0 }
+    }
+
+    fn apply(&mut self, samples: &mut [f32]) {
+        let rms = (samples.iter().map(|&x| x * x).sum::<f32>() / samples.len().max(1) as f32).sqrt();
+        if rms > NORMALIZE_SILENCE_RMS {
+            let target = (NORMALIZE_TARGET_RMS / rms).min(NORMALIZE_MAX_GAIN);
+            self.gain += (target - self.gain) * NORMALIZE_SMOOTHING;
+        }
+        for sample in samples.iter_mut() {
+            *sample = (*sample * self.gain).clamp(-1.0, 1.0);
+        }
+    }
+}
+
+// User volume on top of the mix weights, stored as f32 bits so it can change mid-recording
+static REALTIME_MIC_VOLUME: AtomicU32 = AtomicU32::new(0x3F80_0000); // 1.0f32
+static REALTIME_SYSTEM_VOLUME: AtomicU32 = AtomicU32::new(0x3F80_0000); // 1.0f32
+
+pub fn realtime_volumes() -> (f32, f32) {
+    (
+        f32::from_bits(REALTIME_MIC_VOLUME.load(Ordering::Relaxed)),
+        f32::from_bits(REALTIME_SYSTEM_VOLUME.load(Ordering::Relaxed)),
+    )
+}
+
+pub fn set_realtime_mic_volume(volume: f32) {
+    REALTIME_MIC_VOLUME.store(volume.to_bits(), Ordering::Relaxed);
+}
+
+pub fn set_realtime_system_volume(volume: f32) {
+    REALTIME_SYSTEM_VOLUME.store(volume.to_bits(), Ordering::Relaxed);
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct SyncStats {
+    pub drift_ppm: f32,
+    pub correction_factor: f32,
+    pub total_corrected_samples: u64,
+}
+
+pub struct AudioClock {
+    anchor_sample: u64,
+    anchor_instant: Instant,
+    sample_rate: u32,
+}
+
+impl AudioClock {
+    pub fn new(anchor_sample: u64, sample_rate: u32) -> Self {
+        Self { anchor_sample, anchor_instant: Instant::now(), sample_rate }
+    }
+
+    /// Samples a perfect clock would have produced since the anchor.
+    pub fn elapsed_samples(&self) -> u64 {
+        self.anchor_sample + (self.anchor_instant.elapsed().as_secs_f64() * self.sample_rate as f64) as u64
+    }
+
+    /// Ratio to resample by so `observed_sample` catches up with `expected_sample`.
+    pub fn correct_drift(observed_sample: u64, expected_sample: u64) -> f32 {
+        if observed_sample == 0 {
+            return 1.0;
+        }
+        let ratio = expected_sample as f64 / observed_sample as f64;
+        (ratio as f32).clamp(1.0 - MAX_CORRECTION, 1.0 + MAX_CORRECTION)
+    }
+}
+
+/// Stretches or compresses mic audio to the wall clock before mixing it with system audio.
+pub struct SyncedMixer {
+    clock: Option<AudioClock>,
+    sample_rate: u32,
+    mic_samples_received: u64,
+    correction_factor: f32,
+    // Fractional read position carried between chunks so interpolation stays continuous
+    phase: f64,
+    last_sample: f32,
+    total_corrected_samples: u64,
+    config: MixingConfig,
+    mic_normalizer: Option<Normalizer>,
+    system_normalizer: Option<Normalizer>,
+}
+
+impl SyncedMixer {
+    pub fn new(sample_rate: u32, config: MixingConfig) -> Self {
+        Self {
+            clock: None,
+            sample_rate,
+            mic_samples_received: 0,
+            correction_factor: 1.0,
+            phase: 0.0,
+            last_sample: 0.0,
+            total_corrected_samples: 0,
+            config,
+            mic_normalizer: config.normalize_mic.then(Normalizer::new),
+            system_normalizer: config.normalize_system.then(Normalizer::new),
+        }
+    }
+
+    pub fn stats(&self) -> SyncStats {
+        SyncStats {
+            drift_ppm: (self.correction_factor - 1.0) * 1_000_000.0,
+            correction_factor: self.correction_factor,
+            total_corrected_samples: self.total_corrected_samples,
+        }
+    }
+
+    pub fn mix(&mut self, mic: &[f32], system: &[f32]) -> Vec<f32> {
+        let mut mic = self.correct_mic(mic);
+        if let Some(normalizer) = &mut self.mic_normalizer {
+            normalizer.apply(&mut mic);
+        }
+        let mut system = system.to_vec();
+        if let Some(normalizer) = &mut self.system_normalizer {
+            normalizer.apply(&mut system);
+        }
+        let max_len = mic.len().max(system.len());
+        let (mic_volume, system_volume) = realtime_volumes();
+        let (mic_weight, system_weight) = (self.config.mic_gain * mic_volume, self.config.system_gain * system_volume);
+        (0..max_len)
+            .map(|i| {
+                let mic_sample = mic.get(i).copied().unwrap_or(0.0);
+                let system_sample = system.get(i).copied().unwrap_or(0.0);
+                (mic_sample * mic_weight) + (system_sample * system_weight)
+            })
+            .collect()
+    }
+
+    fn correct_mic(&mut self, mic: &[f32]) -> Vec<f32> {
+        if mic.is_empty() {
+            return Vec::new();
+        }
+        // Anchor on the first delivery so device start-up latency doesn't count as drift
+        let clock = self.clock.get_or_insert_with(|| AudioClock::new(0, self.sample_rate));
+        self.mic_samples_received += mic.len() as u64;
+
+        if self.mic_samples_received > WARMUP_SECS * self.sample_rate as u64 {
+            let estimate = AudioClock::correct_drift(self.mic_samples_received, clock.elapsed_samples());
+            self.correction_factor += (estimate - self.correction_factor) * SMOOTHING;
+        }
+        if self.correction_factor == 1.0 {
+            self.last_sample = mic[mic.len() - 1];
+            return mic.to_vec();
+        }
+
+        let corrected = self.interpolate(mic);
+        self.total_corrected_samples += corrected.len().abs_diff(mic.len()) as u64;
+        corrected
+    }
+
+    // Linear interpolation with the previous chunk's last sample as the left neighbour of index 0
+    fn interpolate(&mut self, mic: &[f32]) -> Vec<f32> {
+        let step = 1.0 / self.correction_factor as f64;
+        let mut output = Vec::with_capacity((mic.len() as f64 * self.correction_factor as f64) as usize + 1);
+        let mut position = self.phase;
+        while position < mic.len() as f64 {
+            let index = position.floor() as isize - 1;
+            let fraction = (position - position.floor()) as f32;
+            let left = if index < 0 { self.last_sample } else { mic[index as usize] };
+            let right = mic[(index + 1) as usize];
+            output.push(left + (right - left) * fraction);
+            position += step;
+        }
+        self.phase = position - mic.len() as f64;
+        self.last_sample = mic[mic.len() - 1];
+        output
+    }
+}
@@ -0,0 +1,115 @@
+// Fixes the RIFF and data chunk sizes of WAV files whose writer never finalized them
+use anyhow::{anyhow, Result};
+use log::info;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WavRepairResult {
+    pub original_data_size: u32,
+    pub corrected_data_size: u32,
+    pub sample_count: u64,
+    pub duration_secs: f32,
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+/// Returns the repaired file contents and the original/corrected data chunk sizes.
+pub fn repair_wav_bytes(bytes: &[u8]) -> Result<(Vec<u8>, u32, u32)> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(anyhow!("Not a RIFF/WAVE file"));
+    }
+
+    // Walk the chunks up to `data`; everything before it must be intact
+    let mut block_align = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let chunk_id = &bytes[offset..offset + 4];
+        let chunk_size = read_u32(bytes, offset + 4);
+        let body = offset + 8;
+
+        if chunk_id == b"data" {
+            let block_align = block_align.ok_or_else(|| anyhow!("data chunk found before fmt chunk"))? as usize;
+            let available = (bytes.len() - body) as u32;
+            // A size of zero (or past the end of the file) means the header was never updated
+            let corrected = if chunk_size != 0 && chunk_size <= available {
+                chunk_size
+            } else {
+                available - available % block_align.max(1) as u32
+            };
+
+            let mut repaired = bytes.to_vec();
+            if corrected != chunk_size {
+                // Trailing partial frames and anything written after them are dropped
+                repaired.truncate(body + corrected as usize);
+                if corrected % 2 == 1 {
+                    repaired.push(0);
+                }
+                repaired[offset + 4..offset + 8].copy_from_slice(&corrected.to_le_bytes());
+            }
+            let riff_size = (repaired.len() - 8) as u32;
+            repaired[4..8].copy_from_slice(&riff_size.to_le_bytes());
+            return Ok((repaired, chunk_size, corrected));
+        }
+
+        if chunk_id == b"fmt " {
+            if body + 16 > bytes.len() {
+                return Err(anyhow!("Truncated fmt chunk"));
+            }
+            block_align = Some(read_u16(bytes, body + 12));
+        }
+
+        // Chunks are word-aligned
+        offset = body + chunk_size as usize + (chunk_size as usize % 2);
+    }
+
+    Err(anyhow!("No data chunk found"))
+}
+
+#[tauri::command]
+pub fn repair_wav_file(input_path: String, output_path: Option<String>) -> Result<WavRepairResult, String> {
+    let bytes = fs::read(&input_path).map_err(|e| format!("Failed to read {}: {}", input_path, e))?;
+    let (repaired, original_data_size, corrected_data_size) =
+        repair_wav_bytes(&bytes).map_err(|e| format!("Failed to repair {}: {}", input_path, e))?;
+
+    // In-place repairs go through a temp file so a failure never leaves a half-written recording
+    let target = output_path.clone().unwrap_or_else(|| input_path.clone());
+    let write_path = match output_path {
+        Some(path) => path,
+        None => format!("{}.repair.tmp", input_path),
+    };
+    fs::write(&write_path, &repaired).map_err(|e| format!("Failed to write {}: {}", write_path, e))?;
+
+    let reader = match hound::WavReader::open(&write_path) {
+        Ok(reader) => reader,
+        Err(e) => {
+            let _ = fs::remove_file(&write_path);
+            return Err(format!("Repaired file is still not readable: {}", e));
+        }
+    };
+    let spec = reader.spec();
+    let result = WavRepairResult {
+        original_data_size,
+        corrected_data_size,
+        sample_count: reader.len() as u64,
+        duration_secs: reader.duration() as f32 / spec.sample_rate as f32,
+    };
+    drop(reader);
+
+    if write_path != target {
+        fs::rename(&write_path, Path::new(&target)).map_err(|e| format!("Failed to replace {}: {}", target, e))?;
+    }
+
+    info!(
+        "Repaired {}: data size {} -> {} ({:.1}s)",
+        input_path, original_data_size, corrected_data_size, result.duration_secs
+    );
+    Ok(result)
+}
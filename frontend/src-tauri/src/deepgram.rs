@@ -1,15 +1,308 @@
+// Deepgram speech-to-text using the Nova-2 model, which handles conversational meeting audio better than Whisper
 use anyhow::{anyhow, Result};
 use screenpipe_core::Language;
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
 
-/// Placeholder Deepgram transcription implementation.
-/// Always returns an error because Deepgram support is disabled in this build.
-#[allow(unused_variables)]
+use crate::http_client;
+
+pub const DEFAULT_LISTEN_URL: &str = "https://api.deepgram.com/v1/listen";
+// Overrides the endpoint, e.g. for Deepgram's EU region or a self-hosted deployment
+const LISTEN_URL_ENV: &str = "DEEPGRAM_LISTEN_URL";
+const DEEPGRAM_MODEL: &str = "nova-2";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpeakerSegment {
+    pub speaker: u32,
+    pub text: String,
+    pub start: f32,
+    pub end: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeepgramTranscript {
+    pub transcript: String,
+    // Consecutive words from the same diarized speaker; None when Deepgram returned no word timings
+    pub speakers: Option<Vec<SpeakerSegment>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenResponse {
+    results: ListenResults,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenResults {
+    channels: Vec<ListenChannel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenChannel {
+    alternatives: Vec<ListenAlternative>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenAlternative {
+    transcript: String,
+    #[serde(default)]
+    words: Vec<ListenWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenWord {
+    word: String,
+    #[serde(default)]
+    punctuated_word: Option<String>,
+    start: f32,
+    end: f32,
+    #[serde(default)]
+    speaker: Option<u32>,
+}
+
+// 16-bit mono WAV, the same encoding the Groq upload uses
+fn encode_wav(audio: &[f32], sample_rate: u32) -> Result<Vec<u8>> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::new(Cursor::new(Vec::<u8>::new()), spec)?;
+    for &sample in audio {
+        writer.write_sample((sample.clamp(-1.0, 1.0) * 32767.0) as i16)?;
+    }
+    Ok(writer.into_inner()?.into_inner())
+}
+
+fn listen_query(languages: &[Language]) -> Vec<(&'static str, String)> {
+    // Deepgram takes a single language; use the first hint like the Groq path does
+    let language = languages
+        .first()
+        .map(|language| language.as_lang_code().to_string())
+        .unwrap_or_else(|| "en".to_string());
+    vec![
+        ("model", DEEPGRAM_MODEL.to_string()),
+        ("language", language),
+        ("punctuate", "true".to_string()),
+        ("diarize", "true".to_string()),
+    ]
+}
+
+fn speaker_segments(words: Vec<ListenWord>) -> Vec<SpeakerSegment> {
+    let mut segments: Vec<SpeakerSegment> = Vec::new();
+    for word in words {
+        let speaker = word.speaker.unwrap_or(0);
+        let text = word.punctuated_word.unwrap_or(word.word);
+        match segments.last_mut() {
+            Some(segment) if segment.speaker == speaker => {
+                segment.text.push(' ');
+                segment.text.push_str(&text);
+                segment.end = word.end;
+            }
+            _ => segments.push(SpeakerSegment { speaker, text, start: word.start, end: word.end }),
+        }
+    }
+    segments
+}
+
+// The pre-recorded audio endpoint, from `DEEPGRAM_LISTEN_URL` when set
+fn listen_url() -> String {
+    std::env::var(LISTEN_URL_ENV)
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_LISTEN_URL.to_string())
+}
+
+/// Transcribes mono PCM samples with Deepgram Nova-2.
+///
+/// * `api_key` – Deepgram API key.
+/// * `audio` – Mono PCM samples in the range [-1.0, 1.0].
+/// * `device` – Only used for logging.
+/// * `sample_rate` – Sample rate of `audio` (Hz); it is sent in the WAV header, so no resampling is needed.
 pub async fn transcribe_with_deepgram(
     api_key: &str,
     audio: &[f32],
-    _device: &str,
-    _sample_rate: u32,
-    _languages: Vec<Language>,
-) -> Result<String> {
-    Err(anyhow!("Deepgram transcription is not enabled in this build"))
-}
\ No newline at end of file
+    device: &str,
+    sample_rate: u32,
+    languages: Vec<Language>,
+) -> Result<DeepgramTranscript> {
+    transcribe_with_deepgram_at(&listen_url(), api_key, audio, device, sample_rate, languages).await
+}
+
+async fn transcribe_with_deepgram_at(
+    listen_url: &str,
+    api_key: &str,
+    audio: &[f32],
+    device: &str,
+    sample_rate: u32,
+    languages: Vec<Language>,
+) -> Result<DeepgramTranscript> {
+    if api_key.trim().is_empty() {
+        return Err(anyhow!("Missing DEEPGRAM_API_KEY"));
+    }
+
+    let wav_bytes = encode_wav(audio, sample_rate)?;
+    log::debug!("Sending {} bytes of audio from {} to Deepgram", wav_bytes.len(), device);
+
+    let resp = http_client::get_client()
+        .post(listen_url)
+        .query(&listen_query(&languages))
+        .header("Authorization", format!("Token {}", api_key))
+        .header("Content-Type", "audio/wav")
+        .body(wav_bytes)
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let err_body = resp.text().await.unwrap_or_default();
+        return Err(anyhow!("Deepgram API error {status}: {err_body}"));
+    }
+
+    let body: ListenResponse = resp.json().await?;
+    let alternative = body
+        .results
+        .channels
+        .into_iter()
+        .next()
+        .and_then(|channel| channel.alternatives.into_iter().next())
+        .ok_or_else(|| anyhow!("Deepgram response has no transcript"))?;
+
+    let speakers = (!alternative.words.is_empty()).then(|| speaker_segments(alternative.words));
+    Ok(DeepgramTranscript { transcript: alternative.transcript.trim().to_string(), speakers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    struct CapturedRequest {
+        request_line: String,
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    }
+
+    impl CapturedRequest {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    // Answers a single request with `status` and `body`, handing back what was sent
+    async fn mock_deepgram(status: &'static str, body: &'static str) -> (String, tokio::task::JoinHandle<CapturedRequest>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1/listen", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 8192];
+            let header_end = loop {
+                let read = stream.read(&mut buf).await.unwrap();
+                received.extend_from_slice(&buf[..read]);
+                if let Some(pos) = received.windows(4).position(|window| window == b"\r\n\r\n") {
+                    break pos + 4;
+                }
+            };
+            let head = String::from_utf8_lossy(&received[..header_end]).to_string();
+            let mut lines = head.lines();
+            let request_line = lines.next().unwrap_or_default().to_string();
+            let headers: Vec<(String, String)> = lines
+                .filter_map(|line| line.split_once(':'))
+                .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                .collect();
+            let content_length: usize = headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+                .and_then(|(_, value)| value.parse().ok())
+                .unwrap_or(0);
+            while received.len() < header_end + content_length {
+                let read = stream.read(&mut buf).await.unwrap();
+                received.extend_from_slice(&buf[..read]);
+            }
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            CapturedRequest { request_line, headers, body: received[header_end..].to_vec() }
+        });
+        (url, server)
+    }
+
+    const DIARIZED_RESPONSE: &str = r#"{"results":{"channels":[{"alternatives":[{
+        "transcript":" hello there general kenobi ",
+        "words":[
+            {"word":"hello","punctuated_word":"Hello","start":0.0,"end":0.4,"speaker":0},
+            {"word":"there","punctuated_word":"there.","start":0.4,"end":0.8,"speaker":0},
+            {"word":"general","punctuated_word":"General","start":1.0,"end":1.5,"speaker":1},
+            {"word":"kenobi","punctuated_word":"Kenobi!","start":1.5,"end":2.0,"speaker":1}
+        ]}]}]}}"#;
+
+    #[tokio::test]
+    async fn posts_wav_with_nova2_query() {
+        let (url, server) = mock_deepgram("200 OK", DIARIZED_RESPONSE).await;
+        let audio: Vec<f32> = (0..1600).map(|n| (n as f32 / 1600.0) - 0.5).collect();
+
+        let result = transcribe_with_deepgram_at(&url, "test-key", &audio, "test", 16000, Vec::new())
+            .await
+            .unwrap();
+        assert_eq!(result.transcript, "hello there general kenobi");
+
+        let request = server.await.unwrap();
+        assert!(request.request_line.starts_with("POST /v1/listen?"), "{}", request.request_line);
+        for param in ["model=nova-2", "language=en", "punctuate=true", "diarize=true"] {
+            assert!(request.request_line.contains(param), "{} missing from {}", param, request.request_line);
+        }
+        assert_eq!(request.header("authorization"), Some("Token test-key"));
+        assert_eq!(request.header("content-type"), Some("audio/wav"));
+
+        let mut reader = hound::WavReader::new(Cursor::new(request.body)).unwrap();
+        let spec = reader.spec();
+        assert_eq!((spec.channels, spec.sample_rate, spec.bits_per_sample), (1, 16000, 16));
+        assert_eq!(spec.sample_format, hound::SampleFormat::Int);
+        let samples: Vec<i16> = reader.samples::<i16>().map(|sample| sample.unwrap()).collect();
+        assert_eq!(samples.len(), audio.len());
+        assert_eq!(samples[0], (-0.5 * 32767.0) as i16);
+    }
+
+    #[tokio::test]
+    async fn groups_words_by_speaker() {
+        let (url, server) = mock_deepgram("200 OK", DIARIZED_RESPONSE).await;
+        let result = transcribe_with_deepgram_at(&url, "test-key", &[0.0; 160], "test", 16000, Vec::new())
+            .await
+            .unwrap();
+        server.await.unwrap();
+
+        let speakers = result.speakers.unwrap();
+        assert_eq!(speakers.len(), 2);
+        assert_eq!((speakers[0].speaker, speakers[0].text.as_str()), (0, "Hello there."));
+        assert_eq!((speakers[1].speaker, speakers[1].text.as_str()), (1, "General Kenobi!"));
+        assert_eq!((speakers[1].start, speakers[1].end), (1.0, 2.0));
+    }
+
+    #[tokio::test]
+    async fn surfaces_api_errors() {
+        let (url, server) = mock_deepgram("401 Unauthorized", r#"{"err_msg":"Invalid credentials."}"#).await;
+        let error = transcribe_with_deepgram_at(&url, "bad-key", &[0.0; 160], "test", 16000, Vec::new())
+            .await
+            .unwrap_err()
+            .to_string();
+        server.await.unwrap();
+        assert!(error.contains("401") && error.contains("Invalid credentials"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_key_without_a_request() {
+        assert!(transcribe_with_deepgram_at("http://127.0.0.1:9", " ", &[0.0; 160], "test", 16000, Vec::new())
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,266 @@
+// Labels transcript segments by speaker by clustering MFCC voice embeddings
+use log::{debug as log_debug, info as log_info};
+use once_cell::sync::Lazy;
+use realfft::RealFftPlanner;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::{store, transcript_store};
+
+const SAMPLE_RATE: u32 = 16000;
+const FRAME_LEN: usize = 400; // 25 ms
+const HOP_LEN: usize = 160; // 10 ms
+const FFT_LEN: usize = 512;
+const MEL_BANDS: usize = 26;
+// c0 only tracks loudness, so coefficients 1..=12 are kept
+const CEPSTRAL_COEFFS: usize = 13;
+// Frames quieter than this are pauses and would pull every speaker towards the same point
+const MIN_FRAME_RMS: f32 = 0.005;
+const MIN_VOICED_FRAMES: usize = 20;
+
+const MAX_SPEAKERS: usize = 8;
+// Embeddings closer than this (cosine distance) to a centroid belong to that speaker
+const NEW_SPEAKER_DISTANCE: f32 = 0.25;
+// Recent embeddings kept per speaker; the centroid follows the voice as the meeting goes on
+const EMBEDDINGS_PER_SPEAKER: usize = 20;
+
+// Speaker ids restart with every recording, so names are kept per meeting (or per session until it is saved)
+const SPEAKER_NAMES_KEY_PREFIX: &str = "speakerNames_";
+
+fn speaker_names_key(meeting_id: &str) -> String {
+    format!("{}{}", SPEAKER_NAMES_KEY_PREFIX, meeting_id)
+}
+
+struct Speaker {
+    id: String,
+    embeddings: VecDeque<Vec<f32>>,
+    centroid: Vec<f32>,
+}
+
+impl Speaker {
+    fn new(id: String, embedding: Vec<f32>) -> Self {
+        let mut speaker = Self { id, embeddings: VecDeque::new(), centroid: Vec::new() };
+        speaker.add(embedding);
+        speaker
+    }
+
+    // Online k-means step: the centroid is the mean of the rolling buffer
+    fn add(&mut self, embedding: Vec<f32>) {
+        if self.embeddings.len() == EMBEDDINGS_PER_SPEAKER {
+            self.embeddings.pop_front();
+        }
+        self.embeddings.push_back(embedding);
+        let mut centroid = vec![0.0; self.embeddings[0].len()];
+        for embedding in &self.embeddings {
+            for (c, value) in centroid.iter_mut().zip(embedding) {
+                *c += value / self.embeddings.len() as f32;
+            }
+        }
+        self.centroid = centroid;
+    }
+}
+
+/// Assigns 16 kHz speech to up to eight speakers, "Speaker A" through "Speaker H".
+pub struct SpeakerTracker {
+    speakers: Vec<Speaker>,
+    last_speaker: Option<String>,
+    mel_filters: Vec<Vec<f32>>,
+}
+
+impl SpeakerTracker {
+    pub fn new() -> Self {
+        Self { speakers: Vec::new(), last_speaker: None, mel_filters: mel_filterbank() }
+    }
+
+    /// Returns the speaker id for `samples`. Audio too short or quiet to embed is
+    /// attributed to whoever spoke last.
+    pub fn identify_speaker(&mut self, samples: &[f32]) -> String {
+        let Some(embedding) = self.embed(samples) else {
+            return self.last_speaker.clone().unwrap_or_else(|| speaker_label(0));
+        };
+
+        let nearest = self
+            .speakers
+            .iter()
+            .enumerate()
+            .map(|(index, speaker)| (index, cosine_distance(&embedding, &speaker.centroid)))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        let id = match nearest {
+            Some((index, distance)) if distance < NEW_SPEAKER_DISTANCE || self.speakers.len() == MAX_SPEAKERS => {
+                log_debug!("Matched {} at cosine distance {:.3}", self.speakers[index].id, distance);
+                self.speakers[index].add(embedding);
+                self.speakers[index].id.clone()
+            }
+            _ => {
+                let id = speaker_label(self.speakers.len());
+                log_info!("New speaker detected: {}", id);
+                self.speakers.push(Speaker::new(id.clone(), embedding));
+                id
+            }
+        };
+        self.last_speaker = Some(id.clone());
+        id
+    }
+
+    // Mean and standard deviation of MFCCs 1..=12 over the voiced frames
+    fn embed(&self, samples: &[f32]) -> Option<Vec<f32>> {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let fft = planner.plan_fft_forward(FFT_LEN);
+        let window: Vec<f32> = (0..FRAME_LEN)
+            .map(|i| 0.54 - 0.46 * (2.0 * std::f32::consts::PI * i as f32 / (FRAME_LEN - 1) as f32).cos())
+            .collect();
+        let mut input = fft.make_input_vec();
+        let mut spectrum = fft.make_output_vec();
+
+        let mut frames: Vec<Vec<f32>> = Vec::new();
+        for start in (0..samples.len().saturating_sub(FRAME_LEN)).step_by(HOP_LEN) {
+            let frame = &samples[start..start + FRAME_LEN];
+            let rms = (frame.iter().map(|s| s * s).sum::<f32>() / FRAME_LEN as f32).sqrt();
+            if rms < MIN_FRAME_RMS {
+                continue;
+            }
+            input.iter_mut().for_each(|value| *value = 0.0);
+            for (i, (&sample, &w)) in frame.iter().zip(&window).enumerate() {
+                input[i] = sample * w;
+            }
+            if fft.process(&mut input, &mut spectrum).is_err() {
+                continue;
+            }
+            let power: Vec<f32> = spectrum.iter().map(|c| c.norm_sqr() / FFT_LEN as f32).collect();
+            let log_mel: Vec<f32> = self
+                .mel_filters
+                .iter()
+                .map(|filter| filter.iter().zip(&power).map(|(f, p)| f * p).sum::<f32>().max(1e-10).ln())
+                .collect();
+            frames.push(dct(&log_mel)[1..CEPSTRAL_COEFFS].to_vec());
+        }
+        if frames.len() < MIN_VOICED_FRAMES {
+            return None;
+        }
+
+        let dims = CEPSTRAL_COEFFS - 1;
+        let count = frames.len() as f32;
+        let mean: Vec<f32> = (0..dims).map(|d| frames.iter().map(|f| f[d]).sum::<f32>() / count).collect();
+        let std: Vec<f32> = (0..dims)
+            .map(|d| (frames.iter().map(|f| (f[d] - mean[d]).powi(2)).sum::<f32>() / count).sqrt())
+            .collect();
+        Some(mean.into_iter().chain(std).collect())
+    }
+}
+
+fn speaker_label(index: usize) -> String {
+    format!("Speaker {}", (b'A' + index as u8) as char)
+}
+
+fn hz_to_mel(hz: f32) -> f32 {
+    2595.0 * (1.0 + hz / 700.0).log10()
+}
+
+fn mel_to_hz(mel: f32) -> f32 {
+    700.0 * (10f32.powf(mel / 2595.0) - 1.0)
+}
+
+// Triangular filters spaced evenly on the mel scale up to Nyquist
+fn mel_filterbank() -> Vec<Vec<f32>> {
+    let bins = FFT_LEN / 2 + 1;
+    let max_mel = hz_to_mel(SAMPLE_RATE as f32 / 2.0);
+    let edges: Vec<f32> = (0..MEL_BANDS + 2)
+        .map(|i| mel_to_hz(max_mel * i as f32 / (MEL_BANDS + 1) as f32) * FFT_LEN as f32 / SAMPLE_RATE as f32)
+        .collect();
+    (0..MEL_BANDS)
+        .map(|band| {
+            let (left, center, right) = (edges[band], edges[band + 1], edges[band + 2]);
+            (0..bins)
+                .map(|bin| {
+                    let bin = bin as f32;
+                    if bin <= left || bin >= right {
+                        0.0
+                    } else if bin <= center {
+                        (bin - left) / (center - left)
+                    } else {
+                        (right - bin) / (right - center)
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn dct(values: &[f32]) -> Vec<f32> {
+    let n = values.len() as f32;
+    (0..values.len())
+        .map(|k| {
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| v * (std::f32::consts::PI * k as f32 * (i as f32 + 0.5) / n).cos())
+                .sum()
+        })
+        .collect()
+}
+
+fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        1.0
+    } else {
+        1.0 - dot / norm
+    }
+}
+
+// Speakers are only meaningful within one recording, so this is replaced when capture starts
+static SPEAKER_TRACKER: Lazy<Mutex<SpeakerTracker>> = Lazy::new(|| Mutex::new(SpeakerTracker::new()));
+
+pub fn reset() {
+    if let Ok(mut tracker) = SPEAKER_TRACKER.lock() {
+        *tracker = SpeakerTracker::new();
+    }
+}
+
+pub fn identify_speaker(samples: &[f32]) -> Option<String> {
+    SPEAKER_TRACKER.lock().ok().map(|mut tracker| tracker.identify_speaker(samples))
+}
+
+/// Moves the names given during recording session `from` to meeting `to`.
+pub fn reassign_speaker_names<R: Runtime>(app: &AppHandle<R>, from: &str, to: &str) -> Result<(), String> {
+    let Some(names) = store::get_value::<HashMap<String, String>>(app, &speaker_names_key(from)) else {
+        return Ok(());
+    };
+    store::set_value(app, &speaker_names_key(to), &names)?;
+    store::delete_value(app, &speaker_names_key(from))
+}
+
+/// Display names the user gave speaker ids in `meeting_id` (a meeting id, or the session id while recording).
+#[tauri::command]
+pub fn get_speaker_names<R: Runtime>(app: AppHandle<R>, meeting_id: String) -> HashMap<String, String> {
+    store::get_value(&app, &speaker_names_key(&meeting_id)).unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn rename_speaker<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    old_id: String,
+    new_name: String,
+) -> Result<(), String> {
+    let new_name = new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err("Speaker name cannot be empty".to_string());
+    }
+    let mut names = get_speaker_names(app.clone(), meeting_id.clone());
+    names.insert(old_id.clone(), new_name.clone());
+    store::set_value(&app, &speaker_names_key(&meeting_id), &names)?;
+    // Segments already stored carry the id they were transcribed with
+    match transcript_store::rename_speaker(&meeting_id, &old_id, &new_name) {
+        Ok(updated) => log_info!("Renamed {} to {} in meeting {} ({} segments)", old_id, new_name, meeting_id, updated),
+        Err(e) => log::error!("Failed to relabel stored segments for meeting {}: {}", meeting_id, e),
+    }
+    // Lets the live transcript relabel segments it is already showing
+    if let Err(e) = app.emit("speaker-renamed", (&meeting_id, &old_id, &new_name)) {
+        log::error!("Failed to emit speaker-renamed event: {}", e);
+    }
+    Ok(())
+}
@@ -0,0 +1,191 @@
+// Disk-backed queue for transcription chunks that failed every retry, so the audio can be resent later
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use log::{error as log_error, info as log_info, warn as log_warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::{send_audio_chunk, transcription_backend, TranscriptResponse};
+
+const QUEUE_DIR: &str = "meetily_failed_chunks";
+const MAX_AGE_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FailedChunkMeta {
+    chunk_seq: u64,
+    recording_session_id: String,
+    stream_url: String,
+    failed_at: String,
+    #[serde(default)]
+    retry_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedChunkInfo {
+    pub id: String,
+    pub size_bytes: u64,
+    pub failed_at: String,
+    pub retry_count: u32,
+}
+
+pub struct FailedChunkQueue {
+    dir: PathBuf,
+}
+
+impl FailedChunkQueue {
+    pub fn new() -> Self {
+        Self { dir: std::env::temp_dir().join(QUEUE_DIR) }
+    }
+
+    fn chunk_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("failed_chunk_{}.bin", id))
+    }
+
+    fn meta_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.meta.json", id))
+    }
+
+    // Ids come from the frontend, so keep them to the digits we generate
+    fn validate_id(id: &str) -> Result<()> {
+        anyhow::ensure!(!id.is_empty() && id.chars().all(|c| c.is_ascii_digit()), "Invalid failed chunk id: {}", id);
+        Ok(())
+    }
+
+    /// Writes the samples as little-endian f32 and returns the new chunk id.
+    fn push(&self, samples: &[f32], chunk_seq: u64, recording_session_id: String, stream_url: String) -> Result<String> {
+        fs::create_dir_all(&self.dir).with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let now = Utc::now();
+        // Several chunks can fail in the same millisecond; the sequence number keeps ids unique
+        let id = format!("{}{:06}", now.timestamp_millis(), chunk_seq % 1_000_000);
+
+        fs::write(self.chunk_path(&id), bytemuck::cast_slice::<f32, u8>(samples))?;
+        let meta = FailedChunkMeta {
+            chunk_seq,
+            recording_session_id,
+            stream_url,
+            failed_at: now.to_rfc3339(),
+            retry_count: 0,
+        };
+        fs::write(self.meta_path(&id), serde_json::to_vec_pretty(&meta)?)?;
+        Ok(id)
+    }
+
+    fn read_meta(&self, id: &str) -> Result<FailedChunkMeta> {
+        let contents = fs::read(self.meta_path(id)).with_context(|| format!("Failed chunk {} not found", id))?;
+        Ok(serde_json::from_slice(&contents)?)
+    }
+
+    fn read_samples(&self, id: &str) -> Result<Vec<f32>> {
+        let bytes = fs::read(self.chunk_path(id))?;
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect())
+    }
+
+    fn remove(&self, id: &str) {
+        let _ = fs::remove_file(self.chunk_path(id));
+        let _ = fs::remove_file(self.meta_path(id));
+    }
+
+    fn ids(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut ids: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_str()?.to_string();
+                name.strip_suffix(".meta.json").map(str::to_string)
+            })
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    pub fn list(&self) -> Vec<FailedChunkInfo> {
+        self.ids()
+            .into_iter()
+            .filter_map(|id| {
+                let meta = self.read_meta(&id).ok()?;
+                let size_bytes = fs::metadata(self.chunk_path(&id)).ok()?.len();
+                Some(FailedChunkInfo { id, size_bytes, failed_at: meta.failed_at, retry_count: meta.retry_count })
+            })
+            .collect()
+    }
+
+    /// Deletes chunks older than `max_age`, or with unreadable metadata; returns how many were removed.
+    pub fn purge_older_than(&self, max_age: ChronoDuration) -> usize {
+        let cutoff = Utc::now() - max_age;
+        let mut removed = 0;
+        for id in self.ids() {
+            let expired = self
+                .read_meta(&id)
+                .ok()
+                .and_then(|meta| DateTime::parse_from_rfc3339(&meta.failed_at).ok())
+                .map_or(true, |failed_at| failed_at.with_timezone(&Utc) < cutoff);
+            if expired {
+                self.remove(&id);
+                removed += 1;
+            }
+        }
+        removed
+    }
+}
+
+pub fn purge_expired() {
+    let removed = FailedChunkQueue::new().purge_older_than(ChronoDuration::hours(MAX_AGE_HOURS));
+    if removed > 0 {
+        log_info!("Removed {} failed transcription chunks older than {} hours", removed, MAX_AGE_HOURS);
+    }
+}
+
+/// Persists a chunk that exhausted its retries and tells the frontend about it.
+pub fn enqueue<R: Runtime>(app: &AppHandle<R>, samples: &[f32], chunk_seq: u64, session_id: String, stream_url: String) {
+    let queue = FailedChunkQueue::new();
+    match queue.push(samples, chunk_seq, session_id, stream_url) {
+        Ok(id) => {
+            log_warn!("Saved failed chunk {} as {} for manual retry", chunk_seq, id);
+            if let Some(info) = queue.list().into_iter().find(|info| info.id == id) {
+                if let Err(e) = app.emit("failed-chunk-queued", info) {
+                    log_error!("Failed to emit failed-chunk-queued event: {}", e);
+                }
+            }
+        }
+        Err(e) => log_error!("Failed to persist failed chunk {}: {}", chunk_seq, e),
+    }
+}
+
+#[tauri::command]
+pub async fn list_failed_chunks() -> Result<Vec<FailedChunkInfo>, String> {
+    Ok(FailedChunkQueue::new().list())
+}
+
+// Returns None when the retry fails again; the chunk stays queued with a bumped retry count
+#[tauri::command]
+pub(crate) async fn retry_failed_chunk(chunk_id: String) -> Result<Option<TranscriptResponse>, String> {
+    let queue = FailedChunkQueue::new();
+    FailedChunkQueue::validate_id(&chunk_id).map_err(|e| e.to_string())?;
+    let mut meta = queue.read_meta(&chunk_id).map_err(|e| e.to_string())?;
+    let samples = queue
+        .read_samples(&chunk_id)
+        .map_err(|e| format!("Failed to read failed chunk {}: {}", chunk_id, e))?;
+
+    match send_audio_chunk(samples, &reqwest::Client::new(), transcription_backend()).await {
+        Ok(response) => {
+            queue.remove(&chunk_id);
+            log_info!("Retried failed chunk {} (session {})", chunk_id, meta.recording_session_id);
+            Ok(Some(response))
+        }
+        Err(e) => {
+            log_warn!("Retry of failed chunk {} failed: {}", chunk_id, e);
+            meta.retry_count += 1;
+            let contents = serde_json::to_vec_pretty(&meta).map_err(|e| e.to_string())?;
+            fs::write(queue.meta_path(&chunk_id), contents)
+                .map_err(|e| format!("Failed to update failed chunk {}: {}", chunk_id, e))?;
+            Ok(None)
+        }
+    }
+}
@@ -2,11 +2,91 @@
 use anyhow::{anyhow, Result};
 use hound;
 use reqwest::multipart::{Form, Part};
-use reqwest::Client;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use std::io::Cursor;
+use std::sync::RwLock;
 use screenpipe_core::Language;
+use tauri::{AppHandle, Runtime};
 
-/// Transcribe the provided audio buffer with Groq Whisper Large v3 Turbo.
+use crate::{http_client, store};
+
+const DEFAULT_GROQ_MODEL: &str = "whisper-large-v3-turbo";
+const GROQ_MODEL_KEY: &str = "groqModelId";
+
+// Mirrors the "groqModelId" store key so transcription doesn't need an app handle
+static GROQ_MODEL_ID: Lazy<RwLock<String>> = Lazy::new(|| RwLock::new(DEFAULT_GROQ_MODEL.to_string()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroqModel {
+    pub id: String,
+    pub owned_by: String,
+    pub context_window: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GroqModelList {
+    data: Vec<GroqModel>,
+}
+
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(model_id) = store::get_value::<String>(app, GROQ_MODEL_KEY).filter(|id| !id.is_empty()) {
+        log::info!("Using Groq model {}", model_id);
+        set_current_model(model_id);
+    }
+}
+
+fn current_model() -> String {
+    GROQ_MODEL_ID
+        .read()
+        .map(|id| id.clone())
+        .unwrap_or_else(|_| DEFAULT_GROQ_MODEL.to_string())
+}
+
+fn set_current_model(model_id: String) {
+    if let Ok(mut id) = GROQ_MODEL_ID.write() {
+        *id = model_id;
+    }
+}
+
+/// Lists the speech-to-text models available to `api_key`.
+#[tauri::command]
+pub async fn list_groq_models(api_key: String) -> Result<Vec<GroqModel>, String> {
+    let resp = http_client::get_client()
+        .get("https://api.groq.com/openai/v1/models")
+        .bearer_auth(&api_key)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Groq: {}", e))?;
+
+    if !resp.status().is_success() {
+        return Err(format!("Groq API error {}", resp.status()));
+    }
+
+    let models: GroqModelList = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Groq models: {}", e))?;
+    Ok(models
+        .data
+        .into_iter()
+        .filter(|m| m.id.contains("whisper"))
+        .collect())
+}
+
+#[tauri::command]
+pub fn set_groq_model<R: Runtime>(app: AppHandle<R>, model_id: String) -> Result<(), String> {
+    if model_id.trim().is_empty() {
+        return Err("Model id cannot be empty".to_string());
+    }
+    store::set_value(&app, GROQ_MODEL_KEY, &model_id)?;
+    log::info!("Groq model set to {}", model_id);
+    set_current_model(model_id);
+    Ok(())
+}
+
+/// Transcribe the provided audio buffer with the selected Groq Whisper model
+/// (Whisper Large v3 Turbo unless the user picked another one).
 ///
 /// * `api_key` – Groq API key.
 /// * `audio`   – Mono PCM samples in the range [-1.0, 1.0].
@@ -52,12 +132,12 @@ pub async fn transcribe_with_groq(
 
     let form = Form::new()
         .part("file", file_part)
-        .text("model", "whisper-large-v3-turbo")
+        .text("model", current_model())
         .text("response_format", "text")
         .text("temperature", "0")
         .text("language", language_code);
 
-    let client = Client::new();
+    let client = http_client::get_client();
     let resp = client
         .post("https://api.groq.com/openai/v1/audio/transcriptions")
         .bearer_auth(api_key)
@@ -73,4 +153,61 @@ pub async fn transcribe_with_groq(
 
     let transcript = resp.text().await?.trim().to_string();
     Ok(transcript)
-}
\ No newline at end of file
+}
+// Window size for streaming transcription; short enough to feel live, long enough for Whisper context
+const STREAMING_WINDOW_SECS: u32 = 5;
+// Trailing audio shorter than this is dropped instead of sent on its own
+const STREAMING_MIN_TAIL_SECS: f32 = 0.5;
+
+/// Transcribe audio as it arrives, sending a partial transcript every ~5 seconds.
+///
+/// Samples received on `audio_rx` are buffered into 5 s windows and each window is
+/// sent to Groq as soon as it fills; non-empty results are forwarded on `result_tx`.
+/// Returns once `audio_rx` is closed (after flushing the remaining audio) or when
+/// the receiving side of `result_tx` goes away.
+pub async fn transcribe_with_groq_streaming(
+    api_key: &str,
+    mut audio_rx: tokio::sync::mpsc::Receiver<Vec<f32>>,
+    result_tx: tokio::sync::mpsc::Sender<String>,
+    sample_rate: u32,
+) -> Result<()> {
+    if api_key.trim().is_empty() {
+        return Err(anyhow!("Missing GROQ_API_KEY"));
+    }
+
+    let window_samples = (sample_rate * STREAMING_WINDOW_SECS) as usize;
+    let mut buffer: Vec<f32> = Vec::with_capacity(window_samples);
+
+    while let Some(samples) = audio_rx.recv().await {
+        buffer.extend(samples);
+        while buffer.len() >= window_samples {
+            let window: Vec<f32> = buffer.drain(..window_samples).collect();
+            if !send_window(api_key, &window, sample_rate, &result_tx).await {
+                return Ok(());
+            }
+        }
+    }
+
+    if buffer.len() as f32 >= sample_rate as f32 * STREAMING_MIN_TAIL_SECS {
+        send_window(api_key, &buffer, sample_rate, &result_tx).await;
+    }
+    Ok(())
+}
+
+// Returns false when nobody is listening for results anymore
+async fn send_window(
+    api_key: &str,
+    window: &[f32],
+    sample_rate: u32,
+    result_tx: &tokio::sync::mpsc::Sender<String>,
+) -> bool {
+    match transcribe_with_groq(api_key, window, sample_rate, Vec::new()).await {
+        Ok(text) if text.is_empty() => true,
+        Ok(text) => result_tx.send(text).await.is_ok(),
+        Err(e) => {
+            // A failed window is lost, but the stream keeps going
+            log::error!("Groq streaming window failed: {}", e);
+            true
+        }
+    }
+}
@@ -0,0 +1,119 @@
+// Global hotkey that toggles recording while the window is not focused
+use log::{info as log_info, error as log_error};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::recording_state::RecordingState;
+use crate::{recording_active, start_recording_session, stop_recording_session, store, RecordingArgs};
+
+const DEFAULT_RECORDING_HOTKEY: &str = "Ctrl+Shift+R";
+
+#[derive(Debug, Serialize, Clone)]
+struct HotkeyTriggered {
+    shortcut: String,
+    action: String,
+}
+
+pub fn init<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    app.plugin(
+        tauri_plugin_global_shortcut::Builder::new()
+            .with_handler(|app, shortcut, event| {
+                if event.state() == ShortcutState::Pressed {
+                    toggle_recording(app.clone(), shortcut.to_string());
+                }
+            })
+            .build(),
+    )
+    .map_err(|e| format!("Failed to initialize global shortcut plugin: {}", e))?;
+
+    #[cfg(target_os = "macos")]
+    check_accessibility_permission();
+
+    // An empty stored value means the user disabled the hotkey
+    let hotkey = store::get_value::<String>(app, "recordingHotkey")
+        .unwrap_or_else(|| DEFAULT_RECORDING_HOTKEY.to_string());
+    if hotkey.is_empty() {
+        log_info!("Recording hotkey disabled");
+        return Ok(());
+    }
+
+    let shortcut = parse_shortcut(&hotkey)?;
+    app.global_shortcut()
+        .register(shortcut)
+        .map_err(|e| format!("Failed to register recording hotkey {}: {}", hotkey, e))?;
+    log_info!("Registered recording hotkey: {}", hotkey);
+    Ok(())
+}
+
+fn parse_shortcut(shortcut: &str) -> Result<Shortcut, String> {
+    shortcut
+        .parse::<Shortcut>()
+        .map_err(|e| format!("Invalid shortcut '{}': {}", shortcut, e))
+}
+
+fn toggle_recording<R: Runtime>(app: AppHandle<R>, shortcut: String) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<RecordingState>().inner().clone();
+        let (action, result) = if recording_active() {
+            // The frontend owns the save location, so the hotkey stop never writes audio
+            let args = RecordingArgs { save_path: String::new(), save_audio: false, save_raw_channels: false, meeting_id: None, session_id: None };
+            ("stop", stop_recording_session(app.clone(), &state, args).await)
+        } else {
+            ("start", start_recording_session(app.clone(), &state, None, None).await.map(|_| ()))
+        };
+
+        if let Err(e) = result {
+            log_error!("Recording hotkey failed to {} recording: {}", action, e);
+            return;
+        }
+
+        log_info!("Recording hotkey {} triggered {}", shortcut, action);
+        let payload = HotkeyTriggered { shortcut, action: action.to_string() };
+        if let Err(e) = app.emit("hotkey-triggered", payload) {
+            log_error!("Failed to emit hotkey-triggered event: {}", e);
+        }
+    });
+}
+
+#[cfg(target_os = "macos")]
+fn check_accessibility_permission() {
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+    }
+
+    // Carbon hotkeys work without accessibility access, but some key combinations
+    // are swallowed by other apps unless the user grants it
+    if !unsafe { AXIsProcessTrusted() } {
+        log::warn!("Accessibility permission not granted; enable it in System Settings → Privacy & Security → Accessibility if the recording hotkey does not respond");
+    }
+}
+
+#[tauri::command]
+pub fn register_recording_hotkey<R: Runtime>(app: AppHandle<R>, shortcut: String) -> Result<(), String> {
+    let parsed = parse_shortcut(&shortcut)?;
+
+    let global_shortcut = app.global_shortcut();
+    global_shortcut
+        .unregister_all()
+        .map_err(|e| format!("Failed to unregister previous hotkey: {}", e))?;
+    global_shortcut
+        .register(parsed)
+        .map_err(|e| format!("Failed to register recording hotkey {}: {}", shortcut, e))?;
+
+    store::set_value(&app, "recordingHotkey", &shortcut)?;
+    log_info!("Recording hotkey changed to {}", shortcut);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unregister_recording_hotkey<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    app.global_shortcut()
+        .unregister_all()
+        .map_err(|e| format!("Failed to unregister recording hotkey: {}", e))?;
+
+    store::set_value(&app, "recordingHotkey", &String::new())?;
+    log_info!("Recording hotkey disabled");
+    Ok(())
+}
@@ -0,0 +1,157 @@
+// Shared reqwest client for external APIs, routed through the user's proxy when one is configured
+use log::{error as log_error, info as log_info};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use tauri::{AppHandle, Runtime};
+
+use crate::store;
+
+const PROXY_KEY: &str = "httpProxy";
+const USER_AGENT_KEY: &str = "httpUserAgent";
+// The transcription server and backend run locally and must never go through the proxy
+const NO_PROXY_HOSTS: &str = "localhost,127.0.0.1,::1";
+
+static CLIENT: Lazy<RwLock<Arc<reqwest::Client>>> = Lazy::new(|| RwLock::new(Arc::new(direct_client())));
+// Mirrored from the "httpUserAgent" store key so clients can be rebuilt without an AppHandle
+static USER_AGENT: Lazy<RwLock<String>> = Lazy::new(|| RwLock::new(default_user_agent()));
+
+fn default_user_agent() -> String {
+    format!("MeetingMinutes/{} Tauri/{}", env!("CARGO_PKG_VERSION"), tauri::VERSION)
+}
+
+fn current_user_agent() -> String {
+    USER_AGENT
+        .read()
+        .map(|user_agent| user_agent.clone())
+        .unwrap_or_else(|_| default_user_agent())
+}
+
+fn direct_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .user_agent(current_user_agent())
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyProtocol {
+    Http,
+    Https,
+    Socks5,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyConfig {
+    pub protocol: ProxyProtocol,
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl ProxyConfig {
+    fn url(&self) -> String {
+        let scheme = match self.protocol {
+            ProxyProtocol::Http => "http",
+            ProxyProtocol::Https => "https",
+            ProxyProtocol::Socks5 => "socks5h",
+        };
+        format!("{}://{}:{}", scheme, self.host, self.port)
+    }
+}
+
+fn try_build_client(proxy: Option<&ProxyConfig>) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder().user_agent(current_user_agent());
+    if let Some(config) = proxy {
+        let mut proxy = reqwest::Proxy::all(config.url())
+            .map_err(|e| format!("Invalid proxy {}: {}", config.url(), e))?
+            .no_proxy(reqwest::NoProxy::from_string(NO_PROXY_HOSTS));
+        if let Some(username) = &config.username {
+            proxy = proxy.basic_auth(username, config.password.as_deref().unwrap_or(""));
+        }
+        builder = builder.proxy(proxy);
+    }
+    builder.build().map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+/// Builds a client for `proxy`, falling back to a direct client if the proxy is unusable.
+pub fn build_client(proxy: Option<ProxyConfig>) -> reqwest::Client {
+    try_build_client(proxy.as_ref()).unwrap_or_else(|e| {
+        log_error!("{}; using a direct connection", e);
+        direct_client()
+    })
+}
+
+pub fn get_client() -> Arc<reqwest::Client> {
+    CLIENT
+        .read()
+        .map(|client| client.clone())
+        .unwrap_or_else(|_| Arc::new(direct_client()))
+}
+
+fn set_client(client: reqwest::Client) {
+    if let Ok(mut guard) = CLIENT.write() {
+        *guard = Arc::new(client);
+    }
+}
+
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(user_agent) = store::get_value::<String>(app, USER_AGENT_KEY) {
+        if let Ok(mut guard) = USER_AGENT.write() {
+            *guard = user_agent;
+        }
+    }
+
+    let proxy = store::get_value::<ProxyConfig>(app, PROXY_KEY);
+    if let Some(config) = &proxy {
+        log_info!("Routing external requests through proxy {}", config.url());
+    }
+    set_client(build_client(proxy));
+}
+
+#[tauri::command]
+pub fn set_http_proxy<R: Runtime>(app: AppHandle<R>, config: Option<ProxyConfig>) -> Result<(), String> {
+    // Surface configuration errors here rather than silently falling back
+    let client = try_build_client(config.as_ref())?;
+
+    match &config {
+        Some(config) => {
+            store::set_value(&app, PROXY_KEY, config)?;
+            log_info!("HTTP proxy set to {}", config.url());
+        }
+        None => {
+            store::delete_value(&app, PROXY_KEY)?;
+            log_info!("HTTP proxy disabled");
+        }
+    }
+    set_client(client);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_http_user_agent<R: Runtime>(app: AppHandle<R>, user_agent: String) -> Result<(), String> {
+    let user_agent = user_agent.trim().to_string();
+    if user_agent.is_empty() {
+        return Err("User-Agent must not be empty".to_string());
+    }
+    if user_agent.contains(['\r', '\n']) {
+        return Err("User-Agent must not contain newlines".to_string());
+    }
+    reqwest::header::HeaderValue::from_str(&user_agent)
+        .map_err(|e| format!("Invalid User-Agent: {}", e))?;
+
+    store::set_value(&app, USER_AGENT_KEY, &user_agent)?;
+    if let Ok(mut guard) = USER_AGENT.write() {
+        *guard = user_agent.clone();
+    }
+    set_client(build_client(store::get_value::<ProxyConfig>(&app, PROXY_KEY)));
+    log_info!("HTTP User-Agent set to {}", user_agent);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_http_user_agent() -> String {
+    current_user_agent()
+}
@@ -0,0 +1,100 @@
+// Files meeting action items as issues in a user-chosen JIRA project
+use anyhow::{anyhow, Result};
+use log::{info as log_info, warn as log_warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::http_client;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JiraConfig {
+    pub base_url: String,
+    // Jira Cloud API tokens authenticate with Basic auth as the account's email
+    pub email: String,
+    pub api_token: String,
+    pub project_key: String,
+    pub issue_type: String,
+}
+
+pub struct JiraIssue {
+    pub summary: String,
+    pub description: String,
+    pub priority: Option<String>,
+    // Display name or email; resolved to an account id before filing
+    pub assignee: Option<String>,
+}
+
+pub struct CreatedIssue {
+    pub key: String,
+    pub url: String,
+}
+
+// API v3 only accepts rich text as Atlassian Document Format
+fn adf_document(text: &str) -> Value {
+    let paragraphs: Vec<Value> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| json!({ "type": "paragraph", "content": [{ "type": "text", "text": line }] }))
+        .collect();
+    json!({ "type": "doc", "version": 1, "content": paragraphs })
+}
+
+// Cloud only accepts account ids for users; the first match for `query` is taken
+async fn find_account_id(config: &JiraConfig, query: &str) -> Result<Option<String>> {
+    let response = http_client::get_client()
+        .get(format!("{}/rest/api/3/user/search", config.base_url.trim_end_matches('/')))
+        .basic_auth(&config.email, Some(&config.api_token))
+        .query(&[("query", query)])
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("JIRA user search failed: {}", response.status()));
+    }
+    let users: Vec<Value> = response.json().await?;
+    Ok(users
+        .first()
+        .and_then(|user| user.get("accountId"))
+        .and_then(|id| id.as_str())
+        .map(str::to_string))
+}
+
+/// Creates one issue and returns its key and browse URL.
+pub async fn create_issue(config: &JiraConfig, issue: &JiraIssue) -> Result<CreatedIssue> {
+    let base_url = config.base_url.trim_end_matches('/');
+    let mut fields = json!({
+        "project": { "key": config.project_key },
+        "issuetype": { "name": config.issue_type },
+        "summary": issue.summary,
+        "description": adf_document(&issue.description),
+    });
+    if let Some(priority) = issue.priority.as_deref().filter(|p| !p.trim().is_empty()) {
+        fields["priority"] = json!({ "name": priority });
+    }
+    if let Some(assignee) = issue.assignee.as_deref().map(str::trim).filter(|a| !a.is_empty()) {
+        match find_account_id(config, assignee).await? {
+            Some(account_id) => fields["assignee"] = json!({ "accountId": account_id }),
+            None => log_warn!("No JIRA user matches '{}'; leaving the issue unassigned", assignee),
+        }
+    }
+
+    let response = http_client::get_client()
+        .post(format!("{}/rest/api/3/issue", base_url))
+        .basic_auth(&config.email, Some(&config.api_token))
+        .json(&json!({ "fields": fields }))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("JIRA API error {}: {}", status, body));
+    }
+
+    let body: Value = response.json().await?;
+    let key = body
+        .get("key")
+        .and_then(|key| key.as_str())
+        .ok_or_else(|| anyhow!("JIRA response did not include an issue key"))?
+        .to_string();
+    log_info!("Created JIRA issue {}", key);
+    Ok(CreatedIssue { url: format!("{}/browse/{}", base_url, key), key })
+}
@@ -1,32 +1,90 @@
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, AtomicU32, Ordering}};
 use std::time::Duration;
 use serde::{Deserialize, Serialize};
+use once_cell::sync::Lazy;
 
 // Declare audio module
 pub mod audio;
 pub mod ollama;
 pub mod groq;
+pub mod http_client;
 pub mod deepgram;
+pub mod diarization;
+pub mod failed_chunks;
+pub mod api;
+pub mod analytics;
+pub mod assemblyai;
+pub mod hotkey;
+pub mod jira_integration;
+pub mod llm;
+pub mod meeting_templates;
+#[cfg(feature = "local-whisper")]
+mod local_whisper;
+pub mod notion_integration;
+pub mod openai_whisper;
+pub mod plugin;
+mod recording_state;
+pub mod rest_api;
+pub mod server_metrics;
+pub mod slack_integration;
+pub mod storage;
+pub mod store;
+pub mod telemetry;
+pub mod templates;
+#[cfg(feature = "integration-tests")]
+#[doc(hidden)]
+pub mod test_support;
+pub mod transcript;
+mod transcript_store;
+pub mod transcription_errors;
+pub mod transcription_failover;
+pub mod updater;
 
 use audio::{
-    default_input_device, default_output_device, AudioStream,
-    encode_single_audio,
+    default_input_device, default_output_device, list_audio_devices, parse_audio_device, AudioDevice, AudioError, DeviceType, AudioStream, AudioStreamConfig, AudioTranscriptionEngine,
+    encode_single_audio, await_stream_reconnect, StreamReconnector,
 };
+use audio::audio_processing::{
+    apply_eq_shelf, current_silence_removal, rms_and_peak, eq_shelf, highpass_cutoff, map_to_original_time, remove_silence_segments,
+    chunk_has_speech, vad_config, ButterworthFilter, MonoDownmixStrategy, SilenceRemovalConfig, VadConfig, HIGHPASS_ORDER,
+};
+use audio::resample::resample_audio;
+use audio::ring_buffer::{RingBuffer, BUFFER_SECONDS_KEY, DEFAULT_CAPACITY_SECS};
+use recording_state::{RecordingState, SampleBuffer};
+use audio::sync::{MixingConfig, SyncStats, SyncedMixer, MAX_MIX_GAIN, MIXING_CONFIG_KEY};
 use ollama::{OllamaModel};
-use tauri::{Runtime, AppHandle, Emitter};
-use log::{info as log_info, error as log_error, debug as log_debug};
+use tauri::{Runtime, AppHandle, Emitter, Manager, State};
+use log::{info as log_info, error as log_error, debug as log_debug, warn as log_warn};
 use reqwest::multipart::{Form, Part};
+use tokio::sync::broadcast::error::TryRecvError;
 
 static RECORDING_FLAG: AtomicBool = AtomicBool::new(false);
-static mut MIC_BUFFER: Option<Arc<Mutex<Vec<f32>>>> = None;
-static mut SYSTEM_BUFFER: Option<Arc<Mutex<Vec<f32>>>> = None;
-static mut MIC_STREAM: Option<Arc<AudioStream>> = None;
-static mut SYSTEM_STREAM: Option<Arc<AudioStream>> = None;
-static mut IS_RUNNING: Option<Arc<AtomicBool>> = None;
-static mut RECORDING_START_TIME: Option<std::time::Instant> = None;
-
-// Audio configuration constants
+// Streams keep running while paused; the capture loop drops what they deliver
+static PAUSED_FLAG: AtomicBool = AtomicBool::new(false);
+// Capture rate of the buffered mic/system audio, needed when the backend writes the file itself
+static RECORDING_SAMPLE_RATE: AtomicU32 = AtomicU32::new(0);
+// Per-device gain offsets in dB, mirrored from the "deviceVolumeOffsets" store key
+static DEVICE_VOLUME_OFFSETS: Lazy<Mutex<HashMap<String, f32>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+// Extra headers for self-hosted transcription servers, mirrored from "transcriptServerHeaders"
+static TRANSCRIPT_SERVER_HEADERS: Lazy<Mutex<reqwest::header::HeaderMap>> =
+    Lazy::new(|| Mutex::new(reqwest::header::HeaderMap::new()));
+// Whisper language hint mirrored from "transcriptionLanguage"; None lets the server auto-detect
+static TRANSCRIPTION_LANGUAGE: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+static ACTIVE_SESSION: Lazy<Mutex<Option<RecordingSession>>> = Lazy::new(|| Mutex::new(None));
+static AUDIO_STATS: Lazy<Mutex<RealtimeAudioStats>> = Lazy::new(|| Mutex::new(RealtimeAudioStats::default()));
+static SYNC_STATS: Lazy<Mutex<SyncStats>> = Lazy::new(|| Mutex::new(SyncStats::default()));
+// Last spectrum and when it was computed, so pollers faster than 60 Hz share one FFT
+static SPECTRUM_CACHE: Lazy<Mutex<Option<(std::time::Instant, audio::spectrum::SpectrumSnapshot)>>> =
+    Lazy::new(|| Mutex::new(None));
+static SPECTRUM_STREAM_TASK: Lazy<Mutex<Option<tauri::async_runtime::JoinHandle<()>>>> = Lazy::new(|| Mutex::new(None));
+static CHUNK_SCHEDULER: Lazy<Mutex<AdaptiveChunkScheduler>> =
+    Lazy::new(|| Mutex::new(AdaptiveChunkScheduler::new(&TranscriptionConfig::default())));
+
+const TRANSCRIPTION_SERVER_URL: &str = "http://127.0.0.1:8178";
+
+// Audio configuration constants; chunking and sentence timing are defaults for TranscriptionConfig
 const CHUNK_DURATION_MS: u32 = 30000; // 30 seconds per chunk for better sentence processing
 const WHISPER_SAMPLE_RATE: u32 = 16000; // Whisper's required sample rate
 const WAV_SAMPLE_RATE: u32 = 44100; // WAV file sample rate
@@ -35,51 +93,297 @@ const WHISPER_CHANNELS: u16 = 1; // Mono for Whisper API
 const SENTENCE_TIMEOUT_MS: u64 = 1000; // Emit incomplete sentence after 1 second of silence
 const MIN_CHUNK_DURATION_MS: u32 = 2000; // Minimum duration before sending chunk
 const MIN_RECORDING_DURATION_MS: u64 = 2000; // 2 seconds minimum
+const CAPTURE_TASK_STOP_TIMEOUT: Duration = Duration::from_secs(60); // Covers the last requests and their retries
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 3; // Transcription requests in flight at once
 
 #[derive(Debug, Deserialize)]
 struct RecordingArgs {
     save_path: String,
+    // Honour the audioFormat setting (FLAC) instead of always writing WAV
+    #[serde(default)]
+    save_audio: bool,
+    // Also write `<name>_mic.wav` and `<name>_system.wav` next to the mix, for debugging
+    #[serde(default)]
+    save_raw_channels: bool,
+    // Set when the frontend already created the meeting for this recording
+    #[serde(default)]
+    meeting_id: Option<String>,
+    // Stops only if it matches the active session
+    #[serde(default)]
+    session_id: Option<String>,
+}
+
+// The audio pipeline is process-wide, so at most one window can own a recording at a time
+#[derive(Debug, Clone)]
+struct RecordingSession {
+    session_id: String,
+    window_label: Option<String>,
+    started_at: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(Debug, Serialize, Clone)]
-struct TranscriptUpdate {
-    text: String,
-    timestamp: String,
-    source: String,
+struct RecordingSessionInfo {
+    session_id: String,
+    window_label: Option<String>,
+    start_time: String,
+    duration_secs: u64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TranscriptUpdate {
+    pub text: String,
+    pub timestamp: String,
+    pub source: String,
+    // "Speaker A".. from live diarization; display names live under "speakerNames_<meeting id>"
+    #[serde(default)]
+    pub speaker_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 struct TranscriptSegment {
     text: String,
     t0: f32,
     t1: f32,
+    // Filled in locally after the server responds
+    #[serde(default)]
+    speaker_id: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 struct TranscriptResponse {
     segments: Vec<TranscriptSegment>,
     buffer_size_ms: i32,
 }
 
+#[derive(Debug, Serialize, Clone, Default)]
+struct RealtimeAudioStats {
+    mic_samples_received: u64,
+    system_samples_received: u64,
+    chunks_sent: u32,
+    chunks_failed: u32,
+    avg_chunk_latency_ms: f64,
+    buffer_fill_percent: f32,
+    last_mic_sample_age_ms: u64,
+}
+
+fn update_audio_stats<F: FnOnce(&mut RealtimeAudioStats)>(update: F) {
+    if let Ok(mut stats) = AUDIO_STATS.lock() {
+        update(&mut stats);
+    }
+}
+
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+const TRANSCRIPTION_CONFIG_KEY: &str = "transcriptionConfig";
+const CHUNK_DURATION_RANGE_MS: std::ops::RangeInclusive<u32> = 500..=120_000;
+
+/// Chunking, sentence timing and transcription mix weights, persisted under "transcriptionConfig".
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+struct TranscriptionConfig {
+    chunk_duration_ms: u32,
+    min_chunk_duration_ms: u32,
+    sentence_timeout_ms: u64,
+    mix_ratio_mic: f32,
+    mix_ratio_system: f32,
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self::with_mixing(&MixingConfig::default())
+    }
+}
+
+impl TranscriptionConfig {
+    // Until a transcription config is saved, the mix ratios follow the mixingConfig gains
+    fn with_mixing(mixing: &MixingConfig) -> Self {
+        Self {
+            chunk_duration_ms: CHUNK_DURATION_MS,
+            min_chunk_duration_ms: MIN_CHUNK_DURATION_MS,
+            sentence_timeout_ms: SENTENCE_TIMEOUT_MS,
+            mix_ratio_mic: mixing.mic_gain,
+            mix_ratio_system: mixing.system_gain,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for (name, value) in [("Chunk duration", self.chunk_duration_ms), ("Minimum chunk duration", self.min_chunk_duration_ms)] {
+            if !CHUNK_DURATION_RANGE_MS.contains(&value) {
+                return Err(format!(
+                    "{} must be between {} and {} ms, got {}",
+                    name,
+                    CHUNK_DURATION_RANGE_MS.start(),
+                    CHUNK_DURATION_RANGE_MS.end(),
+                    value
+                ));
+            }
+        }
+        if self.chunk_duration_ms < self.min_chunk_duration_ms {
+            return Err(format!(
+                "Chunk duration ({} ms) must not be shorter than the minimum chunk duration ({} ms)",
+                self.chunk_duration_ms, self.min_chunk_duration_ms
+            ));
+        }
+        for (name, ratio) in [("Mic", self.mix_ratio_mic), ("System", self.mix_ratio_system)] {
+            if !(0.0..=MAX_MIX_GAIN).contains(&ratio) {
+                return Err(format!("{} mix ratio must be between 0.0 and {}", name, MAX_MIX_GAIN));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Shrinks the chunk duration when the transcription server keeps up comfortably
+#[derive(Debug)]
+struct AdaptiveChunkScheduler {
+    avg_response_ms: f64,
+    sample_count: u32,
+    min_chunk_ms: u32,
+    max_chunk_ms: u32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct AdaptiveStats {
+    current_chunk_ms: u32,
+    avg_response_ms: u64,
+    sample_count: u32,
+}
+
+impl AdaptiveChunkScheduler {
+    fn new(config: &TranscriptionConfig) -> Self {
+        Self {
+            avg_response_ms: 0.0,
+            sample_count: 0,
+            min_chunk_ms: config.min_chunk_duration_ms,
+            max_chunk_ms: config.chunk_duration_ms,
+        }
+    }
+
+    fn update_latency(&mut self, response_time_ms: u64) {
+        let response_time_ms = response_time_ms as f64;
+        self.avg_response_ms = if self.sample_count == 0 {
+            response_time_ms
+        } else {
+            LATENCY_EWMA_ALPHA * response_time_ms + (1.0 - LATENCY_EWMA_ALPHA) * self.avg_response_ms
+        };
+        self.sample_count += 1;
+    }
+
+    // Two response times of headroom keeps requests from piling up
+    fn recommended_chunk_ms(&self) -> u32 {
+        if self.sample_count == 0 {
+            return self.max_chunk_ms;
+        }
+        ((2.0 * self.avg_response_ms) as u32).clamp(self.min_chunk_ms, self.max_chunk_ms)
+    }
+
+    fn stats(&self) -> AdaptiveStats {
+        AdaptiveStats {
+            current_chunk_ms: self.recommended_chunk_ms(),
+            avg_response_ms: self.avg_response_ms.round() as u64,
+            sample_count: self.sample_count,
+        }
+    }
+}
+
 // Helper struct to accumulate transcript segments
 #[derive(Debug)]
 struct TranscriptAccumulator {
     current_sentence: String,
     sentence_start_time: f32,
+    sentence_speaker: Option<String>,
     last_update_time: std::time::Instant,
-    last_segment_hash: u64,
+    sentence_timeout: Duration,
+    // Raw segments are kept under this id for re-processing; None when replaying stored ones
+    segment_store_id: Option<String>,
+    deduplicator: transcript::TranscriptDeduplicator,
+    // Chunks are transcribed concurrently; these keep them in recording order
+    next_chunk_seq: u64,
+    next_response_seq: u64,
+}
+
+// A chunk's response with the 16 kHz audio that was sent, so speakers are identified in recording order
+struct ChunkTranscript {
+    response: TranscriptResponse,
+    samples: Vec<f32>,
+    // Maps times in the sent audio back to the recording when silence was cut out
+    time_map: Vec<(f32, f32)>,
 }
 
+// Responses that arrived ahead of an earlier chunk, keyed by chunk sequence number.
+// Failed requests are recorded as None so the ordering doesn't stall on them.
+type PendingResponses = Arc<Mutex<BTreeMap<u64, Option<ChunkTranscript>>>>;
+
 impl TranscriptAccumulator {
-    fn new() -> Self {
+    fn new(
+        deduplicator: transcript::TranscriptDeduplicator,
+        sentence_timeout: Duration,
+        segment_store_id: Option<String>,
+    ) -> Self {
         Self {
             current_sentence: String::new(),
             sentence_start_time: 0.0,
+            sentence_speaker: None,
             last_update_time: std::time::Instant::now(),
-            last_segment_hash: 0,
+            sentence_timeout,
+            segment_store_id,
+            deduplicator,
+            next_chunk_seq: 0,
+            next_response_seq: 0,
+        }
+    }
+
+    fn take_chunk_seq(&mut self) -> u64 {
+        let seq = self.next_chunk_seq;
+        self.next_chunk_seq += 1;
+        seq
+    }
+
+    // Feeds every response that is next in line into the accumulator
+    fn drain_responses<R: Runtime>(&mut self, pending: &PendingResponses, app: &AppHandle<R>) {
+        loop {
+            let chunk = match pending.lock() {
+                Ok(mut pending) => match pending.remove(&self.next_response_seq) {
+                    Some(chunk) => chunk,
+                    None => return,
+                },
+                Err(_) => return,
+            };
+            self.next_response_seq += 1;
+
+            let Some(ChunkTranscript { mut response, samples, time_map }) = chunk else {
+                continue;
+            };
+            for segment in &mut response.segments {
+                // Segment times still match the audio that was sent at this point
+                let start = ((segment.t0.max(0.0) * WHISPER_SAMPLE_RATE as f32) as usize).min(samples.len());
+                let end = ((segment.t1.max(0.0) * WHISPER_SAMPLE_RATE as f32) as usize).clamp(start, samples.len());
+                segment.speaker_id = diarization::identify_speaker(&samples[start..end]);
+                if !time_map.is_empty() {
+                    segment.t0 = map_to_original_time(&time_map, segment.t0);
+                    segment.t1 = map_to_original_time(&time_map, segment.t1);
+                }
+            }
+            log_info!("Received {} transcript segments", response.segments.len());
+            for segment in response.segments {
+                log_info!("Processing segment: {} ({:.1}s - {:.1}s)", 
+                         segment.text.trim(), segment.t0, segment.t1);
+                if let (Some(store_id), false) = (&self.segment_store_id, segment.text.trim().is_empty()) {
+                    if let Err(e) = transcript_store::insert(store_id, &segment, "Mixed Audio") {
+                        log_error!("Failed to store raw transcript segment: {}", e);
+                    }
+                }
+                // Add segment to accumulator and check for complete sentence
+                if let Some(update) = self.add_segment(&segment) {
+                    // Emit the update
+                    if let Err(e) = app.emit("transcript-update", update) {
+                        log_error!("Failed to emit transcript update: {}", e);
+                    }
+                }
+            }
         }
     }
 
+    #[cfg_attr(feature = "otel-tracing", tracing::instrument(level = "debug", skip_all))]
     fn add_segment(&mut self, segment: &TranscriptSegment) -> Option<TranscriptUpdate> {
         log_info!("Processing new transcript segment: {:?}", segment);
         
@@ -87,11 +391,17 @@ impl TranscriptAccumulator {
         self.last_update_time = std::time::Instant::now();
 
         // Clean up the text (remove [BLANK_AUDIO], [AUDIO OUT] and trim)
-        let clean_text = segment.text
+        let mut clean_text = segment.text
             .replace("[BLANK_AUDIO]", "")
             .replace("[AUDIO OUT]", "")
             .trim()
             .to_string();
+        if let Some(pipeline) = transcript::processing::current_pipeline() {
+            match transcript::apply_post_processing_pipeline(&clean_text, &pipeline) {
+                Ok(processed) => clean_text = processed.trim().to_string(),
+                Err(e) => log_error!("Transcript post-processing failed, keeping raw text: {}", e),
+            }
+        }
             
         if !clean_text.is_empty() {
             log_info!("Clean transcript text: {}", clean_text);
@@ -102,23 +412,16 @@ impl TranscriptAccumulator {
             return None;
         }
 
-        // Calculate hash of this segment to detect duplicates
-        use std::hash::{Hash, Hasher};
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        segment.text.hash(&mut hasher);
-        segment.t0.to_bits().hash(&mut hasher);
-        segment.t1.to_bits().hash(&mut hasher);
-        let segment_hash = hasher.finish();
-
-        // Skip if this is a duplicate segment
-        if segment_hash == self.last_segment_hash {
+        // Skip segments the server already returned, exactly or with shifted timestamps
+        if self.deduplicator.is_duplicate(&clean_text, segment.t0, segment.t1) {
+            log_debug!("Dropping duplicate transcript segment: {}", clean_text);
             return None;
         }
-        self.last_segment_hash = segment_hash;
 
         // If this is the start of a new sentence, store the start time
         if self.current_sentence.is_empty() {
             self.sentence_start_time = segment.t0;
+            self.sentence_speaker = segment.speaker_id.clone();
         }
 
         // Add the new text with proper spacing
@@ -134,6 +437,7 @@ impl TranscriptAccumulator {
                 text: sentence.trim().to_string(),
                 timestamp: format!("{:.1} - {:.1}", self.sentence_start_time, segment.t1),
                 source: "Mixed Audio".to_string(),
+                speaker_id: self.sentence_speaker.take(),
             };
             log_info!("Generated transcript update: {:?}", update);
             Some(update)
@@ -143,14 +447,23 @@ impl TranscriptAccumulator {
     }
 
     fn check_timeout(&mut self) -> Option<TranscriptUpdate> {
-        if !self.current_sentence.is_empty() && 
-           self.last_update_time.elapsed() > Duration::from_millis(SENTENCE_TIMEOUT_MS) {
+        if self.last_update_time.elapsed() > self.sentence_timeout {
+            self.flush()
+        } else {
+            None
+        }
+    }
+
+    // Emits whatever is left of the current sentence
+    fn flush(&mut self) -> Option<TranscriptUpdate> {
+        if !self.current_sentence.is_empty() {
             let sentence = std::mem::take(&mut self.current_sentence);
-            let current_time = self.sentence_start_time + (SENTENCE_TIMEOUT_MS as f32 / 1000.0);
+            let current_time = self.sentence_start_time + self.sentence_timeout.as_secs_f32();
             let update = TranscriptUpdate {
                 text: sentence.trim().to_string(),
                 timestamp: format!("{:.1} - {:.1}", self.sentence_start_time, current_time),
                 source: "Mixed Audio".to_string(),
+                speaker_id: self.sentence_speaker.take(),
             };
             Some(update)
         } else {
@@ -159,8 +472,123 @@ impl TranscriptAccumulator {
     }
 }
 
-async fn send_audio_chunk(chunk: Vec<f32>, client: &reqwest::Client) -> Result<TranscriptResponse, String> {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+enum TranscriptionBackend {
+    #[default]
+    LocalServer,
+    Groq,
+    OpenAI,
+}
+
+const TRANSCRIPTION_BACKEND_KEY: &str = "transcriptionBackend";
+const SECONDARY_TRANSCRIPTION_BACKEND_KEY: &str = "secondaryTranscriptionBackend";
+
+// Primary and fallback backends, read from the store when each recording starts
+static TRANSCRIPTION_BACKENDS: Lazy<Mutex<(TranscriptionBackend, Option<TranscriptionBackend>)>> =
+    Lazy::new(|| Mutex::new((TranscriptionBackend::LocalServer, None)));
+
+fn load_transcription_backends<R: Runtime>(app: &AppHandle<R>) {
+    let primary = store::get_value::<TranscriptionBackend>(app, TRANSCRIPTION_BACKEND_KEY).unwrap_or_default();
+    let secondary = store::get_value::<TranscriptionBackend>(app, SECONDARY_TRANSCRIPTION_BACKEND_KEY)
+        .filter(|secondary| *secondary != primary);
+    openai_whisper::load(app);
+    log_info!("Transcription backend: {:?}, fallback: {:?}", primary, secondary);
+    if let Ok(mut backends) = TRANSCRIPTION_BACKENDS.lock() {
+        *backends = (primary, secondary);
+    }
+}
+
+fn transcription_backend() -> TranscriptionBackend {
+    TRANSCRIPTION_BACKENDS.lock().map(|backends| backends.0).unwrap_or_default()
+}
+
+fn secondary_transcription_backend() -> Option<TranscriptionBackend> {
+    TRANSCRIPTION_BACKENDS.lock().ok().and_then(|backends| backends.1)
+}
+
+// Takes effect from the next recording
+#[tauri::command]
+fn set_transcription_backend<R: Runtime>(
+    app: AppHandle<R>,
+    backend: TranscriptionBackend,
+    secondary: Option<TranscriptionBackend>,
+) -> Result<(), String> {
+    store::set_value(&app, TRANSCRIPTION_BACKEND_KEY, &backend)?;
+    match secondary.filter(|secondary| *secondary != backend) {
+        Some(secondary) => store::set_value(&app, SECONDARY_TRANSCRIPTION_BACKEND_KEY, &secondary)?,
+        None => store::delete_value(&app, SECONDARY_TRANSCRIPTION_BACKEND_KEY)?,
+    }
+    Ok(())
+}
+
+/// Transcribes a 16 kHz chunk with `backend`, falling back to the configured secondary
+/// backend once the primary has used up its retries.
+#[cfg_attr(feature = "otel-tracing", tracing::instrument(skip(chunk, client), fields(samples = chunk.len())))]
+async fn send_audio_chunk(
+    chunk: Vec<f32>,
+    client: &reqwest::Client,
+    backend: TranscriptionBackend,
+) -> Result<TranscriptResponse, String> {
+    let result = transcribe_with_backend(&chunk, client, backend).await;
+    match (result, secondary_transcription_backend()) {
+        (Err(e), Some(secondary)) if secondary != backend => {
+            log_error!("{:?} transcription failed, trying {:?}: {}", backend, secondary, e);
+            transcribe_with_backend(&chunk, client, secondary).await
+        }
+        (result, _) => result,
+    }
+}
+
+async fn transcribe_with_backend(
+    chunk: &[f32],
+    client: &reqwest::Client,
+    backend: TranscriptionBackend,
+) -> Result<TranscriptResponse, String> {
+    if backend == TranscriptionBackend::LocalServer {
+        return send_to_local_server(chunk.to_vec(), client).await;
+    }
+
+    let language = TRANSCRIPTION_LANGUAGE.lock().ok().and_then(|language| language.clone());
+    let mut retry_count = 0;
+    let text = loop {
+        let result = match backend {
+            TranscriptionBackend::Groq => {
+                let api_key = std::env::var("GROQ_API_KEY").unwrap_or_default();
+                groq::transcribe_with_groq(&api_key, chunk, WHISPER_SAMPLE_RATE, Vec::new()).await
+            }
+            _ => openai_whisper::transcribe_with_openai(chunk, WHISPER_SAMPLE_RATE, language.clone()).await,
+        };
+        match result {
+            Ok(text) => break text,
+            Err(e) if retry_count >= CHUNK_MAX_RETRIES => {
+                return Err(format!("Failed after {} retries. Last error: {}", CHUNK_MAX_RETRIES, e));
+            }
+            Err(e) => {
+                retry_count += 1;
+                let delay = Duration::from_millis(100 * 2_u64.pow(retry_count));
+                log_error!("{:?} transcription failed, retrying in {:?}: {}", backend, delay, e);
+                tokio::time::sleep(delay).await;
+            }
+        }
+    };
+
+    // Hosted APIs return plain text, so the whole chunk becomes one segment
+    let segments = if text.is_empty() {
+        Vec::new()
+    } else {
+        vec![TranscriptSegment { text, t0: 0.0, t1: chunk.len() as f32 / WHISPER_SAMPLE_RATE as f32, speaker_id: None }]
+    };
+    Ok(TranscriptResponse { segments, buffer_size_ms: 0 })
+}
+
+async fn send_to_local_server(chunk: Vec<f32>, client: &reqwest::Client) -> Result<TranscriptResponse, String> {
     log_debug!("Preparing to send audio chunk of size: {}", chunk.len());
+
+    #[cfg(feature = "local-whisper")]
+    if local_whisper::is_enabled() {
+        let segments = local_whisper::transcribe_chunk(chunk).await?;
+        return Ok(TranscriptResponse { segments, buffer_size_ms: 0 });
+    }
     
     // Convert f32 samples to bytes
     let bytes: Vec<u8> = chunk.iter()
@@ -169,9 +597,39 @@ async fn send_audio_chunk(chunk: Vec<f32>, client: &reqwest::Client) -> Result<T
             clamped.to_le_bytes().to_vec()
         })
         .collect();
-    
+
+    // While failed over, give the primary one attempt per chunk so we notice it coming back
+    if let Some(backup_url) = transcription_failover::active_backup_url() {
+        if let Ok(transcript) = post_audio_chunk(&bytes, client, TRANSCRIPTION_SERVER_URL, 0).await {
+            transcription_failover::record_primary_success();
+            return Ok(transcript);
+        }
+        return post_audio_chunk(&bytes, client, &backup_url, CHUNK_MAX_RETRIES).await;
+    }
+
+    match post_audio_chunk(&bytes, client, TRANSCRIPTION_SERVER_URL, CHUNK_MAX_RETRIES).await {
+        Ok(transcript) => {
+            transcription_failover::record_primary_success();
+            Ok(transcript)
+        }
+        Err(e) => match transcription_failover::record_primary_failure() {
+            // Don't lose the chunk that tipped us over
+            Some(backup_url) => post_audio_chunk(&bytes, client, &backup_url, CHUNK_MAX_RETRIES).await,
+            None => Err(e),
+        },
+    }
+}
+
+const CHUNK_MAX_RETRIES: u32 = 3;
+
+// POSTs raw f32 audio to `{base_url}/stream`, retrying with exponential backoff
+async fn post_audio_chunk(
+    bytes: &[u8],
+    client: &reqwest::Client,
+    base_url: &str,
+    max_retries: u32,
+) -> Result<TranscriptResponse, String> {
     // Retry configuration
-    let max_retries = 3;
     let mut retry_count = 0;
     let mut last_error = String::new();
 
@@ -185,16 +643,24 @@ async fn send_audio_chunk(chunk: Vec<f32>, client: &reqwest::Client) -> Result<T
         }
 
         // Create fresh multipart form for each attempt since Form can't be reused
-        let part = Part::bytes(bytes.clone())
+        let part = Part::bytes(bytes.to_vec())
             .file_name("audio.raw")
             .mime_str("audio/x-raw")
             .unwrap();
-        let form = Form::new().part("audio", part);
+        let mut form = Form::new().part("audio", part);
+        if let Some(language) = TRANSCRIPTION_LANGUAGE.lock().ok().and_then(|language| language.clone()) {
+            form = form.text("language", language);
+        }
+
+        let mut request = match client.post(format!("{}/stream", base_url)).multipart(form).build() {
+            Ok(request) => request,
+            Err(e) => return Err(format!("Failed to build transcription request: {}", e)),
+        };
+        if let Ok(headers) = TRANSCRIPT_SERVER_HEADERS.lock() {
+            request.headers_mut().extend(headers.clone());
+        }
 
-        match client.post("http://127.0.0.1:8178/stream")
-            .multipart(form)
-            .send()
-            .await {
+        match client.execute(request).await {
                 Ok(response) => {
                     match response.json::<TranscriptResponse>().await {
                         Ok(transcript) => return Ok(transcript),
@@ -216,71 +682,303 @@ async fn send_audio_chunk(chunk: Vec<f32>, client: &reqwest::Client) -> Result<T
     Err(format!("Failed after {} retries. Last error: {}", max_retries, last_error))
 }
 
+fn active_session() -> Option<RecordingSession> {
+    ACTIVE_SESSION.lock().ok().and_then(|session| session.clone())
+}
+
+fn set_active_session(session: Option<RecordingSession>) {
+    if let Ok(mut guard) = ACTIVE_SESSION.lock() {
+        *guard = session;
+    }
+}
+
 #[tauri::command]
-async fn start_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
-    log_info!("Attempting to start recording...");
-    
-    if is_recording() {
-        log_error!("Recording already in progress");
-        return Err("Recording already in progress".to_string());
+async fn start_recording<R: Runtime>(
+    app: AppHandle<R>,
+    window: tauri::Window<R>,
+    state: State<'_, RecordingState>,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    start_recording_session(app, &state, Some(window.label().to_string()), session_id).await
+}
+
+// Starts capture on behalf of a window (or a hotkey when `window_label` is None) and returns the session id
+async fn start_recording_session<R: Runtime>(
+    app: AppHandle<R>,
+    state: &RecordingState,
+    window_label: Option<String>,
+    session_id: Option<String>,
+) -> Result<String, String> {
+    // Claimed before any await so two starts racing on different workers can't both get through
+    if RECORDING_FLAG
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return Err(match active_session() {
+            Some(active) => {
+                log_error!("Recording session {} already in progress", active.session_id);
+                format!("Recording already in progress in session {}", active.session_id)
+            }
+            None => {
+                log_error!("Recording already in progress");
+                "Recording already in progress".to_string()
+            }
+        });
     }
 
-    // Initialize recording flag and buffers
-    RECORDING_FLAG.store(true, Ordering::SeqCst);
-    log_info!("Recording flag set to true");
+    let session_id = session_id.unwrap_or_else(|| format!("{:032x}", rand::random::<u128>()));
+    if let Err(e) = start_capture(app.clone(), state, &session_id).await {
+        RECORDING_FLAG.store(false, Ordering::SeqCst);
+        return Err(e);
+    }
 
-    // Store recording start time
-    unsafe {
-        RECORDING_START_TIME = Some(std::time::Instant::now());
+    // Raw segments wait under the session id until the transcript is saved as a meeting
+    if let Err(e) = store::set_value(&app, transcript_store::PENDING_SEGMENTS_KEY, &session_id) {
+        log_error!("Failed to store pending segments session: {}", e);
+    }
+    log_info!("Started recording session {} for {:?}", session_id, window_label);
+    let started_at = chrono::Utc::now();
+    // Picked up by api_save_transcript so absolute timestamps can be derived later
+    let started_at_ms = started_at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    if let Err(e) = store::set_value(&app, api::PENDING_RECORDING_START_KEY, &started_at_ms) {
+        log_error!("Failed to store recording start time: {}", e);
     }
+    set_active_session(Some(RecordingSession {
+        session_id: session_id.clone(),
+        window_label,
+        started_at,
+    }));
+    Ok(session_id)
+}
 
-    // Initialize audio buffers
-    unsafe {
-        MIC_BUFFER = Some(Arc::new(Mutex::new(Vec::new())));
-        SYSTEM_BUFFER = Some(Arc::new(Mutex::new(Vec::new())));
-        log_info!("Initialized audio buffers");
+// Permission and missing-device failures get a message the user can act on; the rest pass through
+fn audio_error_message<R: Runtime>(app: &AppHandle<R>, source: &str, error: AudioError) -> String {
+    log_error!("Failed to open {}: {}", source, error);
+    match error {
+        AudioError::PermissionDenied { device } => {
+            if let Err(e) = app.emit("audio-permission-denied", &device) {
+                log_error!("Failed to emit audio-permission-denied event: {}", e);
+            }
+            format!("Access to the {} ({}) was denied; allow it in your system privacy settings and try again", source, device)
+        }
+        AudioError::DeviceNotFound { .. } => format!("No {} found; connect one and try again", source),
+        error => error.into(),
     }
-    
-    // Get default devices
-    let mic_device = Arc::new(default_input_device().map_err(|e| {
-        log_error!("Failed to get default input device: {}", e);
-        e.to_string()
-    })?);
-    
-    let system_device = Arc::new(default_output_device().map_err(|e| {
-        log_error!("Failed to get default output device: {}", e);
-        e.to_string()
-    })?);
-    
-    // Create audio streams
-    let is_running = Arc::new(AtomicBool::new(true));
-    
+}
+
+const SELECTED_INPUT_DEVICE_KEY: &str = "selectedInputDevice";
+const SELECTED_OUTPUT_DEVICE_KEY: &str = "selectedOutputDevice";
+
+// Accepts a bare device name or the "Name (input)" form that `list_audio_devices` displays
+fn device_from_selection(name: &str, device_type: DeviceType) -> Result<AudioDevice, String> {
+    let suffix = match device_type {
+        DeviceType::Input => "(input)",
+        DeviceType::Output => "(output)",
+    };
+    let qualified = if name.to_lowercase().ends_with(suffix) { name.to_string() } else { format!("{} {}", name, suffix) };
+    parse_audio_device(&qualified).map_err(|e| format!("Invalid device {}: {}", name, e))
+}
+
+// The stored selection, if it is still connected; a missing device is reported to the UI
+async fn selected_device<R: Runtime>(app: &AppHandle<R>, key: &str, device_type: DeviceType) -> Option<AudioDevice> {
+    let name = store::get_value::<String>(app, key).filter(|name| !name.trim().is_empty())?;
+    let device = match device_from_selection(&name, device_type) {
+        Ok(device) => device,
+        Err(e) => {
+            log_error!("{}", e);
+            return None;
+        }
+    };
+    match list_audio_devices().await {
+        Ok(devices) if devices.contains(&device) => Some(device),
+        Ok(_) => {
+            log_error!("Selected device {} is not connected, using the default", device);
+            if let Err(e) = app.emit("device-not-found", device.to_string()) {
+                log_error!("Failed to emit device-not-found event: {}", e);
+            }
+            None
+        }
+        Err(e) => {
+            log_error!("Failed to list audio devices, using the default: {}", e);
+            None
+        }
+    }
+}
+
+#[tauri::command]
+async fn get_audio_devices() -> Result<Vec<AudioDevice>, String> {
+    list_audio_devices()
+        .await
+        .map_err(|e| format!("Failed to list audio devices: {}", e))
+}
+
+// None goes back to the system default; applies from the next recording
+#[tauri::command]
+async fn set_recording_devices<R: Runtime>(
+    app: AppHandle<R>,
+    selected_input_device: Option<String>,
+    selected_output_device: Option<String>,
+) -> Result<(), String> {
+    for (key, selection, device_type) in [
+        (SELECTED_INPUT_DEVICE_KEY, selected_input_device, DeviceType::Input),
+        (SELECTED_OUTPUT_DEVICE_KEY, selected_output_device, DeviceType::Output),
+    ] {
+        match selection.filter(|name| !name.trim().is_empty()) {
+            Some(name) => {
+                device_from_selection(&name, device_type)?;
+                store::set_value(&app, key, &name)?;
+            }
+            None => store::delete_value(&app, key)?,
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_recording_devices<R: Runtime>(app: AppHandle<R>) -> (Option<String>, Option<String>) {
+    (
+        store::get_value(&app, SELECTED_INPUT_DEVICE_KEY),
+        store::get_value(&app, SELECTED_OUTPUT_DEVICE_KEY),
+    )
+}
+
+async fn open_capture_streams<R: Runtime>(
+    app: &AppHandle<R>,
+    is_running: Arc<AtomicBool>,
+) -> Result<(Arc<AudioStream>, Arc<AudioStream>), String> {
+    // Use the devices picked in settings when they are still connected, otherwise the defaults
+    let mic_device = match selected_device(app, SELECTED_INPUT_DEVICE_KEY, DeviceType::Input).await {
+        Some(device) => device,
+        None => default_input_device().map_err(|e| audio_error_message(app, "microphone", e))?,
+    };
+    let system_device = match selected_device(app, SELECTED_OUTPUT_DEVICE_KEY, DeviceType::Output).await {
+        Some(device) => device,
+        None => default_output_device().map_err(|e| audio_error_message(app, "system audio device", e))?,
+    };
+    let (mic_device, system_device) = (Arc::new(mic_device), Arc::new(system_device));
+
+    let stream_config = store::get_value::<AudioStreamConfig>(app, AUDIO_STREAM_CONFIG_KEY).unwrap_or_default();
+
     // Create microphone stream
-    let mic_stream = AudioStream::from_device(mic_device.clone(), is_running.clone())
+    let mic_stream = AudioStream::from_device(mic_device, is_running.clone(), stream_config)
         .await
-        .map_err(|e| {
-            log_error!("Failed to create microphone stream: {}", e);
-            e.to_string()
-        })?;
-    let mic_stream = Arc::new(mic_stream);
-    
+        .map_err(|e| audio_error_message(app, "microphone", e))?;
+
     // Create system audio stream
-    let system_stream = AudioStream::from_device(system_device.clone(), is_running.clone())
+    let system_stream = AudioStream::from_device(system_device, is_running, stream_config)
         .await
-        .map_err(|e| {
-            log_error!("Failed to create system stream: {}", e);
-            e.to_string()
-        })?;
-    let system_stream = Arc::new(system_stream);
+        .map_err(|e| audio_error_message(app, "system audio device", e))?;
 
-    unsafe {
-        MIC_STREAM = Some(mic_stream.clone());
-        SYSTEM_STREAM = Some(system_stream.clone());
-        IS_RUNNING = Some(is_running.clone());
+    Ok((Arc::new(mic_stream), Arc::new(system_stream)))
+}
+
+// Expects RECORDING_FLAG to be claimed already; the caller clears it if this fails
+async fn start_capture<R: Runtime>(app: AppHandle<R>, state: &RecordingState, session_id: &str) -> Result<(), String> {
+    log_info!("Attempting to start recording...");
+
+    #[cfg(target_os = "macos")]
+    ensure_microphone_permission().await?;
+
+    #[cfg(target_os = "macos")]
+    if let Err(e) = audio::core::configure_audio_session_for_recording() {
+        log_error!("Failed to configure background recording: {}", e);
+    }
+
+    // Warn the user up front; capture may still work on the device that isn't held
+    #[cfg(target_os = "windows")]
+    audio::exclusive_mode::check_and_notify(&app);
+
+    PAUSED_FLAG.store(false, Ordering::SeqCst);
+
+    // Store recording start time
+    state.lock().await.start_time = Some(std::time::Instant::now());
+    
+    load_preferred_audio_host(&app);
+
+    // Create audio streams
+    let is_running = Arc::new(AtomicBool::new(true));
+
+    // Integration tests hand in mock streams instead of opening real devices
+    #[cfg(feature = "integration-tests")]
+    let injected_streams = test_support::take_injected_streams();
+    #[cfg(not(feature = "integration-tests"))]
+    let injected_streams = None;
+
+    let (mic_stream, system_stream) = match injected_streams {
+        Some(streams) => streams,
+        None => match open_capture_streams(&app, is_running.clone()).await {
+            Ok(streams) => streams,
+            Err(e) => {
+                // Leave nothing behind for the next attempt to trip over
+                state.take().await;
+                return Err(e);
+            }
+        },
+    };
+
+    // Sized once the capture rate is known; both streams deliver at the mic's rate
+    let buffer_secs = store::get_value::<u32>(&app, BUFFER_SECONDS_KEY).unwrap_or(DEFAULT_CAPACITY_SECS);
+    let capture_rate = mic_stream.device_config.sample_rate().0;
+    let mic_buffer: SampleBuffer = Arc::new(Mutex::new(RingBuffer::with_duration(buffer_secs, capture_rate)));
+    let system_buffer: SampleBuffer = Arc::new(Mutex::new(RingBuffer::with_duration(buffer_secs, capture_rate)));
+    log_info!("Initialized audio buffers for the last {} s at {} Hz", buffer_secs, capture_rate);
+
+    {
+        let mut recording = state.lock().await;
+        recording.mic_buffer = Some(mic_buffer.clone());
+        recording.system_buffer = Some(system_buffer.clone());
+        recording.mic_stream = Some(mic_stream.clone());
+        recording.system_stream = Some(system_stream.clone());
+        recording.is_running = Some(is_running.clone());
     }
+
+    // Optionally rebuild streams when a device is unplugged and plugged back in
+    let auto_reconnect = store::get_value::<bool>(&app, "autoReconnectDevices").unwrap_or(false);
+    let (mut mic_updates, mut system_updates) = if auto_reconnect {
+        log_info!("Automatic device reconnection enabled");
+        let mic_app = app.clone();
+        let mic_state = state.clone();
+        let mic_reconnector = StreamReconnector::spawn(mic_stream.clone(), is_running.clone(), move |stream| {
+            let state = mic_state.clone();
+            let replacement = stream.clone();
+            tokio::spawn(async move {
+                let mut recording = state.lock().await;
+                // A stop may have cleared the state while the device was coming back
+                if recording.is_running.is_some() {
+                    recording.mic_stream = Some(replacement);
+                }
+            });
+            if let Err(e) = mic_app.emit("device-reconnected", stream.device.to_string()) {
+                log_error!("Failed to emit device-reconnected event: {}", e);
+            }
+        });
+        let system_app = app.clone();
+        let system_state = state.clone();
+        let system_reconnector = StreamReconnector::spawn(system_stream.clone(), is_running.clone(), move |stream| {
+            let state = system_state.clone();
+            let replacement = stream.clone();
+            tokio::spawn(async move {
+                let mut recording = state.lock().await;
+                if recording.is_running.is_some() {
+                    recording.system_stream = Some(replacement);
+                }
+            });
+            if let Err(e) = system_app.emit("device-reconnected", stream.device.to_string()) {
+                log_error!("Failed to emit device-reconnected event: {}", e);
+            }
+        });
+        (Some(mic_reconnector.subscribe()), Some(system_reconnector.subscribe()))
+    } else {
+        (None, None)
+    };
     
     // Create HTTP client for transcription
-    let client = reqwest::Client::new();
+    let client = http_client::get_client();
+    let max_concurrent_requests = store::get_value::<usize>(&app, "maxConcurrentTranscriptionRequests")
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS)
+        .max(1);
+    let request_permits = Arc::new(tokio::sync::Semaphore::new(max_concurrent_requests));
+    let pending_responses: PendingResponses = Arc::new(Mutex::new(BTreeMap::new()));
     
     // Start transcription task
     let app_handle = app.clone();
@@ -311,23 +1009,90 @@ async fn start_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
     let chunk_counter = Arc::new(std::sync::atomic::AtomicUsize::new(0));
     let chunk_counter_clone = chunk_counter.clone();
     
+    // Resolved once per recording; saved changes apply from the next one
+    let transcription_config = get_transcription_config(app.clone());
+    log_info!("Transcription config: {:?}", transcription_config);
+
     // Create transcript accumulator
-    let mut accumulator = TranscriptAccumulator::new();
+    let dedup_config = store::get_value::<transcript::DedupConfig>(&app, DEDUP_CONFIG_KEY).unwrap_or_default();
+    let mut accumulator = TranscriptAccumulator::new(
+        transcript::TranscriptDeduplicator::new(dedup_config),
+        Duration::from_millis(transcription_config.sentence_timeout_ms),
+        Some(session_id.to_string()),
+    );
     
     let device_config = mic_stream.device_config.clone();
     let _device_name = mic_stream.device.to_string();
     let sample_rate = device_config.sample_rate().0;
     let channels = device_config.channels();
+    RECORDING_SAMPLE_RATE.store(sample_rate, Ordering::SeqCst);
+
+    load_device_volume_offsets(&app);
+    load_transcription_language(&app);
+    transcription_failover::load(&app);
+    diarization::reset();
+    load_transcription_backends(&app);
+    transcript::processing::set_current_pipeline(
+        store::get_value::<transcript::PostProcessingPipeline>(&app, POST_PROCESSING_KEY),
+    );
+    audio::audio_processing::set_current_silence_removal(get_silence_removal_config(app.clone()));
+    audio::audio_processing::set_vad_config(get_vad_config(app.clone()));
+    #[cfg(feature = "local-whisper")]
+    local_whisper::configure(&app);
+    if let Err(e) = load_transcript_server_headers(&app) {
+        log_error!("Ignoring stored transcription server headers: {}", e);
+    }
+
+    if store::get_value::<bool>(&app, "muteDetectionEnabled").unwrap_or(true) {
+        audio::level_monitor::spawn(
+            app.clone(),
+            mic_buffer.clone(),
+            system_buffer.clone(),
+            sample_rate,
+            is_running.clone(),
+            &PAUSED_FLAG,
+        );
+    }
+
+    // Streaming engines replace the 30 s chunked requests: AssemblyAI over WebSocket, Groq with 5 s windows
+    let mut streaming_audio_tx = match start_assemblyai_streaming(&app, sample_rate).await {
+        Some(tx) => Some(tx),
+        None => start_groq_streaming(&app, sample_rate),
+    };
+    // The transcription config's ratios replace the mixingConfig gains for the transcription mix only
+    let stored_mixing = store::get_value::<MixingConfig>(&app, MIXING_CONFIG_KEY).unwrap_or_default();
+    let mixing_config = MixingConfig {
+        mic_gain: transcription_config.mix_ratio_mic,
+        system_gain: transcription_config.mix_ratio_system,
+        ..stored_mixing
+    };
     
-    tokio::spawn(async move {
-        let chunk_samples = (WHISPER_SAMPLE_RATE as f32 * (CHUNK_DURATION_MS as f32 / 1000.0)) as usize;
-        let min_samples = (WHISPER_SAMPLE_RATE as f32 * (MIN_CHUNK_DURATION_MS as f32 / 1000.0)) as usize;
-        let mut current_chunk: Vec<f32> = Vec::with_capacity(chunk_samples);
+    let capture_task = tokio::spawn(async move {
+        let mut mic_stream = mic_stream;
+        let mut system_stream = system_stream;
+        if let Ok(mut scheduler) = CHUNK_SCHEDULER.lock() {
+            *scheduler = AdaptiveChunkScheduler::new(&transcription_config);
+        }
+        update_audio_stats(|stats| *stats = RealtimeAudioStats::default());
+        let mut mixer = SyncedMixer::new(sample_rate, mixing_config);
+        let mut highpass: Option<ButterworthFilter> = None;
+        let mut request_tasks: Vec<(u64, tokio::task::JoinHandle<()>)> = Vec::new();
+        let max_chunk_samples = (WHISPER_SAMPLE_RATE as f32 * (transcription_config.chunk_duration_ms as f32 / 1000.0)) as usize;
+        let min_samples = (WHISPER_SAMPLE_RATE as f32 * (transcription_config.min_chunk_duration_ms as f32 / 1000.0)) as usize;
+        let mut current_chunk: Vec<f32> = Vec::with_capacity(max_chunk_samples);
         let mut last_chunk_time = std::time::Instant::now();
         
         log_info!("Mic config: {} Hz, {} channels", sample_rate, channels);
+        let mut was_paused = false;
         
         while is_running.load(Ordering::SeqCst) {
+            let paused = PAUSED_FLAG.load(Ordering::SeqCst);
+            if was_paused && !paused {
+                // The drift clock would read the pause as the mic falling behind
+                mixer = SyncedMixer::new(sample_rate, mixing_config);
+            }
+            was_paused = paused;
+
             // Check for timeout on current sentence
             if let Some(update) = accumulator.check_timeout() {
                 if let Err(e) = app_handle.emit("transcript-update", update) {
@@ -336,82 +1101,163 @@ async fn start_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
             }
 
             // Collect audio samples
-            let mut new_samples = Vec::new();
             let mut mic_samples = Vec::new();
             let mut system_samples = Vec::new();
             
             // Get microphone samples
             let mut got_mic_samples = false;
-            while let Ok(chunk) = mic_receiver_clone.try_recv() {
+            let mut mic_closed = false;
+            loop {
+                let chunk = match mic_receiver_clone.try_recv() {
+                    Ok(chunk) => chunk,
+                    Err(TryRecvError::Closed) => {
+                        mic_closed = true;
+                        break;
+                    }
+                    Err(_) => break,
+                };
                 got_mic_samples = true;
+                if paused {
+                    continue;
+                }
                 log_debug!("Received {} mic samples", chunk.len());
-                let chunk_clone = chunk.clone();
-                mic_samples.extend(chunk);
-                
-                // Store in global buffer
-                unsafe {
-                    if let Some(buffer) = &MIC_BUFFER {
-                        if let Ok(mut guard) = buffer.lock() {
-                            guard.extend(chunk_clone);
-                        }
-                    }
+                update_audio_stats(|stats| stats.mic_samples_received += chunk.len() as u64);
+                // Store in the recording buffer
+                if let Ok(mut guard) = mic_buffer.lock() {
+                    guard.push_slice(&chunk);
                 }
+                mic_samples.extend(chunk);
             }
-            // If we didn't get any samples, try to resubscribe to clear any backlog
-            if !got_mic_samples {
+            let mic_stale = mic_closed || mic_stream.is_disconnected();
+            if let (true, Some(updates)) = (mic_stale, mic_updates.as_mut()) {
+                log_info!("Microphone stream is stale, waiting for the device to reconnect...");
+                if let Some(stream) = await_stream_reconnect(updates, &is_running).await {
+                    mic_stream = stream;
+                    mic_receiver_clone = mic_stream.subscribe().await;
+                }
+            } else if !got_mic_samples {
+                // If we didn't get any samples, try to resubscribe to clear any backlog
                 log_debug!("No mic samples received, resubscribing to clear channel");
                 mic_receiver_clone = mic_stream.subscribe().await;
             }
             
             // Get system audio samples
             let mut got_system_samples = false;
-            while let Ok(chunk) = system_receiver.try_recv() {
+            let mut system_closed = false;
+            loop {
+                let chunk = match system_receiver.try_recv() {
+                    Ok(chunk) => chunk,
+                    Err(TryRecvError::Closed) => {
+                        system_closed = true;
+                        break;
+                    }
+                    Err(_) => break,
+                };
                 got_system_samples = true;
+                if paused {
+                    continue;
+                }
                 log_debug!("Received {} system samples", chunk.len());
-                let chunk_clone = chunk.clone();
-                system_samples.extend(chunk);
-                
-                // Store in global buffer
-                unsafe {
-                    if let Some(buffer) = &SYSTEM_BUFFER {
-                        if let Ok(mut guard) = buffer.lock() {
-                            guard.extend(chunk_clone);
-                        }
-                    }
+                update_audio_stats(|stats| stats.system_samples_received += chunk.len() as u64);
+                // Store in the recording buffer
+                if let Ok(mut guard) = system_buffer.lock() {
+                    guard.push_slice(&chunk);
                 }
+                system_samples.extend(chunk);
             }
-            // If we didn't get any samples, try to resubscribe to clear any backlog
-            if !got_system_samples {
+            let system_stale = system_closed || system_stream.is_disconnected();
+            if let (true, Some(updates)) = (system_stale, system_updates.as_mut()) {
+                log_info!("System audio stream is stale, waiting for the device to reconnect...");
+                if let Some(stream) = await_stream_reconnect(updates, &is_running).await {
+                    system_stream = stream;
+                    system_receiver = system_stream.subscribe().await;
+                }
+            } else if !got_system_samples {
+                // If we didn't get any samples, try to resubscribe to clear any backlog
                 log_debug!("No system samples received, resubscribing to clear channel");
                 system_receiver = system_stream.subscribe().await;
             }
+
+            if paused {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                continue;
+            }
             
-            // Mix samples with debug info
-            let max_len = mic_samples.len().max(system_samples.len());
-            for i in 0..max_len {
-                let mic_sample = if i < mic_samples.len() { mic_samples[i] } else { 0.0 };
-                let system_sample = if i < system_samples.len() { system_samples[i] } else { 0.0 };
-                // Increase mic sensitivity by giving it more weight in the mix (80% mic, 20% system)
-                new_samples.push((mic_sample * 0.7) + (system_sample * 0.3));
+            // Even out device sensitivity before mixing
+            apply_device_gain(&mut mic_samples, &mic_stream.device.name);
+            apply_device_gain(&mut system_samples, &system_stream.device.name);
+
+            // Mix samples, correcting mic clock drift first
+            let mut new_samples = {
+                #[cfg(feature = "otel-tracing")]
+                let _mix_span = tracing::debug_span!("mix_audio", samples = mic_samples.len().max(system_samples.len())).entered();
+                mixer.mix(&mic_samples, &system_samples)
+            };
+            if let Ok(mut stats) = SYNC_STATS.lock() {
+                *stats = mixer.stats();
             }
             
             log_debug!("Mixed {} samples", new_samples.len());
-            
-            // Add samples to current chunk
-            for sample in new_samples {
+
+            if let Some(tx) = &streaming_audio_tx {
+                if new_samples.is_empty() {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    continue;
+                }
+                match tx.send(new_samples).await {
+                    Ok(()) => {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        continue;
+                    }
+                    // The session ended early; the rest of the recording goes through chunked requests
+                    Err(tokio::sync::mpsc::error::SendError(samples)) => {
+                        log_error!("Streaming transcription ended, falling back to chunked transcription");
+                        streaming_audio_tx = None;
+                        new_samples = samples;
+                    }
+                }
+            }
+            
+            // Add samples to current chunk
+            for sample in new_samples {
                 current_chunk.push(sample);
             }
             
             // Check if we should send the chunk based on size or time
+            let chunk_ms = CHUNK_SCHEDULER
+                .lock()
+                .map(|scheduler| scheduler.recommended_chunk_ms())
+                .unwrap_or(transcription_config.chunk_duration_ms);
+            let chunk_samples = (WHISPER_SAMPLE_RATE as f32 * (chunk_ms as f32 / 1000.0)) as usize;
+            update_audio_stats(|stats| {
+                stats.buffer_fill_percent = (current_chunk.len() as f32 / chunk_samples as f32 * 100.0).min(100.0);
+            });
             let should_send = current_chunk.len() >= chunk_samples || 
                             (current_chunk.len() >= min_samples && 
-                             last_chunk_time.elapsed() >= Duration::from_millis(CHUNK_DURATION_MS as u64));
+                             last_chunk_time.elapsed() >= Duration::from_millis(chunk_ms as u64));
             
             if should_send {
                 log_info!("Should send chunk with {} samples", current_chunk.len());
                 let chunk_to_send = current_chunk.clone();
                 current_chunk.clear();
                 last_chunk_time = std::time::Instant::now();
+
+                // Remove low-frequency rumble before transcription. Chunks are contiguous at this
+                // point, so the filter state carries across them without a click at each boundary.
+                let chunk_to_send = match highpass_cutoff() {
+                    Some(cutoff_hz) => {
+                        if highpass.as_ref().is_some_and(|filter| filter.cutoff_hz() != cutoff_hz) {
+                            highpass = None;
+                        }
+                        highpass
+                            .get_or_insert_with(|| ButterworthFilter::highpass(sample_rate, cutoff_hz, HIGHPASS_ORDER))
+                            .process(&chunk_to_send)
+                    }
+                    None => {
+                        highpass = None;
+                        chunk_to_send
+                    }
+                };
                 
                 // Save debug chunks
                 let chunk_num = chunk_counter_clone.fetch_add(1, Ordering::SeqCst);
@@ -512,6 +1358,35 @@ async fn start_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
                 //     }
                 // }
                 
+                // Drop long silences so Whisper neither bills for nor hallucinates over them
+                let (chunk_to_send, time_map) = match current_silence_removal() {
+                    Some(config) => remove_silence_segments(&chunk_to_send, sample_rate, &config),
+                    None => (chunk_to_send, Vec::new()),
+                };
+                if chunk_to_send.is_empty() {
+                    log_debug!("Chunk {} is entirely silent, not sending it", chunk_num);
+                    continue;
+                }
+
+                // Background noise alone only produces garbage segments
+                let vad = vad_config();
+                if !chunk_has_speech(&chunk_to_send, sample_rate, &vad) {
+                    log_debug!("Chunk {} has no speech above {} dBFS, not sending it", chunk_num, vad.threshold_db);
+                    let silence = VadSilenceEvent {
+                        chunk: chunk_num,
+                        duration_secs: chunk_to_send.len() as f32 / sample_rate as f32,
+                    };
+                    if let Err(e) = app_handle.emit("transcript-vad-silence", silence) {
+                        log_error!("Failed to emit transcript-vad-silence event: {}", e);
+                    }
+                    continue;
+                }
+
+                let chunk_to_send = match eq_shelf() {
+                    Some(eq) => apply_eq_shelf(&chunk_to_send, sample_rate, &eq),
+                    None => chunk_to_send,
+                };
+
                 // Process chunk for Whisper API
                 let whisper_samples = if sample_rate != WHISPER_SAMPLE_RATE {
                     log_debug!("Resampling audio from {} to {}", sample_rate, WHISPER_SAMPLE_RATE);
@@ -524,30 +1399,76 @@ async fn start_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
                     chunk_to_send
                 };
 
-                // Send chunk for transcription
-                match send_audio_chunk(whisper_samples, &client).await {
-                    Ok(response) => {
-                        log_info!("Received {} transcript segments", response.segments.len());
-                        for segment in response.segments {
-                            log_info!("Processing segment: {} ({:.1}s - {:.1}s)", 
-                                     segment.text.trim(), segment.t0, segment.t1);
-                            // Add segment to accumulator and check for complete sentence
-                            if let Some(update) = accumulator.add_segment(&segment) {
-                                // Emit the update
-                                if let Err(e) = app_handle.emit("transcript-update", update) {
-                                    log_error!("Failed to emit transcript update: {}", e);
-                                }
+                // Send chunk for transcription without waiting for the response
+                let seq = accumulator.take_chunk_seq();
+                let client = client.clone();
+                let pending = pending_responses.clone();
+                let failure_app = app_handle.clone();
+                let permits = request_permits.clone();
+                let request = tokio::spawn(async move {
+                    let Ok(_permit) = permits.acquire_owned().await else {
+                        return;
+                    };
+                    let request_start = std::time::Instant::now();
+                    // Kept for speaker identification, and so the audio survives if every retry fails
+                    let retry_samples = whisper_samples.clone();
+                    let result = send_audio_chunk(whisper_samples, &client, transcription_backend()).await;
+                    transcription_failover::emit_pending_event(&failure_app);
+                    let response = match result {
+                        Ok(response) => {
+                            let latency_ms = request_start.elapsed().as_millis() as u64;
+                            if let Ok(mut scheduler) = CHUNK_SCHEDULER.lock() {
+                                scheduler.update_latency(latency_ms);
                             }
+                            update_audio_stats(|stats| {
+                                stats.chunks_sent += 1;
+                                stats.avg_chunk_latency_ms +=
+                                    (latency_ms as f64 - stats.avg_chunk_latency_ms) / stats.chunks_sent as f64;
+                            });
+                            Some(ChunkTranscript { response, samples: retry_samples, time_map })
                         }
+                        Err(e) => {
+                            log_error!("Transcription error for chunk {}: {}", seq, e);
+                            update_audio_stats(|stats| stats.chunks_failed += 1);
+                            let server_url = transcription_failover::active_backup_url()
+                                .unwrap_or_else(|| TRANSCRIPTION_SERVER_URL.to_string());
+                            transcription_errors::record(
+                                seq as u32,
+                                &e,
+                                format!("{}/stream", server_url),
+                                "Queued for manual retry",
+                            );
+                            let session_id = active_session().map(|session| session.session_id).unwrap_or_default();
+                            failed_chunks::enqueue(
+                                &failure_app,
+                                &retry_samples,
+                                seq,
+                                session_id,
+                                format!("{}/stream", TRANSCRIPTION_SERVER_URL),
+                            );
+                            None
+                        }
+                    };
+                    if let Ok(mut pending) = pending.lock() {
+                        pending.insert(seq, response);
                     }
-                    Err(e) => {
-                        log_error!("Transcription error: {}", e);
-                    }
-                }
+                });
+                request_tasks.push((seq, request));
             }
+
+            let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut request_tasks)
+                .into_iter()
+                .partition(|(_, request)| request.is_finished());
+            request_tasks = running;
+            join_transcription_requests(finished, &pending_responses).await;
+            accumulator.drain_responses(&pending_responses, &app_handle);
             
             tokio::time::sleep(Duration::from_millis(10)).await;
         }
+
+        // Includes requests still queued for a permit, so the last chunks aren't dropped
+        join_transcription_requests(request_tasks, &pending_responses).await;
+        accumulator.drain_responses(&pending_responses, &app_handle);
         
         // Emit any remaining transcript when recording stops
         if let Some(update) = accumulator.check_timeout() {
@@ -558,26 +1479,181 @@ async fn start_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
         
         log_info!("Transcription task ended");
     });
+    state.lock().await.capture_task = Some(capture_task);
     
     Ok(())
 }
 
+// Waits for chunk requests; one that panicked never filed its response, so it is recorded as
+// failed to keep drain_responses from stalling on the gap
+async fn join_transcription_requests(requests: Vec<(u64, tokio::task::JoinHandle<()>)>, pending: &PendingResponses) {
+    let results = futures_util::future::join_all(
+        requests.into_iter().map(|(seq, request)| async move { (seq, request.await) }),
+    )
+    .await;
+    for (seq, result) in results {
+        if let Err(e) = result {
+            log_error!("Transcription request for chunk {} did not finish: {}", seq, e);
+            if let Ok(mut pending) = pending.lock() {
+                pending.entry(seq).or_insert(None);
+            }
+        }
+    }
+}
+
+// Spawns the Groq streaming transcriber when it is enabled for the selected engine and
+// returns the sender the capture loop should feed mixed samples into
+fn start_groq_streaming<R: Runtime>(app: &AppHandle<R>, sample_rate: u32) -> Option<tokio::sync::mpsc::Sender<Vec<f32>>> {
+    let engine = store::get_value::<AudioTranscriptionEngine>(app, "transcriptionEngine").unwrap_or_default();
+    let enabled = store::get_value::<bool>(app, "useGroqStreaming").unwrap_or(false);
+    if engine != AudioTranscriptionEngine::WhisperLargeV3Turbo || !enabled {
+        return None;
+    }
+    let Some(api_key) = std::env::var("GROQ_API_KEY").ok().filter(|key| !key.trim().is_empty()) else {
+        log_error!("Groq streaming is enabled but GROQ_API_KEY is not set, falling back to local transcription");
+        return None;
+    };
+
+    log_info!("Using Groq streaming transcription");
+    let (audio_tx, audio_rx) = tokio::sync::mpsc::channel::<Vec<f32>>(100);
+    let (result_tx, mut result_rx) = tokio::sync::mpsc::channel::<String>(10);
+
+    tokio::spawn(async move {
+        if let Err(e) = groq::transcribe_with_groq_streaming(&api_key, audio_rx, result_tx, sample_rate).await {
+            log_error!("Groq streaming transcription failed: {}", e);
+        }
+    });
+
+    let app_handle = app.clone();
+    tokio::spawn(async move {
+        let started = std::time::Instant::now();
+        let mut last_end = 0.0f32;
+        while let Some(text) = result_rx.recv().await {
+            let end = started.elapsed().as_secs_f32();
+            let update = TranscriptUpdate {
+                text,
+                timestamp: format!("{:.1} - {:.1}", last_end, end),
+                source: "Mixed Audio".to_string(),
+                speaker_id: None,
+            };
+            last_end = end;
+            if let Err(e) = app_handle.emit("transcript-update", update) {
+                log_error!("Failed to emit transcript update: {}", e);
+            }
+        }
+    });
+
+    Some(audio_tx)
+}
+
+// Opens the AssemblyAI real-time session when that engine is selected and returns the
+// sender the capture loop should feed mixed samples into. None if the session can't be
+// opened, so the caller falls back to chunked requests.
+async fn start_assemblyai_streaming<R: Runtime>(app: &AppHandle<R>, sample_rate: u32) -> Option<tokio::sync::mpsc::Sender<Vec<f32>>> {
+    let engine = store::get_value::<AudioTranscriptionEngine>(app, "transcriptionEngine").unwrap_or_default();
+    if engine != AudioTranscriptionEngine::AssemblyAI {
+        return None;
+    }
+    let Some(api_key) = std::env::var("ASSEMBLYAI_API_KEY").ok().filter(|key| !key.trim().is_empty()) else {
+        log_error!("AssemblyAI is selected but ASSEMBLYAI_API_KEY is not set, falling back to local transcription");
+        return None;
+    };
+
+    let url = store::get_value::<String>(app, assemblyai::REALTIME_URL_KEY)
+        .unwrap_or_else(|| assemblyai::DEFAULT_REALTIME_URL.to_string());
+    let mut streamer = match assemblyai::AssemblyAiStreamer::connect(&url, &api_key, sample_rate).await {
+        Ok(streamer) => streamer,
+        Err(e) => {
+            log_error!("Failed to start AssemblyAI session, falling back to chunked transcription: {}", e);
+            return None;
+        }
+    };
+
+    log_info!("Using AssemblyAI streaming transcription");
+    let (audio_tx, mut audio_rx) = tokio::sync::mpsc::channel::<Vec<f32>>(100);
+    let app_handle = app.clone();
+    tokio::spawn(async move {
+        let mut audio_open = true;
+        loop {
+            tokio::select! {
+                samples = audio_rx.recv(), if audio_open => match samples {
+                    Some(samples) => {
+                        if let Err(e) = streamer.send_chunk(&samples).await {
+                            log_error!("AssemblyAI streaming failed: {}", e);
+                            break;
+                        }
+                    }
+                    // Recording stopped; keep reading until the last final transcripts arrive
+                    None => {
+                        audio_open = false;
+                        if let Err(e) = streamer.terminate().await {
+                            log_error!("{}", e);
+                            break;
+                        }
+                    }
+                },
+                event = streamer.next_event() => match event {
+                    Ok(Some(event)) => {
+                        let (name, text, start_secs, end_secs) = match event {
+                            assemblyai::AssemblyAiEvent::Final { text, start_secs, end_secs } => ("transcript-update", text, start_secs, end_secs),
+                            assemblyai::AssemblyAiEvent::Partial { text, start_secs, end_secs } => ("transcript-partial", text, start_secs, end_secs),
+                        };
+                        let update = TranscriptUpdate {
+                            text,
+                            timestamp: format!("{:.1} - {:.1}", start_secs, end_secs),
+                            source: "Mixed Audio".to_string(),
+                            speaker_id: None,
+                        };
+                        if let Err(e) = app_handle.emit(name, update) {
+                            log_error!("Failed to emit {} event: {}", name, e);
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        log_error!("AssemblyAI streaming failed: {}", e);
+                        break;
+                    }
+                },
+            }
+        }
+        log_info!("AssemblyAI streaming session ended");
+    });
+
+    Some(audio_tx)
+}
+
 #[tauri::command]
-async fn stop_recording(args: RecordingArgs) -> Result<(), String> {
+async fn stop_recording<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, RecordingState>,
+    args: RecordingArgs,
+) -> Result<(), String> {
+    stop_recording_session(app, &state, args).await
+}
+
+async fn stop_recording_session<R: Runtime>(
+    app: AppHandle<R>,
+    state: &RecordingState,
+    mut args: RecordingArgs,
+) -> Result<(), String> {
     log_info!("Attempting to stop recording...");
     
+    if let (Some(requested), Some(active)) = (args.session_id.as_ref(), active_session()) {
+        if *requested != active.session_id {
+            return Err(format!("Session {} is not the active recording session", requested));
+        }
+    }
+
     // Only check recording state if we haven't already started stopping
     if !RECORDING_FLAG.load(Ordering::SeqCst) {
         log_info!("Recording is already stopped");
+        set_active_session(None);
         return Ok(());
     }
 
     // Check minimum recording duration
-    let elapsed_ms = unsafe {
-        RECORDING_START_TIME
-            .map(|start| start.elapsed().as_millis() as u64)
-            .unwrap_or(0)
-    };
+    let start_time = state.lock().await.start_time;
+    let elapsed_ms = start_time.map(|start| start.elapsed().as_millis() as u64).unwrap_or(0);
 
     if elapsed_ms < MIN_RECORDING_DURATION_MS {
         let remaining = MIN_RECORDING_DURATION_MS - elapsed_ms;
@@ -587,182 +1663,349 @@ async fn stop_recording(args: RecordingArgs) -> Result<(), String> {
 
     // First set the recording flag to false to prevent new data from being processed
     RECORDING_FLAG.store(false, Ordering::SeqCst);
+    PAUSED_FLAG.store(false, Ordering::SeqCst);
+    set_active_session(None);
+
+    #[cfg(target_os = "macos")]
+    if let Err(e) = audio::core::restore_audio_session() {
+        log_error!("Failed to end background recording activity: {}", e);
+    }
     log_info!("Recording flag set to false");
     
-    unsafe {
-        // Stop the running flag for audio streams first
-        if let Some(is_running) = &IS_RUNNING {
-            // Set running flag to false first to stop the tokio task
-            is_running.store(false, Ordering::SeqCst);
-            log_info!("Set recording flag to false, waiting for streams to stop...");
-            
-            // Give the tokio task time to finish and release its references
-            tokio::time::sleep(Duration::from_millis(100)).await;
-            
-            // Stop mic stream if it exists
-            if let Some(mic_stream) = &MIC_STREAM {
-                log_info!("Stopping microphone stream...");
-                if let Err(e) = mic_stream.stop().await {
-                    log_error!("Error stopping mic stream: {}", e);
-                } else {
-                    log_info!("Microphone stream stopped successfully");
-                }
+    // Take everything out of the state so the lock isn't held while the streams shut down
+    let mut recording = state.take().await;
+    let duration_secs = start_time.map(|start| start.elapsed().as_secs_f32()).unwrap_or(0.0);
+
+    // Stop the running flag for audio streams first
+    if let Some(is_running) = &recording.is_running {
+        // Set running flag to false first to stop the tokio task
+        is_running.store(false, Ordering::SeqCst);
+        log_info!("Set recording flag to false, waiting for streams to stop...");
+        
+        // Give the tokio task time to finish and release its references
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        
+        // Stop mic stream if it exists
+        if let Some(mic_stream) = &recording.mic_stream {
+            log_info!("Stopping microphone stream...");
+            match mic_stream.stop().await {
+                Ok(()) => log_info!("Microphone stream stopped successfully"),
+                Err(AudioError::DeviceDisconnected { name }) => log_info!("Microphone {} was already disconnected", name),
+                Err(e) => log_error!("Error stopping mic stream: {}", e),
             }
-            
-            // Stop system stream if it exists
-            if let Some(system_stream) = &SYSTEM_STREAM {
-                log_info!("Stopping system stream...");
-                if let Err(e) = system_stream.stop().await {
-                    log_error!("Error stopping system stream: {}", e);
-                } else {
-                    log_info!("System stream stopped successfully");
-                }
+        }
+        
+        // Stop system stream if it exists
+        if let Some(system_stream) = &recording.system_stream {
+            log_info!("Stopping system stream...");
+            match system_stream.stop().await {
+                Ok(()) => log_info!("System stream stopped successfully"),
+                Err(AudioError::DeviceDisconnected { name }) => log_info!("System device {} was already disconnected", name),
+                Err(e) => log_error!("Error stopping system stream: {}", e),
             }
-            
-            // Clear the stream references
-            MIC_STREAM = None;
-            SYSTEM_STREAM = None;
-            IS_RUNNING = None;
-            
-            // Give streams time to fully clean up
-            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        
+        // Give streams time to fully clean up
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    // The final chunks are still being transcribed; their segments must be filed before they're claimed below
+    if let Some(capture_task) = recording.capture_task.take() {
+        match tokio::time::timeout(CAPTURE_TASK_STOP_TIMEOUT, capture_task).await {
+            Ok(Ok(())) => log_info!("Transcription task finished"),
+            Ok(Err(e)) => log_error!("Transcription task failed: {}", e),
+            Err(_) => log_error!("Transcription task still running after {:?}, not waiting any longer", CAPTURE_TASK_STOP_TIMEOUT),
         }
     }
     
     // Get final buffers
-    let mic_data = unsafe {
-        if let Some(buffer) = &MIC_BUFFER {
-            if let Ok(guard) = buffer.lock() {
-                guard.clone()
-            } else {
-                Vec::new()
-            }
-        } else {
-            Vec::new()
-        }
+    let drain = |buffer: &Option<SampleBuffer>| {
+        buffer
+            .as_ref()
+            .and_then(|buffer| buffer.lock().ok())
+            .map(|mut guard| {
+                if guard.overflowed() {
+                    log_warn!(
+                        "Recording outgrew the audio buffer; only the last {} of {} samples are saved",
+                        guard.capacity(),
+                        guard.total_pushed()
+                    );
+                }
+                guard.drain_all()
+            })
+            .unwrap_or_default()
     };
-    
-    let system_data = unsafe {
-        if let Some(buffer) = &SYSTEM_BUFFER {
-            if let Ok(guard) = buffer.lock() {
-                guard.clone()
-            } else {
-                Vec::new()
+    let mic_data = drain(&recording.mic_buffer);
+    let system_data = drain(&recording.system_buffer);
+    // Streams and buffers are released here; nothing below needs them
+    drop(recording);
+
+    if let Some(meeting_id) = &args.meeting_id {
+        transcript_store::claim_pending(&app, meeting_id);
+    }
+
+    // A failed save still leaves a transcript, so its errors and title are handled before returning
+    let save_result = save_recording_audio(&app, &mut args, mic_data, system_data).await;
+    match &save_result {
+        Ok(()) if !args.save_path.is_empty() && std::path::Path::new(&args.save_path).exists() => {
+            // Describe the saved recording with a sidecar
+            if let Err(e) = write_recording_metadata(&app, &args, duration_secs).await {
+                log_error!("Failed to write recording metadata: {}", e);
             }
-        } else {
-            Vec::new()
         }
-    };
-    /*
-    // Mix the audio and convert to 16-bit PCM
-    let max_len = mic_data.len().max(system_data.len());
-    let mut mixed_data = Vec::with_capacity(max_len);
-    
-    for i in 0..max_len {
-        let mic_sample = if i < mic_data.len() { mic_data[i] } else { 0.0 };
-        let system_sample = if i < system_data.len() { system_data[i] } else { 0.0 };
-        mixed_data.push((mic_sample + system_sample) * 0.5);
+        Ok(()) => {}
+        Err(e) => log_error!("{}", e),
     }
 
-    if mixed_data.is_empty() {
-        log_error!("No audio data captured");
-        return Err("No audio data captured".to_string());
+    transcription_errors::persist(&app);
+
+    // Title generation talks to an LLM, so don't hold up the stop command
+    if store::get_value::<bool>(&app, "autoTitleEnabled").unwrap_or(false) {
+        if let Some(meeting_id) = args.meeting_id.clone() {
+            let provider = store::get_value::<String>(&app, "autoTitleProvider")
+                .unwrap_or_else(|| "ollama".to_string());
+            tokio::spawn(async move {
+                match api::api_auto_title_meeting(meeting_id.clone(), provider).await {
+                    Ok(title) => log_info!("Auto-titled meeting {}: {}", meeting_id, title),
+                    Err(e) => log_error!("Failed to auto-title meeting {}: {}", meeting_id, e),
+                }
+            });
+        }
     }
-    
-    log_info!("Mixed {} audio samples", mixed_data.len());
-    
-    // Resample the audio to 16kHz for Whisper compatibility
-    let original_sample_rate = 48000; // Assuming original sample rate is 48kHz
-    if original_sample_rate != WHISPER_SAMPLE_RATE {
-        log_info!("Resampling audio from {} Hz to {} Hz for Whisper compatibility", 
-                 original_sample_rate, WHISPER_SAMPLE_RATE);
-        mixed_data = resample_audio(&mixed_data, original_sample_rate, WHISPER_SAMPLE_RATE);
-        log_info!("Resampled to {} samples", mixed_data.len());
+
+    save_result
+}
+
+// Writes the drained buffers to `args.save_path`, switching it to the .flac path when saving FLAC
+async fn save_recording_audio<R: Runtime>(
+    app: &AppHandle<R>,
+    args: &mut RecordingArgs,
+    mic_data: Vec<f32>,
+    system_data: Vec<f32>,
+) -> Result<(), String> {
+    if args.save_path.is_empty() {
+        log_info!("No save path given, skipping audio save");
+        return Ok(());
     }
-    
-    // Convert to 16-bit PCM samples
-    let mut bytes = Vec::with_capacity(mixed_data.len() * 2);
-    for &sample in mixed_data.iter() {
-        let value = (sample.max(-1.0).min(1.0) * 32767.0) as i16;
-        bytes.extend_from_slice(&value.to_le_bytes());
+    if mic_data.is_empty() && system_data.is_empty() {
+        log_info!("No audio data captured, skipping audio save");
+        return Ok(());
     }
-    
-    log_info!("Converted to {} bytes of PCM data", bytes.len());
-
-    // Create WAV header
-    let data_size = bytes.len() as u32;
-    let file_size = 36 + data_size;
-    let sample_rate = WHISPER_SAMPLE_RATE; // Use Whisper's required sample rate (16000 Hz)
-    let channels = 1u16; // Mono
-    let bits_per_sample = 16u16;
-    let block_align = channels * (bits_per_sample / 8);
-    let byte_rate = sample_rate * block_align as u32;
-    
-    let mut wav_file = Vec::with_capacity(44 + bytes.len());
-    
-    // RIFF header
-    wav_file.extend_from_slice(b"RIFF");
-    wav_file.extend_from_slice(&file_size.to_le_bytes());
-    wav_file.extend_from_slice(b"WAVE");
-    
-    // fmt chunk
-    wav_file.extend_from_slice(b"fmt ");
-    wav_file.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
-    wav_file.extend_from_slice(&1u16.to_le_bytes()); // audio format (PCM)
-    wav_file.extend_from_slice(&channels.to_le_bytes()); // num channels
-    wav_file.extend_from_slice(&sample_rate.to_le_bytes()); // sample rate
-    wav_file.extend_from_slice(&byte_rate.to_le_bytes()); // byte rate
-    wav_file.extend_from_slice(&block_align.to_le_bytes()); // block align
-    wav_file.extend_from_slice(&bits_per_sample.to_le_bytes()); // bits per sample
-    
-    // data chunk
-    wav_file.extend_from_slice(b"data");
-    wav_file.extend_from_slice(&data_size.to_le_bytes());
-    wav_file.extend_from_slice(&bytes);
-    
-    log_info!("Created WAV file with {} bytes total", wav_file.len());
-    */
+
     // Create the save directory if it doesn't exist
     if let Some(parent) = std::path::Path::new(&args.save_path).parent() {
         if !parent.exists() {
             log_info!("Creating directory: {:?}", parent);
-            if let Err(e) = std::fs::create_dir_all(parent) {
-                let err_msg = format!("Failed to create save directory: {}", e);
-                log_error!("{}", err_msg);
-                return Err(err_msg);
-            }
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create save directory: {}", e))?;
         }
     }
 
-    /*
-    // Save the recording
-    log_info!("Saving recording to: {}", args.save_path);
-    match fs::write(&args.save_path, wav_file) {
-        Ok(_) => log_info!("Successfully saved recording"),
-        Err(e) => {
-            let err_msg = format!("Failed to save recording: {}", e);
-            log_error!("{}", err_msg);
-            return Err(err_msg);
+    let mixing_config = store::get_value::<MixingConfig>(app, MIXING_CONFIG_KEY).unwrap_or_default();
+    let save_flac = args.save_audio && store::get_value::<String>(app, "audioFormat").as_deref() == Some("flac");
+    let save_path = args.save_path.clone();
+    let save_raw_channels = args.save_raw_channels;
+    // Mixing, resampling and encoding a long recording is CPU-bound, so it stays off the async workers
+    let saved = tokio::task::spawn_blocking(move || {
+        if save_flac {
+            save_flac_recording(&save_path, &mic_data, &system_data, &mixing_config).map(Some)
+        } else {
+            save_wav_recording(&save_path, &mic_data, &system_data, &mixing_config, save_raw_channels).map(|()| None)
         }
+    })
+    .await
+    .map_err(|e| format!("Failed to save recording: {}", e))?
+    .map_err(|e| format!("Failed to save recording: {}", e))?;
+
+    if let Some(flac_path) = saved {
+        args.save_path = flac_path;
     }
-    */
-    
-    // Clean up
-    unsafe {
-        MIC_BUFFER = None;
-        SYSTEM_BUFFER = None;
-        MIC_STREAM = None;
-        SYSTEM_STREAM = None;
-        IS_RUNNING = None;
-        RECORDING_START_TIME = None;
+    Ok(())
+}
+
+fn recording_active() -> bool {
+    RECORDING_FLAG.load(Ordering::SeqCst)
+}
+
+// Keeps the devices open so resuming is instant; audio captured meanwhile is discarded
+#[tauri::command]
+async fn pause_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    if !recording_active() {
+        return Err("No recording in progress".to_string());
+    }
+    if PAUSED_FLAG.swap(true, Ordering::SeqCst) {
+        return Err("Recording is already paused".to_string());
+    }
+    log_info!("Recording paused");
+    if let Err(e) = app.emit("recording-paused", ()) {
+        log_error!("Failed to emit recording-paused event: {}", e);
     }
-    
     Ok(())
 }
 
 #[tauri::command]
-fn is_recording() -> bool {
-    RECORDING_FLAG.load(Ordering::SeqCst)
+async fn resume_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    if !recording_active() {
+        return Err("No recording in progress".to_string());
+    }
+    if !PAUSED_FLAG.swap(false, Ordering::SeqCst) {
+        return Err("Recording is not paused".to_string());
+    }
+    log_info!("Recording resumed");
+    if let Err(e) = app.emit("recording-resumed", ()) {
+        log_error!("Failed to emit recording-resumed event: {}", e);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn is_paused() -> bool {
+    recording_active() && PAUSED_FLAG.load(Ordering::SeqCst)
+}
+
+// True only when the calling window owns the active session. Sessions started without a
+// window (hotkey, scheduler, REST API) belong to every window.
+#[tauri::command]
+fn is_recording<R: Runtime>(window: tauri::Window<R>) -> bool {
+    recording_active()
+        && active_session().map_or(false, |session| match session.window_label.as_deref() {
+            Some(label) => label == window.label(),
+            None => true,
+        })
+}
+
+#[tauri::command]
+fn list_active_sessions() -> Vec<RecordingSessionInfo> {
+    active_session()
+        .into_iter()
+        .map(|session| RecordingSessionInfo {
+            duration_secs: (chrono::Utc::now() - session.started_at).num_seconds().max(0) as u64,
+            start_time: session.started_at.to_rfc3339(),
+            session_id: session.session_id,
+            window_label: session.window_label,
+        })
+        .collect()
+}
+
+// Mixes the buffered streams like the transcription loop does and writes them next to `save_path` as FLAC
+fn mix_recording(mic_data: &[f32], system_data: &[f32], mixing: &MixingConfig) -> Result<Vec<f32>, String> {
+    let max_len = mic_data.len().max(system_data.len());
+    if max_len == 0 {
+        return Err("No audio data captured".to_string());
+    }
+    Ok((0..max_len)
+        .map(|i| {
+            let mic_sample = mic_data.get(i).copied().unwrap_or(0.0);
+            let system_sample = system_data.get(i).copied().unwrap_or(0.0);
+            (mic_sample * mixing.mic_gain) + (system_sample * mixing.system_gain)
+        })
+        .collect())
+}
+
+fn recording_sample_rate() -> u32 {
+    match RECORDING_SAMPLE_RATE.load(Ordering::SeqCst) {
+        0 => WHISPER_SAMPLE_RATE,
+        rate => rate,
+    }
+}
+
+fn save_flac_recording(save_path: &str, mic_data: &[f32], system_data: &[f32], mixing: &MixingConfig) -> Result<String, String> {
+    let mixed = mix_recording(mic_data, system_data, mixing)?;
+    let sample_rate = recording_sample_rate();
+    let flac = audio::codec::encode_to_flac(&mixed, sample_rate, 1).map_err(|e| e.to_string())?;
+
+    let flac_path = std::path::Path::new(save_path)
+        .with_extension(audio::codec::AudioFormat::Flac.extension())
+        .to_string_lossy()
+        .to_string();
+    fs::write(&flac_path, &flac).map_err(|e| e.to_string())?;
+    log_info!("Saved {} samples as FLAC ({} bytes) to {}", mixed.len(), flac.len(), flac_path);
+    Ok(flac_path)
+}
+
+// 16 kHz 16-bit mono, the same format the transcription backends are sent
+fn write_wav(path: &std::path::Path, samples: &[f32]) -> Result<(), String> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: WHISPER_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let sample_rate = recording_sample_rate();
+    let samples = if sample_rate == WHISPER_SAMPLE_RATE {
+        samples.to_vec()
+    } else {
+        resample_audio(samples, sample_rate, WHISPER_SAMPLE_RATE)
+    };
+
+    let mut writer = hound::WavWriter::create(path, spec)
+        .map_err(|e| format!("Cannot write to {}: {}", path.display(), e))?;
+    for sample in samples {
+        writer
+            .write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+            .map_err(|e| format!("Failed to write samples to {}: {}", path.display(), e))?;
+    }
+    writer
+        .finalize()
+        .map_err(|e| format!("Failed to finalize {}: {}", path.display(), e))
+}
+
+fn save_wav_recording(
+    save_path: &str,
+    mic_data: &[f32],
+    system_data: &[f32],
+    mixing: &MixingConfig,
+    save_raw_channels: bool,
+) -> Result<(), String> {
+    let path = std::path::Path::new(save_path);
+    let mixed = mix_recording(mic_data, system_data, mixing)?;
+    write_wav(path, &mixed)?;
+    log_info!("Saved {} samples as WAV to {}", mixed.len(), save_path);
+
+    if save_raw_channels {
+        let stem = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+        for (channel, data) in [("mic", mic_data), ("system", system_data)] {
+            let channel_path = path.with_file_name(format!("{}_{}.wav", stem, channel));
+            write_wav(&channel_path, data)?;
+            log_info!("Saved raw {} channel to {}", channel, channel_path.display());
+        }
+    }
+    Ok(())
+}
+
+async fn write_recording_metadata<R: Runtime>(app: &AppHandle<R>, args: &RecordingArgs, duration_secs: f32) -> Result<(), String> {
+    let sample_rate = hound::WavReader::open(&args.save_path)
+        .map(|reader| reader.spec().sample_rate)
+        .ok()
+        .or_else(|| audio::codec::flac_sample_rate(std::path::Path::new(&args.save_path)))
+        .unwrap_or(WHISPER_SAMPLE_RATE);
+
+    let meeting_id = args.meeting_id.clone().unwrap_or_default();
+    let meeting = if meeting_id.is_empty() {
+        None
+    } else {
+        api::fetch_meeting(&meeting_id).await.ok()
+    };
+
+    let meta = storage::MeetingMeta {
+        meeting_id: meeting_id.clone(),
+        title: meeting.as_ref().map(|m| m.title.clone()).unwrap_or_default(),
+        created_at: meeting
+            .map(|m| m.created_at)
+            .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+        duration_secs,
+        sample_rate,
+        transcription_engine: store::get_value::<AudioTranscriptionEngine>(app, "transcriptionEngine")
+            .unwrap_or_default()
+            .to_string(),
+        language: store::get_value::<String>(app, "language").unwrap_or_else(|| "en".to_string()),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    storage::write_metadata_file(&args.save_path, &meta).map_err(|e| e.to_string())?;
+
+    if !meeting_id.is_empty() {
+        api::set_meeting_audio_path(app, &meeting_id, &args.save_path)?;
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -793,6 +2036,704 @@ async fn save_transcript(file_path: String, content: String) -> Result<(), Strin
     Ok(())
 }
 
+#[tauri::command]
+fn set_downmix_strategy(strategy: MonoDownmixStrategy) -> Result<(), String> {
+    log_info!("Setting mono downmix strategy to {:?}", strategy);
+    audio::audio_processing::set_current_downmix_strategy(strategy);
+    Ok(())
+}
+
+const TRANSCRIPT_SERVER_HEADERS_KEY: &str = "transcriptServerHeaders";
+// The multipart body sets these itself
+const RESERVED_HEADERS: &[&str] = &["content-type", "content-length"];
+// These can break routing to the local server, so only debug builds may set them
+const DEBUG_ONLY_HEADERS: &[&str] = &["cookie", "host"];
+
+fn build_header_map(headers: &HashMap<String, String>) -> Result<reqwest::header::HeaderMap, String> {
+    use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|e| format!("Invalid header name '{}': {}", name, e))?;
+        if RESERVED_HEADERS.contains(&header_name.as_str()) {
+            return Err(format!("Header '{}' is set by the transcription request and cannot be overridden", name));
+        }
+        if DEBUG_ONLY_HEADERS.contains(&header_name.as_str()) && !cfg!(debug_assertions) {
+            return Err(format!("Header '{}' is only allowed in debug builds", name));
+        }
+        let header_value = HeaderValue::from_str(value.trim())
+            .map_err(|e| format!("Invalid value for header '{}': {}", name, e))?;
+        map.insert(header_name, header_value);
+    }
+    Ok(map)
+}
+
+fn load_transcript_server_headers<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let headers = store::get_value::<HashMap<String, String>>(app, TRANSCRIPT_SERVER_HEADERS_KEY).unwrap_or_default();
+    let map = build_header_map(&headers)?;
+    if let Ok(mut guard) = TRANSCRIPT_SERVER_HEADERS.lock() {
+        *guard = map;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn set_transcript_server_headers<R: Runtime>(app: AppHandle<R>, headers: HashMap<String, String>) -> Result<(), String> {
+    let map = build_header_map(&headers)?;
+    store::set_value(&app, TRANSCRIPT_SERVER_HEADERS_KEY, &headers)?;
+    if let Ok(mut guard) = TRANSCRIPT_SERVER_HEADERS.lock() {
+        *guard = map;
+    }
+    log_info!("Configured {} transcription server headers", headers.len());
+    Ok(())
+}
+
+#[tauri::command]
+fn get_transcript_server_headers<R: Runtime>(app: AppHandle<R>) -> HashMap<String, String> {
+    store::get_value::<HashMap<String, String>>(&app, TRANSCRIPT_SERVER_HEADERS_KEY).unwrap_or_default()
+}
+
+#[tauri::command]
+fn clear_transcript_server_headers<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    store::delete_value(&app, TRANSCRIPT_SERVER_HEADERS_KEY)?;
+    if let Ok(mut guard) = TRANSCRIPT_SERVER_HEADERS.lock() {
+        guard.clear();
+    }
+    log_info!("Cleared transcription server headers");
+    Ok(())
+}
+
+const POST_PROCESSING_KEY: &str = "transcriptPostProcessing";
+
+const DEDUP_CONFIG_KEY: &str = "transcriptDedupConfig";
+
+/// Replays the stored raw segments of `meeting_id` through a fresh accumulator, emitting
+/// "transcript-update" for each sentence as if it had just been transcribed.
+#[tauri::command]
+async fn reprocess_transcript<R: Runtime>(app: AppHandle<R>, meeting_id: String) -> Result<(), String> {
+    // The replayed updates would interleave with the live ones
+    if recording_active() {
+        return Err("Cannot reprocess a transcript while recording".to_string());
+    }
+    let segments = transcript_store::load(&meeting_id).map_err(|e| format!("Failed to load raw segments: {}", e))?;
+    if segments.is_empty() {
+        return Err(format!("No raw segments stored for meeting {}", meeting_id));
+    }
+
+    let dedup_config = store::get_value::<transcript::DedupConfig>(&app, DEDUP_CONFIG_KEY).unwrap_or_default();
+    let transcription_config = get_transcription_config(app.clone());
+    transcript::processing::set_current_pipeline(
+        store::get_value::<transcript::PostProcessingPipeline>(&app, POST_PROCESSING_KEY),
+    );
+    let mut accumulator = TranscriptAccumulator::new(
+        transcript::TranscriptDeduplicator::new(dedup_config),
+        Duration::from_millis(transcription_config.sentence_timeout_ms),
+        None,
+    );
+
+    log_info!("Reprocessing {} raw segments for meeting {}", segments.len(), meeting_id);
+    let mut updates: Vec<TranscriptUpdate> = segments
+        .iter()
+        .filter_map(|segment| accumulator.add_segment(segment))
+        .collect();
+    updates.extend(accumulator.flush());
+    for update in updates {
+        app.emit("transcript-update", update)
+            .map_err(|e| format!("Failed to emit transcript update: {}", e))?;
+    }
+    Ok(())
+}
+
+// Takes effect from the next recording; the running accumulator keeps its deduplicator
+#[tauri::command]
+fn set_dedup_config<R: Runtime>(app: AppHandle<R>, window_size: usize, fuzzy_threshold: f32) -> Result<(), String> {
+    if !(1..=100).contains(&window_size) {
+        return Err(format!("Dedup window must be between 1 and 100 segments, got {}", window_size));
+    }
+    if !(0.0..=0.5).contains(&fuzzy_threshold) {
+        return Err(format!("Fuzzy threshold must be between 0.0 and 0.5, got {}", fuzzy_threshold));
+    }
+    store::set_value(&app, DEDUP_CONFIG_KEY, &transcript::DedupConfig { window_size, fuzzy_threshold })?;
+    log_info!("Transcript dedup set to a {} segment window, fuzzy threshold {}", window_size, fuzzy_threshold);
+    Ok(())
+}
+
+// An empty pipeline turns post-processing off
+#[tauri::command]
+fn set_post_processing_pipeline<R: Runtime>(app: AppHandle<R>, pipeline: transcript::PostProcessingPipeline) -> Result<(), String> {
+    transcript::processing::validate_pipeline(&pipeline).map_err(|e| format!("Invalid post-processing pipeline: {:#}", e))?;
+    if pipeline.steps.is_empty() {
+        store::delete_value(&app, POST_PROCESSING_KEY)?;
+        transcript::processing::set_current_pipeline(None);
+    } else {
+        store::set_value(&app, POST_PROCESSING_KEY, &pipeline)?;
+        log_info!("Transcript post-processing pipeline set with {} steps", pipeline.steps.len());
+        transcript::processing::set_current_pipeline(Some(pipeline));
+    }
+    Ok(())
+}
+
+const SILENCE_REMOVAL_KEY: &str = "silenceRemovalConfig";
+
+#[tauri::command]
+fn set_silence_removal_config<R: Runtime>(app: AppHandle<R>, config: SilenceRemovalConfig) -> Result<(), String> {
+    if config.threshold_db >= 0.0 {
+        return Err("Silence threshold must be below 0 dBFS".to_string());
+    }
+    store::set_value(&app, SILENCE_REMOVAL_KEY, &config)?;
+    log_info!("Silence removal config set to {:?}", config);
+    // Takes effect on the next chunk if a recording is running
+    audio::audio_processing::set_current_silence_removal(config);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_silence_removal_config<R: Runtime>(app: AppHandle<R>) -> SilenceRemovalConfig {
+    store::get_value::<SilenceRemovalConfig>(&app, SILENCE_REMOVAL_KEY).unwrap_or_default()
+}
+
+const VAD_CONFIG_KEY: &str = "vadConfig";
+
+#[derive(Debug, Clone, Serialize)]
+struct VadSilenceEvent {
+    chunk: usize,
+    duration_secs: f32,
+}
+
+#[tauri::command]
+fn set_vad_config<R: Runtime>(app: AppHandle<R>, config: VadConfig) -> Result<(), String> {
+    if config.threshold_db >= 0.0 {
+        return Err("VAD threshold must be below 0 dBFS".to_string());
+    }
+    store::set_value(&app, VAD_CONFIG_KEY, &config)?;
+    log_info!("VAD config set to {:?}", config);
+    // Takes effect on the next chunk if a recording is running
+    audio::audio_processing::set_vad_config(config);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_vad_config<R: Runtime>(app: AppHandle<R>) -> VadConfig {
+    store::get_value::<VadConfig>(&app, VAD_CONFIG_KEY).unwrap_or_default()
+}
+
+// How much of each recording is kept for saving; takes effect from the next recording
+#[tauri::command]
+fn set_audio_buffer_seconds<R: Runtime>(app: AppHandle<R>, seconds: u32) -> Result<(), String> {
+    if seconds == 0 {
+        return Err("Audio buffer length must be greater than zero".to_string());
+    }
+    store::set_value(&app, BUFFER_SECONDS_KEY, &seconds)?;
+    log_info!("Audio buffer length set to {} s", seconds);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_audio_buffer_seconds<R: Runtime>(app: AppHandle<R>) -> u32 {
+    store::get_value::<u32>(&app, BUFFER_SECONDS_KEY).unwrap_or(DEFAULT_CAPACITY_SECS)
+}
+
+const TRANSCRIPTION_LANGUAGE_KEY: &str = "transcriptionLanguage";
+
+fn load_transcription_language<R: Runtime>(app: &AppHandle<R>) {
+    let language = store::get_value::<String>(app, TRANSCRIPTION_LANGUAGE_KEY).filter(|code| !code.is_empty());
+    if let Ok(mut guard) = TRANSCRIPTION_LANGUAGE.lock() {
+        *guard = language;
+    }
+}
+
+// An empty code or "auto" goes back to Whisper's language detection
+#[tauri::command]
+fn set_transcription_language<R: Runtime>(app: AppHandle<R>, language_code: String) -> Result<(), String> {
+    let code = language_code.trim().to_lowercase();
+    let language = if code.is_empty() || code == "auto" {
+        store::delete_value(&app, TRANSCRIPTION_LANGUAGE_KEY)?;
+        None
+    } else {
+        if !transcript::languages::is_supported_language(&code) {
+            return Err(format!("Unsupported transcription language: {}", language_code));
+        }
+        store::set_value(&app, TRANSCRIPTION_LANGUAGE_KEY, &code)?;
+        Some(code)
+    };
+
+    log_info!("Transcription language set to {}", language.as_deref().unwrap_or("auto"));
+    if let Ok(mut guard) = TRANSCRIPTION_LANGUAGE.lock() {
+        *guard = language;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_supported_languages() -> Vec<transcript::LanguageInfo> {
+    transcript::languages::supported_languages()
+}
+
+#[tauri::command]
+fn get_realtime_audio_stats() -> RealtimeAudioStats {
+    let mut stats = AUDIO_STATS.lock().map(|stats| stats.clone()).unwrap_or_default();
+    stats.last_mic_sample_age_ms = audio::core::unix_time_ms()
+        .saturating_sub(audio::LAST_AUDIO_CAPTURE.load(Ordering::Relaxed));
+    stats
+}
+
+#[tauri::command]
+fn get_audio_sync_stats() -> SyncStats {
+    SYNC_STATS.lock().map(|stats| stats.clone()).unwrap_or_default()
+}
+
+#[tauri::command]
+fn get_adaptive_chunk_stats() -> Result<AdaptiveStats, String> {
+    CHUNK_SCHEDULER
+        .lock()
+        .map(|scheduler| scheduler.stats())
+        .map_err(|e| format!("Failed to read chunk scheduler: {}", e))
+}
+
+const PERMISSION_CHECK_TIMEOUT_SECS: u32 = 10;
+const MAX_LOOPBACK_TEST_SECS: u32 = 60;
+
+#[tauri::command]
+async fn run_audio_loopback_test<R: Runtime>(app: AppHandle<R>, duration_secs: u32) -> Result<audio::loopback::LoopbackTestResult, String> {
+    if recording_active() {
+        return Err("Cannot run an audio test while recording".to_string());
+    }
+    if duration_secs == 0 || duration_secs > MAX_LOOPBACK_TEST_SECS {
+        return Err(format!("Test duration must be between 1 and {} seconds", MAX_LOOPBACK_TEST_SECS));
+    }
+    audio::loopback::run_loopback_test(&app, duration_secs)
+        .await
+        .map_err(|e| format!("Audio loopback test failed: {}", e))
+}
+
+// Fails early with actionable guidance instead of recording silence
+#[cfg(target_os = "macos")]
+async fn ensure_microphone_permission() -> Result<(), String> {
+    use audio::core::MicrophonePermissionStatus;
+
+    let mut status = audio::core::get_microphone_permission_status();
+    if status == MicrophonePermissionStatus::NotDetermined {
+        status = audio::core::request_microphone_permission()
+            .await
+            .map_err(|e| format!("Failed to request microphone access: {}", e))?;
+    }
+    match status {
+        MicrophonePermissionStatus::Authorized => Ok(()),
+        MicrophonePermissionStatus::Restricted => {
+            Err("Microphone access is restricted on this Mac by a device management policy".to_string())
+        }
+        _ => Err("Microphone access was denied. Allow it in System Settings → Privacy & Security → Microphone, then restart the app".to_string()),
+    }
+}
+
+// Other platforms have no per-app microphone prompt to check, so they always report Authorized
+#[tauri::command]
+async fn check_microphone_permission() -> Result<audio::core::MicrophonePermissionStatus, String> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(audio::core::get_microphone_permission_status())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        Ok(audio::core::MicrophonePermissionStatus::Authorized)
+    }
+}
+
+#[tauri::command]
+async fn check_audio_permissions<R: Runtime>(app: AppHandle<R>) -> Result<audio::core::PermissionStatus, String> {
+    audio::core::trigger_audio_permission_with_progress(app, PERMISSION_CHECK_TIMEOUT_SECS)
+        .await
+        .map_err(|e| format!("Failed to check audio permissions: {}", e))
+}
+
+#[tauri::command]
+fn set_highpass_filter(enabled: bool, cutoff_hz: f32) -> Result<(), String> {
+    if !(20.0..=1000.0).contains(&cutoff_hz) {
+        return Err(format!("Highpass cutoff must be between 20 and 1000 Hz, got {}", cutoff_hz));
+    }
+    log_info!("Highpass filter {} at {} Hz", if enabled { "enabled" } else { "disabled" }, cutoff_hz);
+    audio::audio_processing::set_highpass(enabled, cutoff_hz);
+    Ok(())
+}
+
+// Gains of 0 dB on both shelves turn the EQ off
+#[tauri::command]
+fn set_eq_shelf_filter(
+    high_shelf_freq: f32,
+    high_shelf_gain_db: f32,
+    low_shelf_freq: f32,
+    low_shelf_gain_db: f32,
+) -> Result<(), String> {
+    for gain in [high_shelf_gain_db, low_shelf_gain_db] {
+        if !(-24.0..=24.0).contains(&gain) {
+            return Err(format!("Shelf gain must be between -24 and 24 dB, got {}", gain));
+        }
+    }
+    for freq in [high_shelf_freq, low_shelf_freq] {
+        if !(20.0..=20_000.0).contains(&freq) {
+            return Err(format!("Shelf frequency must be between 20 and 20000 Hz, got {}", freq));
+        }
+    }
+    log_info!(
+        "EQ shelves set: high {} dB at {} Hz, low {} dB at {} Hz",
+        high_shelf_gain_db, high_shelf_freq, low_shelf_gain_db, low_shelf_freq
+    );
+    audio::audio_processing::set_eq_shelf(audio::audio_processing::EqShelfConfig {
+        high_shelf_freq,
+        high_shelf_gain_db,
+        low_shelf_freq,
+        low_shelf_gain_db,
+    });
+    Ok(())
+}
+
+const PREFERRED_AUDIO_HOST_KEY: &str = "preferredAudioHost";
+
+fn load_preferred_audio_host<R: Runtime>(app: &AppHandle<R>) {
+    let host = store::get_value::<String>(app, PREFERRED_AUDIO_HOST_KEY)
+        .and_then(|id| audio::core::parse_host_id(&id));
+    audio::core::set_preferred_host(host);
+}
+
+#[tauri::command]
+fn check_audio_exclusive_mode_conflict<R: Runtime>(app: AppHandle<R>) -> audio::exclusive_mode::ExclusiveModeStatus {
+    audio::exclusive_mode::check_and_notify(&app)
+}
+
+#[tauri::command]
+fn list_audio_hosts() -> Vec<audio::AudioHostInfo> {
+    audio::core::list_audio_hosts()
+}
+
+// An empty id goes back to the platform default host
+#[tauri::command]
+fn set_preferred_audio_host<R: Runtime>(app: AppHandle<R>, host_id: String) -> Result<(), String> {
+    if host_id.trim().is_empty() {
+        store::delete_value(&app, PREFERRED_AUDIO_HOST_KEY)?;
+        audio::core::set_preferred_host(None);
+        log_info!("Preferred audio host cleared");
+        return Ok(());
+    }
+    let host = audio::core::parse_host_id(&host_id)
+        .ok_or_else(|| format!("Audio host {} is not available on this platform", host_id))?;
+    store::set_value(&app, PREFERRED_AUDIO_HOST_KEY, &host.name().to_lowercase())?;
+    audio::core::set_preferred_host(Some(host));
+    log_info!("Preferred audio host set to {}", host.name());
+    Ok(())
+}
+
+const SPECTRUM_WINDOW_MS: u32 = 20;
+const SPECTRUM_CACHE_TTL: Duration = Duration::from_millis(16);
+const MAX_SPECTRUM_BANDS: usize = 256;
+
+fn buffer_tail(buffer: &Option<SampleBuffer>, len: usize) -> Vec<f32> {
+    buffer
+        .as_ref()
+        .and_then(|buffer| buffer.lock().ok().map(|samples| samples.tail(len)))
+        .unwrap_or_default()
+}
+
+fn spectrum_snapshot(state: &RecordingState, bands: usize) -> audio::spectrum::SpectrumSnapshot {
+    let bands = bands.clamp(1, MAX_SPECTRUM_BANDS);
+    if let Ok(cache) = SPECTRUM_CACHE.lock() {
+        if let Some((computed_at, snapshot)) = cache.as_ref() {
+            if computed_at.elapsed() < SPECTRUM_CACHE_TTL && snapshot.frequency_centers_hz.len() == bands {
+                return snapshot.clone();
+            }
+        }
+    }
+
+    let sample_rate = match RECORDING_SAMPLE_RATE.load(Ordering::SeqCst) {
+        0 => WHISPER_SAMPLE_RATE,
+        rate => rate,
+    };
+    let window = (sample_rate * SPECTRUM_WINDOW_MS / 1000) as usize;
+    // A start or stop in progress reads as silence for one frame
+    let (mic, system) = state
+        .try_lock()
+        .map(|recording| (buffer_tail(&recording.mic_buffer, window), buffer_tail(&recording.system_buffer, window)))
+        .unwrap_or_default();
+    let snapshot = audio::spectrum::SpectrumSnapshot {
+        mic_bands: audio::spectrum::band_levels_db(&mic, sample_rate, bands),
+        system_bands: audio::spectrum::band_levels_db(&system, sample_rate, bands),
+        frequency_centers_hz: audio::spectrum::band_centers(bands, sample_rate),
+    };
+    if let Ok(mut cache) = SPECTRUM_CACHE.lock() {
+        *cache = Some((std::time::Instant::now(), snapshot.clone()));
+    }
+    snapshot
+}
+
+#[tauri::command]
+fn get_audio_spectrum_snapshot(state: State<'_, RecordingState>, bands: usize) -> audio::spectrum::SpectrumSnapshot {
+    spectrum_snapshot(&state, bands)
+}
+
+// Emits "audio-spectrum" with 32 bands while recording; an interval of 0 stops streaming
+#[tauri::command]
+fn start_spectrum_streaming<R: Runtime>(app: AppHandle<R>, interval_ms: u32) -> Result<(), String> {
+    const STREAM_BANDS: usize = 32;
+    let mut task = SPECTRUM_STREAM_TASK.lock().map_err(|_| "Spectrum streaming state poisoned".to_string())?;
+    if let Some(handle) = task.take() {
+        handle.abort();
+    }
+    if interval_ms == 0 {
+        return Ok(());
+    }
+
+    let state = app.state::<RecordingState>().inner().clone();
+    *task = Some(tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(interval_ms.max(16) as u64));
+        loop {
+            interval.tick().await;
+            if !recording_active() {
+                continue;
+            }
+            if let Err(e) = app.emit("audio-spectrum", spectrum_snapshot(&state, STREAM_BANDS)) {
+                log_error!("Failed to emit audio spectrum: {}", e);
+            }
+        }
+    }));
+    log_info!("Streaming audio spectrum every {} ms", interval_ms.max(16));
+    Ok(())
+}
+
+const MAX_RECORDING_VOLUME: f32 = 2.0;
+
+// Applies to the running mix immediately; not persisted
+#[tauri::command]
+fn set_recording_volumes(mic_volume: f32, system_volume: f32) -> Result<(), String> {
+    for (name, volume) in [("Mic", mic_volume), ("System", system_volume)] {
+        if !(0.0..=MAX_RECORDING_VOLUME).contains(&volume) {
+            return Err(format!("{} volume must be between 0.0 and {}", name, MAX_RECORDING_VOLUME));
+        }
+    }
+    audio::sync::set_realtime_mic_volume(mic_volume);
+    audio::sync::set_realtime_system_volume(system_volume);
+    log_info!("Recording volumes set to mic {:.2}, system {:.2}", mic_volume, system_volume);
+    Ok(())
+}
+
+// Read when a recording starts, so changes apply from the next one
+#[tauri::command]
+async fn save_mixing_config<R: Runtime>(app: AppHandle<R>, config: MixingConfig) -> Result<(), String> {
+    config.validate()?;
+    store::set_value(&app, MIXING_CONFIG_KEY, &config)?;
+    log_info!("Saved mixing config: {:?}", config);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_mixing_config<R: Runtime>(app: AppHandle<R>) -> MixingConfig {
+    store::get_value(&app, MIXING_CONFIG_KEY).unwrap_or_default()
+}
+
+// Read when a recording starts, so changes apply from the next one
+#[tauri::command]
+async fn save_transcription_config<R: Runtime>(app: AppHandle<R>, config: TranscriptionConfig) -> Result<(), String> {
+    config.validate()?;
+    store::set_value(&app, TRANSCRIPTION_CONFIG_KEY, &config)?;
+    log_info!("Saved transcription config: {:?}", config);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_transcription_config<R: Runtime>(app: AppHandle<R>) -> TranscriptionConfig {
+    store::get_value(&app, TRANSCRIPTION_CONFIG_KEY).unwrap_or_else(|| {
+        let mixing = store::get_value::<MixingConfig>(&app, MIXING_CONFIG_KEY).unwrap_or_default();
+        TranscriptionConfig::with_mixing(&mixing)
+    })
+}
+
+const MAX_MEASURE_DURATION_MS: u64 = 10_000;
+
+/// Captures the default microphone for `duration_ms` and returns its RMS level (0.0-1.0),
+/// so the UI can show a meter before recording starts.
+#[tauri::command]
+async fn measure_input_rms(duration_ms: u64) -> Result<f32, String> {
+    if recording_active() {
+        return Err("Cannot measure input level while recording".to_string());
+    }
+    if duration_ms == 0 || duration_ms > MAX_MEASURE_DURATION_MS {
+        return Err(format!("Duration must be between 1 and {} ms", MAX_MEASURE_DURATION_MS));
+    }
+
+    let device = Arc::new(default_input_device().map_err(String::from)?);
+    let is_running = Arc::new(AtomicBool::new(true));
+    let stream = AudioStream::from_device(device, is_running.clone(), AudioStreamConfig::default())
+        .await
+        .map_err(String::from)?;
+    let mut receiver = stream.subscribe().await;
+
+    let mut samples = Vec::new();
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(duration_ms);
+    loop {
+        match tokio::time::timeout_at(deadline, receiver.recv()).await {
+            Ok(Ok(chunk)) => samples.extend(chunk),
+            Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(_))) => continue,
+            _ => break,
+        }
+    }
+
+    is_running.store(false, Ordering::SeqCst);
+    if let Err(e) = stream.stop().await {
+        log_error!("Failed to stop level measurement stream: {}", e);
+    }
+    if samples.is_empty() {
+        return Err("No audio received from the microphone".to_string());
+    }
+    Ok(rms_and_peak(&samples).0)
+}
+
+#[tauri::command]
+fn load_noise_suppression_model(
+    model_path: String,
+    model_type: audio::noise_suppression::NoiseSuppressorType,
+) -> Result<(), String> {
+    audio::noise_suppression::load_model(&model_path, model_type)
+        .map_err(|e| format!("Failed to load noise suppression model: {}", e))
+}
+
+#[tauri::command]
+fn unload_noise_suppression_model() -> Result<(), String> {
+    audio::noise_suppression::unload_model()
+        .map_err(|e| format!("Failed to unload noise suppression model: {}", e))
+}
+
+#[tauri::command]
+fn is_noise_suppression_active() -> bool {
+    audio::noise_suppression::is_active()
+}
+
+#[tauri::command]
+fn acknowledge_audio_warning(warning_type: String) -> Result<(), String> {
+    audio::level_monitor::acknowledge(&warning_type)
+}
+
+#[tauri::command]
+fn get_recording_volumes() -> (f32, f32) {
+    audio::sync::realtime_volumes()
+}
+
+#[tauri::command]
+fn mute_mic() -> Result<(), String> {
+    audio::sync::set_realtime_mic_volume(0.0);
+    log_info!("Microphone muted");
+    Ok(())
+}
+
+#[tauri::command]
+fn mute_system() -> Result<(), String> {
+    audio::sync::set_realtime_system_volume(0.0);
+    log_info!("System audio muted");
+    Ok(())
+}
+
+const DEVICE_VOLUME_OFFSETS_KEY: &str = "deviceVolumeOffsets";
+const MAX_DEVICE_VOLUME_OFFSET_DB: f32 = 40.0;
+
+fn load_device_volume_offsets<R: Runtime>(app: &AppHandle<R>) {
+    let offsets = store::get_value::<HashMap<String, f32>>(app, DEVICE_VOLUME_OFFSETS_KEY).unwrap_or_default();
+    if let Ok(mut guard) = DEVICE_VOLUME_OFFSETS.lock() {
+        *guard = offsets;
+    }
+}
+
+fn apply_device_gain(samples: &mut [f32], device_name: &str) {
+    let offset_db = DEVICE_VOLUME_OFFSETS
+        .lock()
+        .ok()
+        .and_then(|offsets| offsets.get(device_name).copied())
+        .unwrap_or(0.0);
+    if offset_db == 0.0 {
+        return;
+    }
+
+    let gain = 10f32.powf(offset_db / 20.0);
+    for sample in samples.iter_mut() {
+        *sample *= gain;
+    }
+}
+
+#[tauri::command]
+fn set_device_volume_offset<R: Runtime>(app: AppHandle<R>, device_name: String, offset_db: f32) -> Result<(), String> {
+    if !(-MAX_DEVICE_VOLUME_OFFSET_DB..=MAX_DEVICE_VOLUME_OFFSET_DB).contains(&offset_db) {
+        return Err(format!("Volume offset must be between -40 and 40 dB, got {}", offset_db));
+    }
+
+    let mut offsets = store::get_value::<HashMap<String, f32>>(&app, DEVICE_VOLUME_OFFSETS_KEY).unwrap_or_default();
+    offsets.insert(device_name.clone(), offset_db);
+    store::set_value(&app, DEVICE_VOLUME_OFFSETS_KEY, &offsets)?;
+
+    if let Ok(mut guard) = DEVICE_VOLUME_OFFSETS.lock() {
+        *guard = offsets;
+    }
+    log_info!("Volume offset for {} set to {} dB", device_name, offset_db);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_device_volume_offset<R: Runtime>(app: AppHandle<R>, device_name: String) -> f32 {
+    store::get_value::<HashMap<String, f32>>(&app, DEVICE_VOLUME_OFFSETS_KEY)
+        .and_then(|offsets| offsets.get(&device_name).copied())
+        .unwrap_or(0.0)
+}
+
+#[tauri::command]
+fn reset_all_device_offsets<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    store::delete_value(&app, DEVICE_VOLUME_OFFSETS_KEY)?;
+    if let Ok(mut guard) = DEVICE_VOLUME_OFFSETS.lock() {
+        guard.clear();
+    }
+    log_info!("Reset all device volume offsets");
+    Ok(())
+}
+
+#[tauri::command]
+async fn load_local_whisper_model(model_path: String) -> Result<(), String> {
+    #[cfg(feature = "local-whisper")]
+    {
+        // Large models take seconds to load; keep that off the async workers
+        tokio::task::spawn_blocking(move || local_whisper::load_model(&model_path))
+            .await
+            .map_err(|e| format!("Model loading task failed: {}", e))?
+    }
+
+    #[cfg(not(feature = "local-whisper"))]
+    {
+        let _ = model_path;
+        Err("Local Whisper inference is not enabled in this build".to_string())
+    }
+}
+
+const AUDIO_STREAM_CONFIG_KEY: &str = "audioStreamConfig";
+const MAX_PRE_FILL_MS: u32 = 1000;
+
+// Applied by the next start_recording; running streams keep their channel
+#[tauri::command]
+fn set_audio_stream_config<R: Runtime>(app: AppHandle<R>, config: AudioStreamConfig) -> Result<(), String> {
+    if !(100..=10_000).contains(&config.broadcast_capacity) {
+        return Err(format!("Broadcast capacity must be between 100 and 10000, got {}", config.broadcast_capacity));
+    }
+    if config.sample_buffer_pre_fill_ms > MAX_PRE_FILL_MS {
+        return Err(format!("Pre-fill must be at most {} ms, got {}", MAX_PRE_FILL_MS, config.sample_buffer_pre_fill_ms));
+    }
+
+    store::set_value(&app, AUDIO_STREAM_CONFIG_KEY, &config)?;
+    log_info!(
+        "Audio stream config set to capacity {} with {} ms pre-fill",
+        config.broadcast_capacity, config.sample_buffer_pre_fill_ms
+    );
+    Ok(())
+}
+
+#[tauri::command]
+fn get_audio_stream_config<R: Runtime>(app: AppHandle<R>) -> AudioStreamConfig {
+    store::get_value::<AudioStreamConfig>(&app, AUDIO_STREAM_CONFIG_KEY).unwrap_or_default()
+}
+
 // Helper function to convert stereo to mono
 fn stereo_to_mono(stereo: &[i16]) -> Vec<i16> {
     let mut mono = Vec::with_capacity(stereo.len() / 2);
@@ -809,9 +2750,43 @@ pub fn run() {
     log::set_max_level(log::LevelFilter::Info);
     
     tauri::Builder::default()
-        .setup(|_app| {
+        .plugin(tauri_plugin_store::Builder::new().build())
+        .manage(RecordingState::default())
+        .setup(|app| {
             log::info!("Application setup complete");
 
+            http_client::init(app.handle());
+            telemetry::init(app.handle());
+            updater::init(app.handle());
+
+            if let Err(e) = analytics::init(app.handle()) {
+                log::error!("Failed to initialize analytics: {}", e);
+            }
+
+            if let Err(e) = hotkey::init(app.handle()) {
+                log::error!("Failed to set up recording hotkey: {}", e);
+            }
+
+            groq::init(app.handle());
+            failed_chunks::purge_expired();
+            transcription_errors::load(app.handle());
+            if let Err(e) = transcript_store::init(app.handle()) {
+                log::error!("Failed to open transcript segment store: {}", e);
+            }
+            ollama::init(app.handle());
+            meeting_templates::start_scheduler(app.handle());
+
+            // 0 is treated as "no policy" so a bad stored value can't empty the library at launch
+            if let Some(retention_days) = store::get_value::<u32>(app.handle(), "retentionDays").filter(|days| *days > 0) {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    match api::api_apply_retention_policy(app_handle, retention_days).await {
+                        Ok(result) => log::info!("Startup retention check: {:?}", result),
+                        Err(e) => log::error!("Startup retention check failed: {}", e),
+                    }
+                });
+            }
+
             // Trigger microphone permission request on startup
             if let Err(e) = audio::core::trigger_audio_permission() {
                 log::error!("Failed to trigger audio permission: {}", e);
@@ -823,29 +2798,168 @@ pub fn run() {
             start_recording,
             stop_recording,
             is_recording,
+            pause_recording,
+            resume_recording,
+            is_paused,
+            get_audio_devices,
+            set_recording_devices,
+            get_recording_devices,
+            list_active_sessions,
+            check_audio_permissions,
+            check_microphone_permission,
+            run_audio_loopback_test,
             read_audio_file,
             save_transcript,
+            set_downmix_strategy,
+            audio::wav_repair::repair_wav_file,
+            audio::decoder::is_ffmpeg_available,
+            set_highpass_filter,
+            set_eq_shelf_filter,
+            get_adaptive_chunk_stats,
+            get_realtime_audio_stats,
+            get_audio_sync_stats,
+            set_post_processing_pipeline,
+            set_dedup_config,
+            transcription_failover::set_backup_transcription_config,
+            transcription_failover::get_backup_transcription_config,
+            transcription_failover::test_backup_transcription_server,
+            set_silence_removal_config,
+            get_silence_removal_config,
+            set_vad_config,
+            diarization::rename_speaker,
+            diarization::get_speaker_names,
+            get_vad_config,
+            set_audio_buffer_seconds,
+            get_audio_buffer_seconds,
+            server_metrics::get_transcription_server_metrics,
+            server_metrics::enable_metrics_polling,
+            meeting_templates::api_create_meeting_template,
+            meeting_templates::api_list_meeting_templates,
+            meeting_templates::api_delete_meeting_template,
+            updater::check_for_updates,
+            updater::get_cached_update_info,
+            ollama::get_ollama_base_url,
+            ollama::set_ollama_base_url,
+            ollama::test_ollama_connection,
+            set_transcript_server_headers,
+            get_transcript_server_headers,
+            clear_transcript_server_headers,
+            set_transcription_language,
+            get_supported_languages,
+            set_device_volume_offset,
+            get_device_volume_offset,
+            set_recording_volumes,
+            set_transcription_backend,
+            openai_whisper::set_openai_api_key,
+            save_mixing_config,
+            get_mixing_config,
+            save_transcription_config,
+            get_transcription_config,
+            measure_input_rms,
+            get_recording_volumes,
+            mute_mic,
+            mute_system,
+            acknowledge_audio_warning,
+            load_noise_suppression_model,
+            unload_noise_suppression_model,
+            is_noise_suppression_active,
+            get_audio_spectrum_snapshot,
+            start_spectrum_streaming,
+            list_audio_hosts,
+            set_preferred_audio_host,
+            check_audio_exclusive_mode_conflict,
+            reset_all_device_offsets,
+            set_audio_stream_config,
+            load_local_whisper_model,
+            get_audio_stream_config,
+            api::api_save_transcript,
+            api::api_delete_meeting,
+            api::api_get_meetings,
+            api::api_archive_meeting,
+            api::api_unarchive_meeting,
+            api::api_search_transcripts,
+            api::api_save_meeting_title,
+            api::api_process_transcript,
+            api::api_process_transcript_local,
+            api::is_local_only_mode,
+            api::api_link_meeting_audio,
+            api::api_split_meeting,
+            api::api_denoise_recording,
+            api::api_trim_meeting_audio,
+            api::api_recompress_meeting_audio,
+            api::api_apply_retention_policy,
+            api::api_bulk_delete_meetings,
+            api::api_transcribe_audio_file,
+            api::api_compute_meeting_embedding,
+            api::api_compute_all_embeddings,
+            api::api_semantic_search,
+            api::api_preview_prompt_template,
+            api::api_save_transcript_version,
+            api::api_diff_transcript_versions,
+            api::api_get_transcript_paragraphs,
+            api::api_get_transcript_with_absolute_timestamps,
+            api::api_get_aggregated_transcript,
+            api::api_import_teams_transcript,
+            api::api_import_google_meet_transcript,
+            api::api_get_audio_stats_range,
+            api::api_get_energy_envelope,
+            api::api_export_all_user_data,
+            api::api_retranscribe_range,
+            api::api_generate_structured_summary,
+            api::api_get_structured_summary,
+            failed_chunks::list_failed_chunks,
+            failed_chunks::retry_failed_chunk,
+            transcription_errors::get_transcription_error_history,
+            transcription_errors::clear_transcription_error_history,
+            transcript_store::get_raw_segments,
+            transcript_store::delete_raw_segments,
+            reprocess_transcript,
+            api::api_check_transcript_coverage,
+            api::api_generate_email_draft,
+            api::export_to_notion,
+            api::api_export_meetings_csv,
+            api::api_export_meeting_csv,
+            api::api_export_podcast_episode,
+            api::set_podcast_config,
+            api::api_save_notion_config,
+            api::api_save_jira_config,
+            api::api_save_slack_config,
+            api::api_test_slack_webhook,
+            api::api_share_to_slack,
+            api::api_create_jira_issues,
+            templates::validate_prompt_template,
+            templates::save_prompt_template,
+            api::api_import_meeting_from_metadata,
+            api::api_auto_title_meeting,
+            api::api_auto_improve_transcript,
+            api::api_track_topics,
+            api::api_get_analytics_dashboard,
+            api::api_import_calendar_events,
+            api::api_link_meeting_to_calendar_event,
+            api::api_get_unrecorded_meetings,
+            api::api_sort_transcript_by_timestamp,
+            plugin::apply_transcript_plugin,
+            rest_api::start_rest_api_server,
+            rest_api::stop_rest_api_server,
+            rest_api::is_rest_api_running,
+            groq::list_groq_models,
+            groq::set_groq_model,
+            http_client::set_http_proxy,
+            http_client::set_http_user_agent,
+            http_client::get_http_user_agent,
+            telemetry::configure_otel_endpoint,
+            analytics::export_analytics_events,
+            analytics::clear_local_analytics_cache,
+            analytics::set_analytics_batch_config,
+            analytics::flush_analytics_batch,
+            analytics::pause_analytics,
+            analytics::resume_analytics,
+            analytics::is_analytics_paused,
+            analytics::get_analytics_opt_out,
+            analytics::set_analytics_opt_out,
+            hotkey::register_recording_hotkey,
+            hotkey::unregister_recording_hotkey,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
-
-// Helper function to resample audio
-fn resample_audio(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
-    if from_rate == to_rate {
-        return samples.to_vec();
-    }
-    
-    let ratio = to_rate as f32 / from_rate as f32;
-    let new_len = (samples.len() as f32 * ratio) as usize;
-    let mut resampled = Vec::with_capacity(new_len);
-    
-    for i in 0..new_len {
-        let src_idx = (i as f32 / ratio) as usize;
-        if src_idx < samples.len() {
-            resampled.push(samples[src_idx]);
-        }
-    }
-    
-    resampled
-}
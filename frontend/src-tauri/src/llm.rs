@@ -0,0 +1,151 @@
+// Minimal single-prompt completions against the LLM providers the backend supports
+use log::{debug as log_debug, info as log_info};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::api::fetch_model_config;
+use crate::{http_client, ollama};
+
+// Prefer the model the user picked in settings when it belongs to the requested provider
+async fn configured_model(provider: &str) -> Option<String> {
+    let config = fetch_model_config().await.ok()?;
+    (config.provider == provider && !config.model.is_empty()).then_some(config.model)
+}
+
+pub fn default_model(provider: &str) -> Option<&'static str> {
+    match provider {
+        "ollama" => Some("llama3.2"),
+        "groq" => Some("llama-3.3-70b-versatile"),
+        "openai" => Some("gpt-4o-mini"),
+        "claude" => Some("claude-3-5-haiku-latest"),
+        _ => None,
+    }
+}
+
+fn api_key(env_var: &str) -> Result<String, String> {
+    std::env::var(env_var)
+        .ok()
+        .filter(|key| !key.trim().is_empty())
+        .ok_or_else(|| format!("Missing {}", env_var))
+}
+
+/// Sends a single prompt to `provider` and returns the raw completion text.
+pub async fn generate(provider: &str, prompt: &str) -> Result<String, String> {
+    let default = default_model(provider)
+        .ok_or_else(|| format!("Unsupported model provider: {}", provider))?;
+    let model = configured_model(provider)
+        .await
+        .unwrap_or_else(|| default.to_string());
+    log_info!("Generating completion with {} ({})", provider, model);
+
+    let client = http_client::get_client();
+    let response = match provider {
+        "ollama" => client
+            .post(format!("{}/api/generate", ollama::get_ollama_base_url()))
+            .json(&json!({ "model": model, "prompt": prompt, "stream": false })),
+        "groq" | "openai" => {
+            let (url, key) = if provider == "groq" {
+                ("https://api.groq.com/openai/v1/chat/completions", api_key("GROQ_API_KEY")?)
+            } else {
+                ("https://api.openai.com/v1/chat/completions", api_key("OPENAI_API_KEY")?)
+            };
+            client.post(url).bearer_auth(key).json(&json!({
+                "model": model,
+                "messages": [{ "role": "user", "content": prompt }],
+                "temperature": 0,
+            }))
+        }
+        _ => client
+            .post("https://api.anthropic.com/v1/messages")
+            .header("x-api-key", api_key("ANTHROPIC_API_KEY")?)
+            .header("anthropic-version", "2023-06-01")
+            .json(&json!({
+                "model": model,
+                "max_tokens": 4096,
+                "messages": [{ "role": "user", "content": prompt }],
+            })),
+    }
+    .send()
+    .await
+    .map_err(|e| format!("{} request failed: {}", provider, e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("{} API error {}: {}", provider, status, body));
+    }
+
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse {} response: {}", provider, e))?;
+    log_debug!("{} response: {}", provider, body);
+
+    let text = match provider {
+        "ollama" => body.pointer("/response"),
+        "groq" | "openai" => body.pointer("/choices/0/message/content"),
+        _ => body.pointer("/content/0/text"),
+    }
+    .and_then(|value| value.as_str())
+    .ok_or_else(|| format!("Unexpected {} response format", provider))?;
+
+    Ok(text.trim().to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EmbeddingModel {
+    Ollama { model_name: String },
+    OpenAI { model: String },
+}
+
+// OpenAI embedding models accept ~8k tokens; stay well under it
+const MAX_EMBEDDING_INPUT_CHARS: usize = 24_000;
+
+/// Embeds `text` with the given model and returns the raw vector.
+pub async fn embed(model: &EmbeddingModel, text: &str) -> Result<Vec<f32>, String> {
+    let input: String = text.chars().take(MAX_EMBEDDING_INPUT_CHARS).collect();
+    let client = http_client::get_client();
+
+    let (provider, request, pointer) = match model {
+        EmbeddingModel::Ollama { model_name } => (
+            "ollama",
+            client
+                .post(format!("{}/api/embeddings", ollama::get_ollama_base_url()))
+                .json(&json!({ "model": model_name, "prompt": input })),
+            "/embedding",
+        ),
+        EmbeddingModel::OpenAI { model } => (
+            "openai",
+            client
+                .post("https://api.openai.com/v1/embeddings")
+                .bearer_auth(api_key("OPENAI_API_KEY")?)
+                .json(&json!({ "model": model, "input": input })),
+            "/data/0/embedding",
+        ),
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("{} embedding request failed: {}", provider, e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("{} API error {}: {}", provider, status, body));
+    }
+
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse {} embedding response: {}", provider, e))?;
+    let embedding = body
+        .pointer(pointer)
+        .and_then(|value| value.as_array())
+        .ok_or_else(|| format!("Unexpected {} embedding response format", provider))?;
+
+    Ok(embedding
+        .iter()
+        .filter_map(|value| value.as_f64())
+        .map(|value| value as f32)
+        .collect())
+}
@@ -0,0 +1,90 @@
+// In-process transcription with whisper.cpp, replacing the HTTP transcription server
+use anyhow::{anyhow, Result};
+use log::{debug as log_debug, info as log_info};
+use once_cell::sync::Lazy;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Runtime};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+use crate::{store, TranscriptSegment};
+
+pub struct LocalWhisperModel {
+    ctx: WhisperContext,
+}
+
+static LOCAL_WHISPER: Lazy<Mutex<Option<LocalWhisperModel>>> = Lazy::new(|| Mutex::new(None));
+// Mirrored from the "useLocalWhisper" and "language" store keys when a recording starts
+static USE_LOCAL_WHISPER: AtomicBool = AtomicBool::new(false);
+static LANGUAGE: Lazy<Mutex<String>> = Lazy::new(|| Mutex::new("en".to_string()));
+
+impl LocalWhisperModel {
+    pub fn load(model_path: &str) -> Result<Self> {
+        let ctx = WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
+            .map_err(|e| anyhow!("Failed to load Whisper model {}: {}", model_path, e))?;
+        Ok(Self { ctx })
+    }
+}
+
+/// Runs whisper.cpp over 16 kHz mono samples; segment times are in seconds from the chunk start.
+pub fn transcribe_local(model: &LocalWhisperModel, samples: &[f32], language: &str) -> Result<Vec<TranscriptSegment>> {
+    let mut state = model.ctx.create_state()?;
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_language(Some(language));
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_special(false);
+    params.set_print_timestamps(false);
+
+    state.full(params, samples)?;
+
+    let mut segments = Vec::new();
+    for i in 0..state.full_n_segments()? {
+        // whisper.cpp reports timestamps in centiseconds
+        segments.push(TranscriptSegment {
+            text: state.full_get_segment_text(i)?,
+            t0: state.full_get_segment_t0(i)? as f32 / 100.0,
+            t1: state.full_get_segment_t1(i)? as f32 / 100.0,
+            speaker_id: None,
+        });
+    }
+    log_debug!("Local Whisper produced {} segments", segments.len());
+    Ok(segments)
+}
+
+pub fn load_model(model_path: &str) -> Result<(), String> {
+    if !Path::new(model_path).is_file() {
+        return Err(format!("Whisper model not found: {}", model_path));
+    }
+    let model = LocalWhisperModel::load(model_path).map_err(|e| e.to_string())?;
+    *LOCAL_WHISPER.lock().map_err(|_| "Local Whisper state poisoned".to_string())? = Some(model);
+    log_info!("Loaded local Whisper model from {}", model_path);
+    Ok(())
+}
+
+pub fn configure<R: Runtime>(app: &AppHandle<R>) {
+    USE_LOCAL_WHISPER.store(store::get_value::<bool>(app, "useLocalWhisper").unwrap_or(false), Ordering::SeqCst);
+    if let Ok(mut language) = LANGUAGE.lock() {
+        *language = store::get_value::<String>(app, "transcriptionLanguage")
+            .or_else(|| store::get_value::<String>(app, "language"))
+            .unwrap_or_else(|| "en".to_string());
+    }
+}
+
+pub fn is_enabled() -> bool {
+    USE_LOCAL_WHISPER.load(Ordering::SeqCst)
+}
+
+pub async fn transcribe_chunk(samples: Vec<f32>) -> Result<Vec<TranscriptSegment>, String> {
+    let language = LANGUAGE.lock().map(|l| l.clone()).unwrap_or_else(|_| "en".to_string());
+    tokio::task::spawn_blocking(move || {
+        let guard = LOCAL_WHISPER.lock().map_err(|_| "Local Whisper state poisoned".to_string())?;
+        let model = guard
+            .as_ref()
+            .ok_or_else(|| "Local Whisper is enabled but no model is loaded".to_string())?;
+        transcribe_local(model, &samples, &language).map_err(|e| format!("Local transcription failed: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Local transcription task failed: {}", e))?
+}
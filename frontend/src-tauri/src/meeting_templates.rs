@@ -0,0 +1,156 @@
+// Recurring meeting templates and the scheduler that auto-starts their recordings
+use chrono::{DateTime, Datelike, Local};
+use cron::Schedule;
+use log::{error as log_error, info as log_info};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::recording_state::RecordingState;
+use crate::{recording_active, start_recording_session, store};
+
+const TEMPLATES_KEY: &str = "meetingTemplates";
+const SCHEDULER_TICK: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringMeetingTemplate {
+    #[serde(default)]
+    pub id: String,
+    pub title_pattern: String,
+    pub schedule_cron: String,
+    #[serde(default)]
+    pub default_tags: Vec<String>,
+    pub summary_prompt_id: Option<String>,
+    #[serde(default)]
+    pub auto_start_recording: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TemplateRecordingStarted {
+    template_id: String,
+    title: String,
+    tags: Vec<String>,
+    summary_prompt_id: Option<String>,
+    session_id: String,
+}
+
+impl RecurringMeetingTemplate {
+    pub fn schedule(&self) -> Result<Schedule, String> {
+        parse_schedule(&self.schedule_cron)
+    }
+
+    /// Expands `{{date}}` and `{{week_number}}` in the title pattern for `at`.
+    pub fn render_title(&self, at: DateTime<Local>) -> String {
+        self.title_pattern
+            .replace("{{date}}", &at.format("%Y-%m-%d").to_string())
+            .replace("{{week_number}}", &at.iso_week().week().to_string())
+    }
+}
+
+// The cron crate wants a seconds field; accept the usual five-field form as well
+fn parse_schedule(expression: &str) -> Result<Schedule, String> {
+    let expression = expression.trim();
+    let expression = if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    };
+    Schedule::from_str(&expression).map_err(|e| format!("Invalid cron schedule '{}': {}", expression, e))
+}
+
+fn load_templates<R: Runtime>(app: &AppHandle<R>) -> Vec<RecurringMeetingTemplate> {
+    store::get_value::<Vec<RecurringMeetingTemplate>>(app, TEMPLATES_KEY).unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn api_create_meeting_template<R: Runtime>(
+    app: AppHandle<R>,
+    mut template: RecurringMeetingTemplate,
+) -> Result<String, String> {
+    if template.title_pattern.trim().is_empty() {
+        return Err("Template title pattern must not be empty".to_string());
+    }
+    template.schedule()?;
+
+    let mut templates = load_templates(&app);
+    if template.id.is_empty() {
+        template.id = format!("{:032x}", rand::random::<u128>());
+    }
+    // Saving an existing id replaces that template
+    templates.retain(|existing| existing.id != template.id);
+    let id = template.id.clone();
+    templates.push(template);
+    store::set_value(&app, TEMPLATES_KEY, &templates)?;
+    log_info!("Saved meeting template {}", id);
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn api_list_meeting_templates<R: Runtime>(app: AppHandle<R>) -> Result<Vec<RecurringMeetingTemplate>, String> {
+    Ok(load_templates(&app))
+}
+
+#[tauri::command]
+pub fn api_delete_meeting_template<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    let mut templates = load_templates(&app);
+    let before = templates.len();
+    templates.retain(|template| template.id != id);
+    if templates.len() == before {
+        return Err(format!("Meeting template {} not found", id));
+    }
+    store::set_value(&app, TEMPLATES_KEY, &templates)?;
+    log_info!("Deleted meeting template {}", id);
+    Ok(())
+}
+
+// Templates are re-read every tick so edits take effect without restarting the scheduler
+pub fn start_scheduler<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut last_check = Local::now();
+        let mut interval = tokio::time::interval(SCHEDULER_TICK);
+        loop {
+            interval.tick().await;
+            let now = Local::now();
+            let due = load_templates(&app).into_iter().find(|template| {
+                template.auto_start_recording
+                    && template
+                        .schedule()
+                        .ok()
+                        .and_then(|schedule| schedule.after(&last_check).next())
+                        .is_some_and(|next| next <= now)
+            });
+            last_check = now;
+
+            if let Some(template) = due {
+                if recording_active() {
+                    log_info!("Skipping scheduled meeting {}: already recording", template.id);
+                    continue;
+                }
+                start_template_recording(&app, template).await;
+            }
+        }
+    });
+}
+
+async fn start_template_recording<R: Runtime>(app: &AppHandle<R>, template: RecurringMeetingTemplate) {
+    let title = template.render_title(Local::now());
+    let state = app.state::<RecordingState>().inner().clone();
+    match start_recording_session(app.clone(), &state, None, None).await {
+        Ok(session_id) => {
+            log_info!("Auto-started recording '{}' from template {}", title, template.id);
+            let payload = TemplateRecordingStarted {
+                template_id: template.id,
+                title,
+                tags: template.default_tags,
+                summary_prompt_id: template.summary_prompt_id,
+                session_id,
+            };
+            if let Err(e) = app.emit("meeting-template-started", payload) {
+                log_error!("Failed to emit meeting-template-started event: {}", e);
+            }
+        }
+        Err(e) => log_error!("Failed to auto-start recording for template {}: {}", template.id, e),
+    }
+}
@@ -0,0 +1,99 @@
+// Exports a meeting as a page in a user-chosen Notion database
+use anyhow::{anyhow, Result};
+use log::info as log_info;
+use serde_json::{json, Value};
+
+use crate::http_client;
+
+const NOTION_API_URL: &str = "https://api.notion.com/v1";
+const NOTION_VERSION: &str = "2022-06-28";
+// Notion rejects requests with more children or longer text runs than this
+const MAX_BLOCKS_PER_REQUEST: usize = 100;
+const MAX_RICH_TEXT_CHARS: usize = 2000;
+
+pub struct MeetingData {
+    pub title: String,
+    // YYYY-MM-DD or full ISO 8601
+    pub date: String,
+    pub duration_secs: f32,
+    pub tags: Vec<String>,
+    pub summary: String,
+    pub sentences: Vec<String>,
+}
+
+pub struct NotionPage {
+    pub id: String,
+    pub url: String,
+}
+
+fn rich_text(text: &str) -> Value {
+    let content: String = text.chars().take(MAX_RICH_TEXT_CHARS).collect();
+    json!([{ "type": "text", "text": { "content": content } }])
+}
+
+fn blocks(meeting: &MeetingData) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    if !meeting.summary.trim().is_empty() {
+        blocks.push(json!({ "object": "block", "type": "heading_2", "heading_2": { "rich_text": rich_text("Summary") } }));
+        blocks.push(json!({ "object": "block", "type": "paragraph", "paragraph": { "rich_text": rich_text(&meeting.summary) } }));
+    }
+    if !meeting.sentences.is_empty() {
+        blocks.push(json!({ "object": "block", "type": "heading_2", "heading_2": { "rich_text": rich_text("Transcript") } }));
+        blocks.extend(meeting.sentences.iter().map(|sentence| {
+            json!({ "object": "block", "type": "bulleted_list_item", "bulleted_list_item": { "rich_text": rich_text(sentence) } })
+        }));
+    }
+    blocks
+}
+
+async fn notion_request(request: reqwest::RequestBuilder, api_key: &str) -> Result<Value> {
+    let response = request
+        .bearer_auth(api_key)
+        .header("Notion-Version", NOTION_VERSION)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Notion API error {}: {}", status, body));
+    }
+    Ok(response.json().await?)
+}
+
+/// Creates the page and returns its id and URL. The database needs `Name` (title),
+/// `Date` (date), `Duration` (number, minutes) and `Tags` (multi-select) properties.
+pub async fn create_notion_page(api_key: &str, database_id: &str, meeting: &MeetingData) -> Result<NotionPage> {
+    let client = http_client::get_client();
+    let mut children = blocks(meeting);
+    let remaining = children.split_off(children.len().min(MAX_BLOCKS_PER_REQUEST));
+
+    let body = json!({
+        "parent": { "database_id": database_id },
+        "properties": {
+            "Name": { "title": rich_text(&meeting.title) },
+            "Date": { "date": { "start": meeting.date } },
+            "Duration": { "number": (meeting.duration_secs / 60.0 * 10.0).round() / 10.0 },
+            "Tags": { "multi_select": meeting.tags.iter().map(|tag| json!({ "name": tag })).collect::<Vec<_>>() },
+        },
+        "children": children,
+    });
+    let page = notion_request(client.post(format!("{}/pages", NOTION_API_URL)).json(&body), api_key).await?;
+
+    let id = page
+        .get("id")
+        .and_then(|id| id.as_str())
+        .ok_or_else(|| anyhow!("Notion response did not include a page id"))?
+        .to_string();
+    let url = page.get("url").and_then(|url| url.as_str()).unwrap_or_default().to_string();
+
+    // Long transcripts are appended in batches after the page exists
+    for batch in remaining.chunks(MAX_BLOCKS_PER_REQUEST) {
+        let request = client
+            .patch(format!("{}/blocks/{}/children", NOTION_API_URL, id))
+            .json(&json!({ "children": batch }));
+        notion_request(request, api_key).await?;
+    }
+
+    log_info!("Created Notion page {}", id);
+    Ok(NotionPage { id, url })
+}
@@ -1,8 +1,20 @@
 use std::process::Command;
+use std::sync::RwLock;
+use std::time::Duration;
+use log::info as log_info;
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use tauri::command;
+use tauri::{command, AppHandle, Runtime};
 use reqwest::blocking::Client;
 
+use crate::{http_client, store};
+
+const BASE_URL_KEY: &str = "ollamaBaseUrl";
+const DEFAULT_BASE_URL: &str = "http://127.0.0.1:11434";
+
+// Mirrored from the "ollamaBaseUrl" store key so callers don't need an AppHandle
+static BASE_URL: Lazy<RwLock<String>> = Lazy::new(|| RwLock::new(DEFAULT_BASE_URL.to_string()));
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OllamaModel {
     pub name: String,
@@ -41,7 +53,7 @@ pub fn get_ollama_models() -> Result<Vec<OllamaModel>, String> {
 fn get_models_via_http() -> Result<Vec<OllamaModel>, String> {
     let client = Client::new();
     let response = client
-        .get("http://localhost:11434/api/tags")
+        .get(format!("{}/api/tags", get_ollama_base_url()))
         .send()
         .map_err(|e| format!("Failed to make HTTP request: {}", e))?;
 
@@ -61,6 +73,61 @@ fn get_models_via_http() -> Result<Vec<OllamaModel>, String> {
     }).collect())
 }
 
+fn normalize_base_url(url: &str) -> Result<String, String> {
+    let url = url.trim().trim_end_matches('/');
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(format!("Ollama URL must start with http:// or https://: {}", url));
+    }
+    reqwest::Url::parse(url).map_err(|e| format!("Invalid Ollama URL {}: {}", url, e))?;
+    Ok(url.to_string())
+}
+
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(url) = store::get_value::<String>(app, BASE_URL_KEY) {
+        if let Ok(mut guard) = BASE_URL.write() {
+            *guard = url;
+        }
+    }
+}
+
+#[command]
+pub fn get_ollama_base_url() -> String {
+    BASE_URL
+        .read()
+        .map(|url| url.clone())
+        .unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+#[command]
+pub fn set_ollama_base_url<R: Runtime>(app: AppHandle<R>, url: String) -> Result<(), String> {
+    let url = normalize_base_url(&url)?;
+    store::set_value(&app, BASE_URL_KEY, &url)?;
+    if let Ok(mut guard) = BASE_URL.write() {
+        *guard = url.clone();
+    }
+    log_info!("Ollama base URL set to {}", url);
+    Ok(())
+}
+
+// Ollama answers "Ollama is running" on its root path
+#[command]
+pub async fn test_ollama_connection(base_url: Option<String>) -> Result<bool, String> {
+    let base_url = match base_url {
+        Some(url) => normalize_base_url(&url)?,
+        None => get_ollama_base_url(),
+    };
+    let response = http_client::get_client()
+        .get(format!("{}/", base_url))
+        .timeout(Duration::from_secs(3))
+        .send()
+        .await;
+    match response {
+        Ok(response) => Ok(response.status().is_success()),
+        Err(e) if e.is_timeout() || e.is_connect() => Ok(false),
+        Err(e) => Err(format!("Failed to reach Ollama at {}: {}", base_url, e)),
+    }
+}
+
 fn get_models_via_cli() -> Result<Vec<OllamaModel>, String> {
     let output = Command::new("ollama")
         .arg("list")
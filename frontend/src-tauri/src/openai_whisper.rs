@@ -0,0 +1,103 @@
+// OpenAI's hosted Whisper (whisper-1), an alternative to the local transcription server
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use std::io::Cursor;
+use std::sync::RwLock;
+use tauri::{AppHandle, Runtime};
+
+use crate::{http_client, store};
+
+const OPENAI_TRANSCRIPTION_URL: &str = "https://api.openai.com/v1/audio/transcriptions";
+const OPENAI_WHISPER_MODEL: &str = "whisper-1";
+const OPENAI_API_KEY_KEY: &str = "openaiApiKey";
+
+// Mirrors the "openaiApiKey" store key so transcription doesn't need an app handle
+static OPENAI_API_KEY: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
+
+#[derive(Debug, Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+}
+
+// Called when a recording starts; falls back to the OPENAI_API_KEY environment variable
+pub fn load<R: Runtime>(app: &AppHandle<R>) {
+    let key = store::get_value::<String>(app, OPENAI_API_KEY_KEY)
+        .or_else(|| std::env::var("OPENAI_API_KEY").ok())
+        .filter(|key| !key.trim().is_empty());
+    if let Ok(mut current) = OPENAI_API_KEY.write() {
+        *current = key;
+    }
+}
+
+pub fn is_configured() -> bool {
+    OPENAI_API_KEY.read().map_or(false, |key| key.is_some())
+}
+
+#[tauri::command]
+pub fn set_openai_api_key<R: Runtime>(app: AppHandle<R>, api_key: String) -> Result<(), String> {
+    let api_key = api_key.trim().to_string();
+    if api_key.is_empty() {
+        store::delete_value(&app, OPENAI_API_KEY_KEY)?;
+    } else {
+        store::set_value(&app, OPENAI_API_KEY_KEY, &api_key)?;
+    }
+    load(&app);
+    log::info!("OpenAI API key {}", if is_configured() { "saved" } else { "cleared" });
+    Ok(())
+}
+
+/// Transcribes mono PCM samples with OpenAI's `whisper-1` model and returns the whole text.
+///
+/// * `audio` – Mono PCM samples in the range [-1.0, 1.0].
+/// * `sample_rate` – Sample rate of `audio` (Hz).
+/// * `language` – Optional ISO-639-1 hint, e.g. "en".
+pub async fn transcribe_with_openai(audio: &[f32], sample_rate: u32, language: Option<String>) -> Result<String> {
+    let api_key = OPENAI_API_KEY
+        .read()
+        .ok()
+        .and_then(|key| key.clone())
+        .ok_or_else(|| anyhow!("Missing OpenAI API key"))?;
+
+    // Same 16-bit WAV encoding as the Groq upload
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::new(Cursor::new(Vec::<u8>::new()), spec)?;
+    for &sample in audio {
+        writer.write_sample((sample.clamp(-1.0, 1.0) * 32767.0) as i16)?;
+    }
+    let wav_bytes = writer.into_inner()?.into_inner();
+
+    let file_part = Part::bytes(wav_bytes)
+        .file_name("audio.wav")
+        .mime_str("audio/wav")?;
+    let mut form = Form::new()
+        .part("file", file_part)
+        .text("model", OPENAI_WHISPER_MODEL)
+        .text("response_format", "json")
+        .text("temperature", "0");
+    if let Some(language) = language {
+        form = form.text("language", language);
+    }
+
+    let resp = http_client::get_client()
+        .post(OPENAI_TRANSCRIPTION_URL)
+        .bearer_auth(api_key)
+        .multipart(form)
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let err_body = resp.text().await.unwrap_or_default();
+        return Err(anyhow!("OpenAI API error {status}: {err_body}"));
+    }
+
+    let body: TranscriptionResponse = resp.json().await?;
+    Ok(body.text.trim().to_string())
+}
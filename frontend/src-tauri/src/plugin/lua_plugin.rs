@@ -0,0 +1,61 @@
+use anyhow::{anyhow, Result};
+use log::debug;
+use mlua::Lua;
+use std::fs;
+
+/// Runs user supplied Lua scripts against a transcript.
+///
+/// Scripts see two globals, `transcript` and `meeting_title`, and may call
+/// `replace(from, to)` to rewrite every occurrence of `from` in the transcript.
+/// Whatever `transcript` holds once the script finishes is returned.
+pub struct LuaPluginRunner {
+    lua: Lua,
+}
+
+impl LuaPluginRunner {
+    pub fn new() -> Result<Self> {
+        let lua = Lua::new();
+
+        let replace = lua
+            .create_function(|lua, (from, to): (String, String)| {
+                let globals = lua.globals();
+                let current: String = globals.get("transcript")?;
+                globals.set("transcript", current.replace(&from, &to))?;
+                Ok(())
+            })
+            .map_err(|e| anyhow!("Failed to register replace helper: {}", e))?;
+        lua.globals()
+            .set("replace", replace)
+            .map_err(|e| anyhow!("Failed to register replace helper: {}", e))?;
+
+        Ok(Self { lua })
+    }
+
+    pub fn run(&self, script_path: &str, transcript: &str, meeting_title: &str) -> Result<String> {
+        let script = fs::read_to_string(script_path)
+            .map_err(|e| anyhow!("Failed to read plugin script {}: {}", script_path, e))?;
+
+        let globals = self.lua.globals();
+        globals
+            .set("transcript", transcript)
+            .map_err(|e| anyhow!("Failed to set transcript: {}", e))?;
+        globals
+            .set("meeting_title", meeting_title)
+            .map_err(|e| anyhow!("Failed to set meeting_title: {}", e))?;
+
+        debug!("Running transcript plugin: {}", script_path);
+        self.lua
+            .load(&script)
+            .set_name(script_path)
+            .exec()
+            .map_err(|e| anyhow!("Plugin {} failed: {}", script_path, e))?;
+
+        globals
+            .get::<_, String>("transcript")
+            .map_err(|e| anyhow!("Plugin {} left an invalid transcript: {}", script_path, e))
+    }
+}
+
+pub fn run_transcript_plugin(script_path: &str, transcript: &str) -> Result<String> {
+    LuaPluginRunner::new()?.run(script_path, transcript, "")
+}
@@ -0,0 +1,37 @@
+// src/plugin/mod.rs
+#[cfg(feature = "lua-plugins")]
+pub mod lua_plugin;
+
+use crate::api::fetch_meeting;
+#[cfg(feature = "lua-plugins")]
+use crate::api::transcript_text;
+
+/// Runs a Lua plugin over a meeting transcript and returns the result.
+/// Nothing is persisted; the frontend calls `api_save_transcript` if the user keeps it.
+#[tauri::command]
+pub async fn apply_transcript_plugin(meeting_id: String, script_path: String) -> Result<String, String> {
+    if !std::path::Path::new(&script_path).is_file() {
+        return Err(format!("Plugin script not found: {}", script_path));
+    }
+
+    let meeting = fetch_meeting(&meeting_id).await?;
+
+    #[cfg(feature = "lua-plugins")]
+    {
+        let transcript = transcript_text(&meeting);
+        // Lua state is not Send, so run the script on a blocking thread
+        tokio::task::spawn_blocking(move || {
+            lua_plugin::LuaPluginRunner::new()
+                .and_then(|runner| runner.run(&script_path, &transcript, &meeting.title))
+                .map_err(|e| e.to_string())
+        })
+        .await
+        .map_err(|e| format!("Plugin task failed: {}", e))?
+    }
+
+    #[cfg(not(feature = "lua-plugins"))]
+    {
+        let _ = meeting;
+        Err("Lua plugins are not enabled in this build".to_string())
+    }
+}
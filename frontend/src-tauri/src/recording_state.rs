@@ -0,0 +1,60 @@
+// Streams, buffers and timing of the current recording, registered with `manage()` in `run`
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use tokio::sync::MutexGuard;
+use tokio::task::JoinHandle;
+
+use crate::audio::ring_buffer::RingBuffer;
+use crate::audio::AudioStream;
+
+/// Shared with the capture loop and the level monitor, which push and read samples without the state lock.
+pub type SampleBuffer = Arc<Mutex<RingBuffer<f32>>>;
+
+#[derive(Default)]
+pub struct RecordingStateInner {
+    pub mic_buffer: Option<SampleBuffer>,
+    pub system_buffer: Option<SampleBuffer>,
+    pub mic_stream: Option<Arc<AudioStream>>,
+    pub system_stream: Option<Arc<AudioStream>>,
+    // Cleared to stop the capture loop and the tasks hanging off it
+    pub is_running: Option<Arc<AtomicBool>>,
+    pub start_time: Option<Instant>,
+    // Finishes once the last transcript segments have been filed and emitted
+    pub capture_task: Option<JoinHandle<()>>,
+}
+
+impl RecordingStateInner {
+    /// True once nothing from a recording is held any more.
+    pub fn is_empty(&self) -> bool {
+        self.mic_buffer.is_none()
+            && self.system_buffer.is_none()
+            && self.mic_stream.is_none()
+            && self.system_stream.is_none()
+            && self.is_running.is_none()
+            && self.start_time.is_none()
+            && self.capture_task.is_none()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct RecordingState {
+    inner: Arc<tokio::sync::Mutex<RecordingStateInner>>,
+}
+
+impl RecordingState {
+    pub async fn lock(&self) -> MutexGuard<'_, RecordingStateInner> {
+        self.inner.lock().await
+    }
+
+    /// For sync readers; gives up rather than waiting while a start or stop holds the lock.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, RecordingStateInner>> {
+        self.inner.try_lock().ok()
+    }
+
+    /// Empties the state and hands back what it held, so it can be shut down without holding the lock.
+    pub async fn take(&self) -> RecordingStateInner {
+        std::mem::take(&mut *self.inner.lock().await)
+    }
+}
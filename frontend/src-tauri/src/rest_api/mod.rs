@@ -0,0 +1,64 @@
+// src/rest_api/mod.rs
+// Read-only HTTP access to meeting data for scripts and other apps
+#[cfg(feature = "rest-api-server")]
+mod server;
+
+use tauri::{AppHandle, Runtime};
+
+#[cfg(feature = "rest-api-server")]
+use crate::store;
+
+#[tauri::command]
+pub async fn start_rest_api_server<R: Runtime>(app: AppHandle<R>, port: u16) -> Result<(), String> {
+    #[cfg(feature = "rest-api-server")]
+    {
+        // Token is generated on first start and reused so scripts keep working
+        let token = match store::get_value::<String>(&app, "restApiToken").filter(|t| !t.is_empty()) {
+            Some(token) => token,
+            None => {
+                let token = format!("{:032x}", rand::random::<u128>());
+                store::set_value(&app, "restApiToken", &token)?;
+                token
+            }
+        };
+        server::start(
+            port,
+            token,
+            crate::api::model_context_window(&app),
+            crate::api::is_local_only_mode(app.clone()),
+        )
+        .await
+    }
+
+    #[cfg(not(feature = "rest-api-server"))]
+    {
+        let _ = (app, port);
+        Err("The REST API server is not enabled in this build".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn stop_rest_api_server() -> Result<(), String> {
+    #[cfg(feature = "rest-api-server")]
+    {
+        server::stop().await
+    }
+
+    #[cfg(not(feature = "rest-api-server"))]
+    {
+        Err("The REST API server is not enabled in this build".to_string())
+    }
+}
+
+#[tauri::command]
+pub fn is_rest_api_running() -> bool {
+    #[cfg(feature = "rest-api-server")]
+    {
+        server::is_running()
+    }
+
+    #[cfg(not(feature = "rest-api-server"))]
+    {
+        false
+    }
+}
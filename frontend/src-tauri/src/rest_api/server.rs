@@ -0,0 +1,129 @@
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use log::{error as log_error, info as log_info};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use crate::api;
+
+struct ServerHandle {
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+static REST_API_HANDLE: Lazy<Mutex<Option<ServerHandle>>> = Lazy::new(|| Mutex::new(None));
+
+type ApiResult = Result<Json<Value>, (StatusCode, Json<Value>)>;
+
+fn api_error(status: StatusCode, message: String) -> (StatusCode, Json<Value>) {
+    (status, Json(json!({ "error": message })))
+}
+
+// Backend errors are reported as 502 since the data lives behind another service
+fn backend_error(message: String) -> (StatusCode, Json<Value>) {
+    api_error(StatusCode::BAD_GATEWAY, message)
+}
+
+async fn require_token(State(token): State<Arc<String>>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map_or(false, |provided| provided == token.as_str());
+
+    if !authorized {
+        return api_error(StatusCode::UNAUTHORIZED, "Missing or invalid bearer token".to_string()).into_response();
+    }
+    next.run(request).await
+}
+
+async fn list_meetings() -> ApiResult {
+    let meetings = api::fetch_meetings().await.map_err(backend_error)?;
+    Ok(Json(json!(meetings)))
+}
+
+async fn get_meeting(Path(id): Path<String>) -> ApiResult {
+    let meeting = api::fetch_meeting(&id).await.map_err(backend_error)?;
+    Ok(Json(json!(meeting)))
+}
+
+async fn get_transcript(Path(id): Path<String>) -> ApiResult {
+    let meeting = api::fetch_meeting(&id).await.map_err(backend_error)?;
+    Ok(Json(json!({ "meeting_id": meeting.id, "transcript": api::transcript_text(&meeting) })))
+}
+
+async fn generate_summary(Path(id): Path<String>, context_window: usize, local_only: bool) -> ApiResult {
+    let process_id = api::process_transcript(&id, context_window, local_only).await.map_err(backend_error)?;
+    Ok(Json(json!({ "meeting_id": id, "process_id": process_id })))
+}
+
+// `context_window` and `local_only` are read from settings when the server starts
+pub async fn start(port: u16, token: String, context_window: usize, local_only: bool) -> Result<(), String> {
+    if is_running() {
+        return Err("REST API server is already running".to_string());
+    }
+
+    let router = Router::new()
+        .route("/meetings", get(list_meetings))
+        .route("/meetings/:id", get(get_meeting))
+        .route("/meetings/:id/transcript", get(get_transcript))
+        .route("/meetings/:id/summary", post(move |path: Path<String>| generate_summary(path, context_window, local_only)))
+        .layer(middleware::from_fn_with_state(Arc::new(token), require_token));
+
+    // Loopback only; other machines have no business reading meeting data
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| format!("Failed to bind REST API to port {}: {}", port, e))?;
+
+    // Only taken once the bind has finished; a std guard held across an await makes the command !Send
+    let mut handle = REST_API_HANDLE.lock().map_err(|_| "REST API state poisoned".to_string())?;
+    // Another start may have won the race while this one was binding
+    if handle.as_ref().map_or(false, |h| !h.task.is_finished()) {
+        return Err("REST API server is already running".to_string());
+    }
+
+    let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+    let task = tokio::spawn(async move {
+        let server = axum::serve(listener, router).with_graceful_shutdown(async {
+            let _ = shutdown_rx.await;
+        });
+        if let Err(e) = server.await {
+            log_error!("REST API server stopped with error: {}", e);
+        }
+    });
+
+    *handle = Some(ServerHandle { shutdown, task });
+    log_info!("REST API server listening on 127.0.0.1:{}", port);
+    Ok(())
+}
+
+pub async fn stop() -> Result<(), String> {
+    let handle = REST_API_HANDLE
+        .lock()
+        .map_err(|_| "REST API state poisoned".to_string())?
+        .take()
+        .ok_or_else(|| "REST API server is not running".to_string())?;
+
+    let _ = handle.shutdown.send(());
+    handle
+        .task
+        .await
+        .map_err(|e| format!("REST API server task failed: {}", e))?;
+    log_info!("REST API server stopped");
+    Ok(())
+}
+
+pub fn is_running() -> bool {
+    REST_API_HANDLE
+        .lock()
+        .map(|handle| handle.as_ref().map_or(false, |h| !h.task.is_finished()))
+        .unwrap_or(false)
+}
@@ -0,0 +1,124 @@
+// Resource usage reported by the transcription server, for sizing hardware
+use log::{info as log_info, warn as log_warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::TRANSCRIPTION_SERVER_URL;
+
+static POLLING_TASK: Lazy<Mutex<Option<tauri::async_runtime::JoinHandle<()>>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerMetrics {
+    #[serde(default)]
+    pub cpu_percent: Option<f32>,
+    #[serde(default)]
+    pub memory_mb: Option<u32>,
+    #[serde(default)]
+    pub gpu_utilization_percent: Option<f32>,
+    #[serde(default)]
+    pub vram_used_mb: Option<u32>,
+    #[serde(default)]
+    pub active_requests: u32,
+    #[serde(default)]
+    pub total_requests_processed: u64,
+    #[serde(default)]
+    pub average_processing_ms: u64,
+}
+
+// Prometheus text format: `name{labels} value [timestamp]`; labelled series are summed
+fn parse_prometheus(body: &str) -> HashMap<String, f64> {
+    let mut values: HashMap<String, f64> = HashMap::new();
+    for line in body.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let mut parts = line.split_whitespace();
+        let (Some(series), Some(value)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let name = series.split('{').next().unwrap_or(series);
+        if let Ok(value) = value.parse::<f64>() {
+            *values.entry(name.to_string()).or_insert(0.0) += value;
+        }
+    }
+    values
+}
+
+// Servers usually prefix their metrics (e.g. `whisper_active_requests`), so match on the suffix
+fn prometheus_value(values: &HashMap<String, f64>, field: &str) -> Option<f64> {
+    values
+        .iter()
+        .find(|(name, _)| *name == field || name.ends_with(&format!("_{}", field)))
+        .map(|(_, value)| *value)
+}
+
+fn metrics_from_prometheus(body: &str) -> ServerMetrics {
+    let values = parse_prometheus(body);
+    let get = |field: &str| prometheus_value(&values, field);
+    ServerMetrics {
+        cpu_percent: get("cpu_percent").map(|v| v as f32),
+        memory_mb: get("memory_mb")
+            .or_else(|| values.get("process_resident_memory_bytes").map(|bytes| bytes / 1_048_576.0))
+            .map(|v| v as u32),
+        gpu_utilization_percent: get("gpu_utilization_percent").map(|v| v as f32),
+        vram_used_mb: get("vram_used_mb").map(|v| v as u32),
+        active_requests: get("active_requests").unwrap_or(0.0) as u32,
+        total_requests_processed: get("total_requests_processed").unwrap_or(0.0) as u64,
+        average_processing_ms: get("average_processing_ms").unwrap_or(0.0) as u64,
+    }
+}
+
+pub async fn fetch_metrics(url: &str) -> Result<ServerMetrics, String> {
+    let response = reqwest::Client::new()
+        .get(format!("{}/metrics", url.trim_end_matches('/')))
+        .timeout(Duration::from_secs(5))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach transcription server: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Transcription server returned HTTP {} for /metrics", response.status()));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read metrics response: {}", e))?;
+    Ok(serde_json::from_str::<ServerMetrics>(&body).unwrap_or_else(|_| metrics_from_prometheus(&body)))
+}
+
+#[tauri::command]
+pub async fn get_transcription_server_metrics(url: Option<String>) -> Result<ServerMetrics, String> {
+    fetch_metrics(url.as_deref().unwrap_or(TRANSCRIPTION_SERVER_URL)).await
+}
+
+// An interval of 0 stops polling
+#[tauri::command]
+pub fn enable_metrics_polling<R: Runtime>(app: AppHandle<R>, interval_secs: u32, url: Option<String>) -> Result<(), String> {
+    let mut task = POLLING_TASK.lock().map_err(|_| "Metrics polling state poisoned".to_string())?;
+    if let Some(handle) = task.take() {
+        handle.abort();
+    }
+    if interval_secs == 0 {
+        log_info!("Transcription server metrics polling stopped");
+        return Ok(());
+    }
+
+    let url = url.unwrap_or_else(|| TRANSCRIPTION_SERVER_URL.to_string());
+    *task = Some(tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs as u64));
+        loop {
+            interval.tick().await;
+            match fetch_metrics(&url).await {
+                Ok(metrics) => {
+                    if let Err(e) = app.emit("server-metrics", metrics) {
+                        log_warn!("Failed to emit server metrics: {}", e);
+                    }
+                }
+                Err(e) => log_warn!("{}", e),
+            }
+        }
+    }));
+    log_info!("Polling transcription server metrics every {} s", interval_secs);
+    Ok(())
+}
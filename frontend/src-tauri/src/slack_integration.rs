@@ -0,0 +1,66 @@
+// Posts meeting minutes to a Slack channel through an incoming webhook
+use anyhow::{anyhow, Result};
+use log::info as log_info;
+use serde_json::{json, Value};
+
+use crate::http_client;
+
+// Slack rejects section text longer than this
+const MAX_SECTION_CHARS: usize = 3000;
+
+pub struct SlackMeetingPayload {
+    pub title: String,
+    pub summary: String,
+    pub action_items: Vec<String>,
+    pub meeting_url: Option<String>,
+}
+
+fn section(text: &str) -> Value {
+    let text: String = text.chars().take(MAX_SECTION_CHARS).collect();
+    json!({ "type": "section", "text": { "type": "mrkdwn", "text": text } })
+}
+
+fn blocks(meeting: &SlackMeetingPayload) -> Vec<Value> {
+    let title: String = meeting.title.chars().take(150).collect();
+    let mut blocks = vec![json!({ "type": "header", "text": { "type": "plain_text", "text": title } })];
+    if !meeting.summary.trim().is_empty() {
+        blocks.push(section(&format!("*Summary*\n{}", meeting.summary.trim())));
+    }
+    if !meeting.action_items.is_empty() {
+        let items: Vec<String> = meeting.action_items.iter().map(|item| format!("• {}", item)).collect();
+        blocks.push(section(&format!("*Action items*\n{}", items.join("\n"))));
+    }
+    if let Some(url) = &meeting.meeting_url {
+        blocks.push(json!({ "type": "context", "elements": [{ "type": "mrkdwn", "text": format!("<{}|Open meeting>", url) }] }));
+    }
+    blocks
+}
+
+async fn post(webhook_url: &str, body: &Value) -> Result<()> {
+    let response = http_client::get_client().post(webhook_url).json(body).send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Slack webhook error {}: {}", status, body));
+    }
+    Ok(())
+}
+
+/// `channel` overrides the webhook's default channel where the webhook allows it.
+pub async fn post_to_slack(webhook_url: &str, meeting: &SlackMeetingPayload, channel: Option<&str>) -> Result<()> {
+    let mut body = json!({
+        // Shown in notifications and clients without Block Kit support
+        "text": format!("Meeting minutes: {}", meeting.title),
+        "blocks": blocks(meeting),
+    });
+    if let Some(channel) = channel {
+        body["channel"] = json!(channel);
+    }
+    post(webhook_url, &body).await?;
+    log_info!("Posted meeting \"{}\" to Slack", meeting.title);
+    Ok(())
+}
+
+pub async fn post_test_message(webhook_url: &str) -> Result<()> {
+    post(webhook_url, &json!({ "text": "Meetily is connected to this channel." })).await
+}
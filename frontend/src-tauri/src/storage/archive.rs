@@ -0,0 +1,67 @@
+// Archived meetings keep their transcript gzip-compressed in the store and their recording under archive/
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::api::Transcript;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedMeeting {
+    pub meeting_id: String,
+    pub title: String,
+    pub created_at: String,
+    // Base64 of the gzipped transcript segments as JSON
+    pub transcript: String,
+    pub archived: bool,
+    pub archived_at: String,
+    // Where the recording lived before it was moved into the archive directory
+    #[serde(default)]
+    pub original_audio_path: Option<String>,
+}
+
+pub fn archive_key(meeting_id: &str) -> String {
+    format!("archivedMeeting_{}", meeting_id)
+}
+
+pub fn compress_transcripts(transcripts: &[Transcript]) -> Result<String> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&serde_json::to_vec(transcripts)?)?;
+    Ok(BASE64.encode(encoder.finish()?))
+}
+
+pub fn decompress_transcripts(compressed: &str) -> Result<Vec<Transcript>> {
+    let bytes = BASE64.decode(compressed).context("Archived transcript is not valid base64")?;
+    let mut json = Vec::new();
+    GzDecoder::new(bytes.as_slice())
+        .read_to_end(&mut json)
+        .context("Archived transcript is not valid gzip")?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+pub fn archive_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .context("Failed to resolve app data directory")?
+        .join("archive");
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    Ok(dir)
+}
+
+// Renames when possible; recordings may sit on another volume, so fall back to copy and delete
+pub fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to).with_context(|| format!("Failed to copy {} to {}", from.display(), to.display()))?;
+    fs::remove_file(from).with_context(|| format!("Failed to remove {}", from.display()))?;
+    Ok(())
+}
@@ -0,0 +1,43 @@
+// JSON sidecar written next to each recording so it stays meaningful when copied elsewhere
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+pub const METADATA_EXTENSION: &str = "mmeta";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingMeta {
+    pub meeting_id: String,
+    pub title: String,
+    pub created_at: String,
+    pub duration_secs: f32,
+    pub sample_rate: u32,
+    pub transcription_engine: String,
+    pub language: String,
+    // App version that wrote the file
+    pub version: String,
+}
+
+pub fn metadata_path(wav_path: &str) -> String {
+    format!("{}.{}", wav_path, METADATA_EXTENSION)
+}
+
+// The sidecar sits at `{wav_path}.mmeta`, so stripping the extension gives the recording back
+pub fn recording_path(mmeta_path: &str) -> Option<&str> {
+    mmeta_path.strip_suffix(&format!(".{}", METADATA_EXTENSION))
+}
+
+pub fn write_metadata_file(wav_path: &str, meta: &MeetingMeta) -> Result<()> {
+    let path = metadata_path(wav_path);
+    let contents = serde_json::to_string_pretty(meta)?;
+    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path))?;
+    Ok(())
+}
+
+pub fn read_metadata_file(mmeta_path: &str) -> Result<MeetingMeta> {
+    let contents = fs::read_to_string(mmeta_path)
+        .with_context(|| format!("Failed to read {}", mmeta_path))?;
+    let meta = serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid metadata file {}", mmeta_path))?;
+    Ok(meta)
+}
@@ -0,0 +1,4 @@
+pub mod archive;
+pub mod metadata;
+
+pub use metadata::{read_metadata_file, write_metadata_file, MeetingMeta};
@@ -0,0 +1,39 @@
+// Thin wrapper around tauri-plugin-store for reading and writing persisted settings
+use serde::{de::DeserializeOwned, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+pub const STORE_FILE: &str = "store.json";
+
+pub fn get_value<R: Runtime, T: DeserializeOwned>(app: &AppHandle<R>, key: &str) -> Option<T> {
+    let store = app.store(STORE_FILE).ok()?;
+    store
+        .get(key)
+        .and_then(|value| serde_json::from_value(value).ok())
+}
+
+pub fn set_value<R: Runtime, T: Serialize>(app: &AppHandle<R>, key: &str, value: &T) -> Result<(), String> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| format!("Failed to open store: {}", e))?;
+    let value = serde_json::to_value(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", key, e))?;
+    store.set(key, value);
+    store.save().map_err(|e| format!("Failed to save store: {}", e))
+}
+
+/// Every key and value currently in the store.
+pub fn entries<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<(String, serde_json::Value)>, String> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| format!("Failed to open store: {}", e))?;
+    Ok(store.entries())
+}
+
+pub fn delete_value<R: Runtime>(app: &AppHandle<R>, key: &str) -> Result<(), String> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| format!("Failed to open store: {}", e))?;
+    store.delete(key);
+    store.save().map_err(|e| format!("Failed to save store: {}", e))
+}
@@ -0,0 +1,111 @@
+// OpenTelemetry export of the tracing spans around the audio pipeline
+use log::error as log_error;
+#[cfg(feature = "otel-tracing")]
+use log::info as log_info;
+use tauri::{AppHandle, Runtime};
+
+use crate::store;
+
+const OTEL_ENDPOINT_KEY: &str = "otelEndpoint";
+const OTEL_ENABLED_KEY: &str = "otelEnabled";
+
+#[cfg(feature = "otel-tracing")]
+mod exporter {
+    use once_cell::sync::OnceCell;
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::{runtime, trace, Resource};
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    use tracing_subscriber::{reload, Registry};
+
+    type OtelLayer = tracing_opentelemetry::OpenTelemetryLayer<Registry, trace::Tracer>;
+
+    // The global subscriber can only be installed once, so the exporter layer is swapped in place
+    static OTEL_LAYER: OnceCell<reload::Handle<Option<OtelLayer>, Registry>> = OnceCell::new();
+
+    fn layer_handle() -> Result<&'static reload::Handle<Option<OtelLayer>, Registry>, String> {
+        OTEL_LAYER.get_or_try_init(|| {
+            let (layer, handle) = reload::Layer::new(None);
+            tracing_subscriber::registry()
+                .with(layer)
+                .try_init()
+                .map_err(|e| format!("Failed to install tracing subscriber: {}", e))?;
+            Ok(handle)
+        })
+    }
+
+    // Must run inside the Tokio runtime; the batch exporter spawns onto it
+    pub fn install(endpoint: &str) -> Result<(), String> {
+        let tracer = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint))
+            .with_trace_config(
+                trace::config().with_resource(Resource::new(vec![KeyValue::new("service.name", "meeting-minutes")])),
+            )
+            .install_batch(runtime::Tokio)
+            .map_err(|e| format!("Failed to create OTLP exporter for {}: {}", endpoint, e))?;
+
+        layer_handle()?
+            .reload(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+            .map_err(|e| format!("Failed to enable tracing export: {}", e))
+    }
+
+    pub fn uninstall() -> Result<(), String> {
+        if let Some(handle) = OTEL_LAYER.get() {
+            handle
+                .reload(None)
+                .map_err(|e| format!("Failed to disable tracing export: {}", e))?;
+        }
+        // Flushes spans still queued in the batch exporter
+        opentelemetry::global::shutdown_tracer_provider();
+        Ok(())
+    }
+}
+
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    if !store::get_value::<bool>(app, OTEL_ENABLED_KEY).unwrap_or(false) {
+        return;
+    }
+    let Some(endpoint) = store::get_value::<String>(app, OTEL_ENDPOINT_KEY) else {
+        return;
+    };
+
+    #[cfg(feature = "otel-tracing")]
+    tauri::async_runtime::spawn(async move {
+        match exporter::install(&endpoint) {
+            Ok(()) => log_info!("Exporting traces to {}", endpoint),
+            Err(e) => log_error!("{}", e),
+        }
+    });
+
+    #[cfg(not(feature = "otel-tracing"))]
+    log_error!("Trace export to {} is configured but OpenTelemetry is not enabled in this build", endpoint);
+}
+
+#[tauri::command]
+pub async fn configure_otel_endpoint<R: Runtime>(app: AppHandle<R>, endpoint: String, enabled: bool) -> Result<(), String> {
+    #[cfg(feature = "otel-tracing")]
+    {
+        if enabled {
+            if endpoint.trim().is_empty() {
+                return Err("OTLP endpoint must not be empty".to_string());
+            }
+            exporter::install(endpoint.trim())?;
+            log_info!("Exporting traces to {}", endpoint.trim());
+        } else {
+            exporter::uninstall()?;
+            log_info!("Trace export disabled");
+        }
+
+        store::set_value(&app, OTEL_ENDPOINT_KEY, &endpoint.trim().to_string())?;
+        store::set_value(&app, OTEL_ENABLED_KEY, &enabled)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "otel-tracing"))]
+    {
+        let _ = (app, endpoint, enabled);
+        Err("OpenTelemetry tracing is not enabled in this build".to_string())
+    }
+}
@@ -0,0 +1,79 @@
+// Prompt templates with {{variable}} placeholders, built in or saved by the user
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use tauri::{AppHandle, Runtime};
+
+use crate::store;
+
+pub const KNOWN_VARIABLES: &[&str] = &["transcript", "meeting_title", "date"];
+
+const CUSTOM_TEMPLATES_KEY: &str = "promptTemplates";
+
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "meeting_summary",
+        "Summarize the meeting \"{{meeting_title}}\" held on {{date}}. List the key decisions, \
+         action items with owners, and open questions.\n\nTranscript:\n{{transcript}}",
+    ),
+    (
+        "action_items",
+        "Extract every action item from the transcript below as a bulleted list, \
+         including the owner and deadline when mentioned.\n\nTranscript:\n{{transcript}}",
+    ),
+];
+
+static PLACEHOLDER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_]+)\s*\}\}").unwrap());
+
+// Custom templates shadow built-ins with the same id
+pub fn load_template<R: Runtime>(app: &AppHandle<R>, template_id: &str) -> Option<String> {
+    store::get_value::<HashMap<String, String>>(app, CUSTOM_TEMPLATES_KEY)
+        .and_then(|templates| templates.get(template_id).cloned())
+        .or_else(|| {
+            BUILTIN_TEMPLATES
+                .iter()
+                .find(|(id, _)| *id == template_id)
+                .map(|(_, template)| template.to_string())
+        })
+}
+
+/// Substitutes known placeholders; unknown ones are left as-is so they show up in previews.
+pub fn render(template: &str, variables: &HashMap<&str, String>) -> String {
+    PLACEHOLDER_REGEX
+        .replace_all(template, |caps: &regex::Captures| {
+            variables
+                .get(&caps[1])
+                .cloned()
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+#[tauri::command]
+pub fn validate_prompt_template(template: String) -> Result<Vec<String>, String> {
+    if template.matches("{{").count() != template.matches("}}").count() {
+        return Err("Template has unbalanced {{ }} braces".to_string());
+    }
+
+    let mut warnings: Vec<String> = PLACEHOLDER_REGEX
+        .captures_iter(&template)
+        .map(|caps| caps[1].to_string())
+        .filter(|name| !KNOWN_VARIABLES.contains(&name.as_str()))
+        .map(|name| format!("Unknown variable {{{{{}}}}}", name))
+        .collect();
+    warnings.dedup();
+
+    if !PLACEHOLDER_REGEX.captures_iter(&template).any(|caps| &caps[1] == "transcript") {
+        warnings.push("Template does not include {{transcript}}".to_string());
+    }
+    Ok(warnings)
+}
+
+#[tauri::command]
+pub fn save_prompt_template<R: Runtime>(app: AppHandle<R>, template_id: String, template: String) -> Result<Vec<String>, String> {
+    let warnings = validate_prompt_template(template.clone())?;
+    let mut templates = store::get_value::<HashMap<String, String>>(&app, CUSTOM_TEMPLATES_KEY).unwrap_or_default();
+    templates.insert(template_id, template);
+    store::set_value(&app, CUSTOM_TEMPLATES_KEY, &templates)?;
+    Ok(warnings)
+}
@@ -0,0 +1,80 @@
+// Hooks for the integration tests in tests/integration; only built with the integration-tests feature
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::audio::AudioStream;
+use crate::recording_state::RecordingState;
+use crate::{active_session, recording_active, RecordingArgs};
+
+// Stands in for the window that owns the recording, since tests have no real webview
+pub const TEST_WINDOW_LABEL: &str = "integration-test";
+
+type InjectedStreams = (Arc<AudioStream>, Arc<AudioStream>);
+
+static INJECTED_STREAMS: Lazy<Mutex<Option<InjectedStreams>>> = Lazy::new(|| Mutex::new(None));
+
+/// Makes the next recording capture from these streams instead of the default devices.
+pub fn inject_streams(mic: Arc<AudioStream>, system: Arc<AudioStream>) {
+    if let Ok(mut streams) = INJECTED_STREAMS.lock() {
+        *streams = Some((mic, system));
+    }
+}
+
+/// Registers the recording state `run` would normally manage; call once per mock app.
+pub fn manage_recording_state<R: Runtime>(app: &AppHandle<R>) {
+    app.manage(RecordingState::default());
+}
+
+fn recording_state<R: Runtime>(app: &AppHandle<R>) -> RecordingState {
+    app.state::<RecordingState>().inner().clone()
+}
+
+pub(crate) fn take_injected_streams() -> Option<InjectedStreams> {
+    INJECTED_STREAMS.lock().ok().and_then(|mut streams| streams.take())
+}
+
+pub async fn start_recording<R: Runtime>(app: AppHandle<R>) -> Result<String, String> {
+    let state = recording_state(&app);
+    crate::start_recording_session(app, &state, Some(TEST_WINDOW_LABEL.to_string()), None).await
+}
+
+/// Same check as the `is_recording` command made from the test window.
+pub fn is_recording() -> bool {
+    recording_active()
+        && active_session().map_or(false, |session| session.window_label.as_deref() == Some(TEST_WINDOW_LABEL))
+}
+
+pub async fn stop_recording<R: Runtime>(app: AppHandle<R>, save_path: String) -> Result<(), String> {
+    stop_recording_with(app, save_path, false).await
+}
+
+pub async fn stop_recording_with<R: Runtime>(
+    app: AppHandle<R>,
+    save_path: String,
+    save_raw_channels: bool,
+) -> Result<(), String> {
+    let state = recording_state(&app);
+    crate::stop_recording_session(
+        app,
+        &state,
+        RecordingArgs { save_path, save_audio: false, save_raw_channels, meeting_id: None, session_id: None },
+    )
+    .await
+}
+
+/// Mic samples the capture loop has buffered so far in the current recording.
+pub async fn buffered_mic_samples<R: Runtime>(app: &AppHandle<R>) -> usize {
+    recording_state(app)
+        .lock()
+        .await
+        .mic_buffer
+        .as_ref()
+        .and_then(|buffer| buffer.lock().ok().map(|samples| samples.len()))
+        .unwrap_or(0)
+}
+
+/// True while any capture stream or buffer from the last recording is still held.
+pub async fn holds_recording_state<R: Runtime>(app: &AppHandle<R>) -> bool {
+    !recording_state(app).lock().await.is_empty()
+}
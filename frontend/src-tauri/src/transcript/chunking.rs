@@ -0,0 +1,72 @@
+// Splits long transcripts into pieces that fit an LLM context window
+use serde::Serialize;
+
+// Rough average for English text across the tokenizers we send to
+const TOKENS_PER_WORD: f32 = 1.3;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptChunk {
+    pub text: String,
+    // Inclusive sentence range within the whole transcript
+    pub start_sentence_idx: usize,
+    pub end_sentence_idx: usize,
+    pub estimated_tokens: usize,
+}
+
+pub fn estimate_tokens(text: &str) -> usize {
+    (text.split_whitespace().count() as f32 * TOKENS_PER_WORD).ceil() as usize
+}
+
+// Transcript lines are already sentence-sized, but a line can hold several sentences
+fn split_sentences(transcript: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    for line in transcript.lines() {
+        let mut start = 0;
+        for (i, c) in line.char_indices() {
+            if matches!(c, '.' | '?' | '!') {
+                let end = i + c.len_utf8();
+                // Only split where the punctuation is followed by whitespace or the end of the line
+                if line[end..].chars().next().map_or(true, char::is_whitespace) {
+                    sentences.push(line[start..end].trim());
+                    start = end;
+                }
+            }
+        }
+        sentences.push(line[start..].trim());
+    }
+    sentences.retain(|sentence| !sentence.is_empty());
+    sentences
+}
+
+/// Packs whole sentences into chunks of at most `max_tokens` (estimated), repeating the
+/// last `overlap_sentences` of each chunk at the start of the next. A single sentence
+/// over the limit becomes its own chunk.
+pub fn chunk_transcript_for_llm(transcript: &str, max_tokens: usize, overlap_sentences: usize) -> Vec<TranscriptChunk> {
+    let sentences = split_sentences(transcript);
+    let tokens: Vec<usize> = sentences.iter().map(|s| estimate_tokens(s)).collect();
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < sentences.len() {
+        let mut end = start;
+        let mut total = tokens[start];
+        while end + 1 < sentences.len() && total + tokens[end + 1] <= max_tokens {
+            end += 1;
+            total += tokens[end];
+        }
+
+        chunks.push(TranscriptChunk {
+            text: sentences[start..=end].join(" "),
+            start_sentence_idx: start,
+            end_sentence_idx: end,
+            estimated_tokens: total,
+        });
+
+        if end + 1 >= sentences.len() {
+            break;
+        }
+        // Always move forward, even when the overlap covers the whole chunk
+        start = (end + 1).saturating_sub(overlap_sentences).max(start + 1);
+    }
+    chunks
+}
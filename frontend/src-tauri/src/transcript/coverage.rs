@@ -0,0 +1,62 @@
+// Finds stretches of a recording that no transcript segment accounts for
+use serde::Serialize;
+
+use crate::api::parse_timestamp_range;
+use crate::TranscriptUpdate;
+
+pub const DEFAULT_MIN_GAP_SECS: f32 = 5.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeGap {
+    pub start_secs: f32,
+    pub end_secs: f32,
+    pub duration_secs: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptCoverage {
+    pub total_audio_secs: f32,
+    pub covered_secs: f32,
+    pub coverage_percent: f32,
+    pub uncovered_gaps: Vec<TimeGap>,
+}
+
+/// Merges the segment time ranges and reports every uncovered stretch longer
+/// than `min_gap_secs`. Segments with unparseable timestamps are ignored.
+pub fn compute_coverage(updates: &[TranscriptUpdate], total_audio_secs: f32, min_gap_secs: f32) -> TranscriptCoverage {
+    let mut ranges: Vec<(f32, f32)> = updates
+        .iter()
+        .filter_map(|update| parse_timestamp_range(&update.timestamp))
+        .map(|(t0, t1)| (t0.clamp(0.0, total_audio_secs), t1.clamp(0.0, total_audio_secs)))
+        .filter(|(t0, t1)| t1 > t0)
+        .collect();
+    ranges.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut covered_secs = 0.0;
+    let mut uncovered_gaps = Vec::new();
+    let mut cursor = 0.0f32;
+    let mut push_gap = |start: f32, end: f32| {
+        if end - start > min_gap_secs {
+            uncovered_gaps.push(TimeGap { start_secs: start, end_secs: end, duration_secs: end - start });
+        }
+    };
+
+    for (t0, t1) in ranges {
+        if t0 > cursor {
+            push_gap(cursor, t0);
+        }
+        // Overlapping segments only count once
+        if t1 > cursor {
+            covered_secs += t1 - t0.max(cursor);
+            cursor = t1;
+        }
+    }
+    push_gap(cursor, total_audio_secs);
+
+    TranscriptCoverage {
+        total_audio_secs,
+        covered_secs,
+        coverage_percent: if total_audio_secs > 0.0 { covered_secs / total_audio_secs * 100.0 } else { 0.0 },
+        uncovered_gaps,
+    }
+}
@@ -0,0 +1,85 @@
+// Drops segments the transcription server returns twice, which Whisper does with shifted timestamps
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+
+// Short phrases ("yes", "okay") legitimately repeat, so only longer text is compared fuzzily
+const MIN_FUZZY_CHARS: usize = 20;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DedupConfig {
+    // How many recent segments new ones are compared against
+    pub window_size: usize,
+    // Maximum edit distance, as a fraction of the longer text, to still count as a duplicate
+    pub fuzzy_threshold: f32,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self { window_size: 8, fuzzy_threshold: 0.1 }
+    }
+}
+
+pub struct TranscriptDeduplicator {
+    recent_hashes: VecDeque<u64>,
+    recent_texts: VecDeque<String>,
+    window_size: usize,
+    fuzzy_threshold: f32,
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn segment_hash(text: &str, t0: f32, t1: f32) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    t0.to_bits().hash(&mut hasher);
+    t1.to_bits().hash(&mut hasher);
+    hasher.finish()
+}
+
+impl TranscriptDeduplicator {
+    pub fn new(config: DedupConfig) -> Self {
+        let window_size = config.window_size.max(1);
+        Self {
+            recent_hashes: VecDeque::with_capacity(window_size),
+            recent_texts: VecDeque::with_capacity(window_size),
+            window_size,
+            fuzzy_threshold: config.fuzzy_threshold.max(0.0),
+        }
+    }
+
+    /// Checks the segment against the recent window and remembers it if it is new.
+    pub fn is_duplicate(&mut self, text: &str, t0: f32, t1: f32) -> bool {
+        let hash = segment_hash(text, t0, t1);
+        if self.recent_hashes.contains(&hash) {
+            return true;
+        }
+
+        let normalized = normalize(text);
+        let length = normalized.chars().count();
+        if length >= MIN_FUZZY_CHARS && self.fuzzy_threshold > 0.0 {
+            let is_near_copy = self.recent_texts.iter().any(|recent| {
+                let max_len = length.max(recent.chars().count());
+                (strsim::levenshtein(&normalized, recent) as f32) < self.fuzzy_threshold * max_len as f32
+            });
+            if is_near_copy {
+                return true;
+            }
+        }
+
+        if self.recent_hashes.len() == self.window_size {
+            self.recent_hashes.pop_front();
+            self.recent_texts.pop_front();
+        }
+        self.recent_hashes.push_back(hash);
+        self.recent_texts.push_back(normalized);
+        false
+    }
+}
@@ -0,0 +1,129 @@
+// Post-processing that turns stored transcript sentences into readable structure
+use serde::Serialize;
+
+use crate::api::parse_timestamp_range;
+use crate::TranscriptUpdate;
+
+pub const DEFAULT_MAX_GAP_SECS: f32 = 3.0;
+pub const DEFAULT_MAX_PARAGRAPH_SENTENCES: usize = 8;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptParagraph {
+    pub start_time: f32,
+    pub end_time: f32,
+    pub sentences: Vec<TranscriptUpdate>,
+    pub speaker: Option<String>,
+}
+
+fn speaker_of(update: &TranscriptUpdate) -> Option<String> {
+    if update.speaker_id.is_some() {
+        return update.speaker_id.clone();
+    }
+    let source = update.source.trim();
+    (!source.is_empty()).then(|| source.to_string())
+}
+
+/// Groups consecutive sentences into paragraphs, breaking on pauses longer than
+/// `max_gap_secs`, after `max_paragraph_sentences`, or when the speaker changes.
+/// Sentences with unparseable timestamps stay in the current paragraph.
+pub fn group_into_paragraphs(
+    updates: &[TranscriptUpdate],
+    max_gap_secs: f32,
+    max_paragraph_sentences: usize,
+) -> Vec<TranscriptParagraph> {
+    let mut paragraphs: Vec<TranscriptParagraph> = Vec::new();
+
+    for update in updates {
+        let range = parse_timestamp_range(&update.timestamp);
+        let speaker = speaker_of(update);
+
+        let starts_new = match paragraphs.last() {
+            None => true,
+            Some(current) => {
+                let long_gap = range.map_or(false, |(t0, _)| t0 - current.end_time > max_gap_secs);
+                long_gap
+                    || current.sentences.len() >= max_paragraph_sentences.max(1)
+                    || current.speaker != speaker
+            }
+        };
+
+        if starts_new {
+            let (start, end) = range.unwrap_or_else(|| {
+                let last_end = paragraphs.last().map_or(0.0, |p| p.end_time);
+                (last_end, last_end)
+            });
+            paragraphs.push(TranscriptParagraph {
+                start_time: start,
+                end_time: end,
+                sentences: Vec::new(),
+                speaker,
+            });
+        }
+
+        let current = paragraphs.last_mut().expect("paragraph was just pushed");
+        if let Some((_, t1)) = range {
+            current.end_time = current.end_time.max(t1);
+        }
+        current.sentences.push(update.clone());
+    }
+
+    paragraphs
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AggregatedTranscript {
+    pub combined_text: String,
+    pub start_time: f32,
+    pub end_time: f32,
+    pub sentence_count: usize,
+}
+
+/// Merges runs of sentences separated by less than `max_gap_secs` into single entries of at
+/// most `max_words_per_aggregate` words. A sentence longer than the limit stands alone.
+pub fn aggregate_transcript_updates(
+    updates: &[TranscriptUpdate],
+    max_gap_secs: f32,
+    max_words_per_aggregate: usize,
+) -> Vec<AggregatedTranscript> {
+    let mut aggregates: Vec<AggregatedTranscript> = Vec::new();
+    let mut current_words = 0;
+
+    for update in updates {
+        let text = update.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let words = text.split_whitespace().count();
+        let range = parse_timestamp_range(&update.timestamp);
+
+        let joins_current = aggregates.last().map_or(false, |current| {
+            let short_gap = range.map_or(true, |(t0, _)| t0 - current.end_time < max_gap_secs);
+            short_gap && current_words + words <= max_words_per_aggregate
+        });
+
+        if joins_current {
+            let current = aggregates.last_mut().expect("checked above");
+            current.combined_text.push(' ');
+            current.combined_text.push_str(text);
+            if let Some((_, t1)) = range {
+                current.end_time = current.end_time.max(t1);
+            }
+            current.sentence_count += 1;
+            current_words += words;
+        } else {
+            let (start, end) = range.unwrap_or_else(|| {
+                let last_end = aggregates.last().map_or(0.0, |a| a.end_time);
+                (last_end, last_end)
+            });
+            aggregates.push(AggregatedTranscript {
+                combined_text: text.to_string(),
+                start_time: start,
+                end_time: end,
+                sentence_count: 1,
+            });
+            current_words = words;
+        }
+    }
+
+    aggregates
+}
@@ -0,0 +1,207 @@
+// Parsers for transcripts exported by other meeting tools
+use anyhow::{anyhow, Context};
+use std::path::Path;
+
+use crate::TranscriptUpdate;
+
+// Exports don't record when the last line ends, so give it a nominal length
+const LAST_ENTRY_SECS: f32 = 5.0;
+
+struct ImportedEntry {
+    start_secs: f32,
+    speaker: String,
+    text: String,
+}
+
+// "HH:MM:SS" or "H:MM:SS" or "MM:SS"
+fn parse_clock(value: &str) -> Option<f32> {
+    let parts: Vec<f32> = value
+        .trim()
+        .split(':')
+        .map(|part| part.parse::<f32>().ok())
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [h, m, s] => Some(h * 3600.0 + m * 60.0 + s),
+        [m, s] => Some(m * 60.0 + s),
+        _ => None,
+    }
+}
+
+// Each entry ends where the next one starts
+fn into_updates(entries: Vec<ImportedEntry>) -> Vec<TranscriptUpdate> {
+    let starts: Vec<f32> = entries.iter().map(|e| e.start_secs).collect();
+    entries
+        .into_iter()
+        .enumerate()
+        .filter(|(_, entry)| !entry.text.trim().is_empty())
+        .map(|(i, entry)| {
+            let end = starts[i + 1..]
+                .iter()
+                .copied()
+                .find(|start| *start > entry.start_secs)
+                .unwrap_or(entry.start_secs + LAST_ENTRY_SECS);
+            TranscriptUpdate {
+                text: entry.text.trim().to_string(),
+                timestamp: format!("{:.1} - {:.1}", entry.start_secs, end),
+                source: entry.speaker,
+                speaker_id: None,
+            }
+        })
+        .collect()
+}
+
+/// Reads the text of every paragraph in a DOCX document, in order.
+pub fn read_docx_paragraphs(path: &Path) -> anyhow::Result<Vec<String>> {
+    let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let docx = docx_rs::read_docx(&bytes).map_err(|e| anyhow!("Failed to parse DOCX: {}", e))?;
+
+    let mut paragraphs = Vec::new();
+    for child in docx.document.children {
+        let docx_rs::DocumentChild::Paragraph(paragraph) = child else {
+            continue;
+        };
+        let mut text = String::new();
+        for child in &paragraph.children {
+            let docx_rs::ParagraphChild::Run(run) = child else {
+                continue;
+            };
+            for child in &run.children {
+                match child {
+                    docx_rs::RunChild::Text(t) => text.push_str(&t.text),
+                    docx_rs::RunChild::Break(_) => text.push('\n'),
+                    docx_rs::RunChild::Tab(_) => text.push('\t'),
+                    _ => {}
+                }
+            }
+        }
+        paragraphs.push(text);
+    }
+    Ok(paragraphs)
+}
+
+/// Parses a Teams transcript: a `[HH:MM:SS] Speaker Name:` line followed by what they said.
+/// Lines before the first speaker line (title, attendees) are ignored.
+pub fn parse_teams_transcript(paragraphs: &[String]) -> Vec<TranscriptUpdate> {
+    let mut entries: Vec<ImportedEntry> = Vec::new();
+    for line in paragraphs.iter().flat_map(|p| p.lines()).map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        let header = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+            .and_then(|(clock, rest)| Some((parse_clock(clock)?, rest.trim())));
+        match header {
+            Some((start_secs, rest)) => {
+                // Some exports put the first words on the header line after the colon
+                let (speaker, text) = rest.split_once(':').unwrap_or((rest, ""));
+                entries.push(ImportedEntry {
+                    start_secs,
+                    speaker: speaker.trim().to_string(),
+                    text: text.trim().to_string(),
+                });
+            }
+            None => {
+                if let Some(entry) = entries.last_mut() {
+                    if !entry.text.is_empty() {
+                        entry.text.push(' ');
+                    }
+                    entry.text.push_str(line);
+                }
+            }
+        }
+    }
+    into_updates(entries)
+}
+
+/// Parses a Google Meet transcript: bare `HH:MM:SS` lines mark time, and each
+/// `Speaker Name: text` line after one starts at that time.
+pub fn parse_google_meet_transcript(contents: &str) -> Vec<TranscriptUpdate> {
+    let mut entries: Vec<ImportedEntry> = Vec::new();
+    let mut current_secs: Option<f32> = None;
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(secs) = parse_clock(line) {
+            current_secs = Some(secs);
+            continue;
+        }
+        // The title and "Transcript" header come before the first timestamp
+        let Some(start_secs) = current_secs else {
+            continue;
+        };
+        match line.split_once(':').filter(|(speaker, _)| !speaker.trim().is_empty() && speaker.len() <= 64) {
+            Some((speaker, text)) => entries.push(ImportedEntry {
+                start_secs,
+                speaker: speaker.trim().to_string(),
+                text: text.trim().to_string(),
+            }),
+            None => {
+                if let Some(entry) = entries.last_mut() {
+                    entry.text.push(' ');
+                    entry.text.push_str(line);
+                }
+            }
+        }
+    }
+    into_updates(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEAMS_SAMPLE: &str = include_str!("../../tests/fixtures/import/teams_transcript.txt");
+    const GOOGLE_MEET_SAMPLE: &str = include_str!("../../tests/fixtures/import/google_meet_transcript.txt");
+
+    fn summary(updates: &[TranscriptUpdate]) -> Vec<(&str, &str, &str)> {
+        updates
+            .iter()
+            .map(|update| (update.timestamp.as_str(), update.source.as_str(), update.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_teams_sample() {
+        let paragraphs: Vec<String> = TEAMS_SAMPLE.lines().map(str::to_string).collect();
+        let updates = parse_teams_transcript(&paragraphs);
+        assert_eq!(
+            summary(&updates),
+            [
+                ("3.0 - 9.0", "Alice Smith", "Morning everyone, let's get started."),
+                ("9.0 - 75.0", "Bob Jones", "Sure. I'll go first: the release branch is cut and QA starts tomorrow."),
+                ("75.0 - 3750.0", "Alice Smith", "Great."),
+                ("3750.0 - 3755.0", "Bob Jones", "That's all from me."),
+            ]
+        );
+    }
+
+    #[test]
+    fn teams_paragraphs_may_hold_several_lines() {
+        // DOCX exports sometimes keep a speaker's header and text in one paragraph with a line break
+        let paragraphs = vec!["[00:00:01] Alice Smith:\nHello.".to_string(), "[00:00:04] Bob Jones:\nHi.".to_string()];
+        let updates = parse_teams_transcript(&paragraphs);
+        assert_eq!(summary(&updates), [("1.0 - 4.0", "Alice Smith", "Hello."), ("4.0 - 9.0", "Bob Jones", "Hi.")]);
+    }
+
+    #[test]
+    fn parses_google_meet_sample() {
+        let updates = parse_google_meet_transcript(GOOGLE_MEET_SAMPLE);
+        assert_eq!(
+            summary(&updates),
+            [
+                ("0.0 - 42.0", "Alice Smith", "Hi both, thanks for joining."),
+                ("0.0 - 42.0", "Bob Jones", "Hey Alice."),
+                ("42.0 - 65.0", "Alice Smith", "First item: the budget. It is still pending approval."),
+                ("65.0 - 70.0", "Bob Jones", "Agreed."),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_text_without_a_recognised_header() {
+        assert!(parse_teams_transcript(&["Just some notes".to_string()]).is_empty());
+        assert!(parse_google_meet_transcript("Weekly sync\nAlice: no timestamp yet").is_empty());
+    }
+}
@@ -0,0 +1,14 @@
+pub mod chunking;
+pub mod coverage;
+pub mod deduplication;
+pub mod formatting;
+pub mod import;
+pub mod languages;
+pub mod processing;
+
+pub use chunking::{chunk_transcript_for_llm, TranscriptChunk};
+pub use coverage::{compute_coverage, TimeGap, TranscriptCoverage};
+pub use deduplication::{DedupConfig, TranscriptDeduplicator};
+pub use formatting::{aggregate_transcript_updates, group_into_paragraphs, AggregatedTranscript, TranscriptParagraph};
+pub use languages::LanguageInfo;
+pub use processing::{apply_post_processing_pipeline, PostProcessingPipeline, PostProcessingStep};
@@ -0,0 +1,103 @@
+// User-configured cleanup applied to each transcript segment as it arrives
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+// Mirrored from the "transcriptPostProcessing" store key so the accumulator doesn't need an AppHandle
+static CURRENT_PIPELINE: Lazy<RwLock<Option<PostProcessingPipeline>>> = Lazy::new(|| RwLock::new(None));
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PostProcessingPipeline {
+    pub steps: Vec<PostProcessingStep>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum PostProcessingStep {
+    RemoveFillerWords { words: Vec<String> },
+    ExpandAbbreviations { map: HashMap<String, String> },
+    CapitalizeProperNouns { nouns: Vec<String> },
+    RegexReplace { pattern: String, replacement: String },
+}
+
+pub fn current_pipeline() -> Option<PostProcessingPipeline> {
+    CURRENT_PIPELINE.read().ok().and_then(|pipeline| pipeline.clone())
+}
+
+pub fn set_current_pipeline(pipeline: Option<PostProcessingPipeline>) {
+    if let Ok(mut guard) = CURRENT_PIPELINE.write() {
+        *guard = pipeline;
+    }
+}
+
+fn whole_word(word: &str, case_insensitive: bool) -> Result<Regex> {
+    RegexBuilder::new(&format!(r"\b{}\b", regex::escape(word.trim())))
+        .case_insensitive(case_insensitive)
+        .build()
+        .with_context(|| format!("Invalid word '{}'", word))
+}
+
+// Collapses the double spaces and stray ", ," left behind by removals
+fn tidy_whitespace(text: &str) -> String {
+    static SPACE_BEFORE_PUNCT: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+([,.?!])").unwrap());
+    static REPEATED_COMMA: Lazy<Regex> = Lazy::new(|| Regex::new(r",(\s*,)+").unwrap());
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let collapsed = SPACE_BEFORE_PUNCT.replace_all(&collapsed, "$1");
+    let collapsed = REPEATED_COMMA.replace_all(&collapsed, ",");
+    collapsed.trim_start_matches([',', ' ']).to_string()
+}
+
+fn apply_step(text: &str, step: &PostProcessingStep) -> Result<String> {
+    Ok(match step {
+        PostProcessingStep::RemoveFillerWords { words } => {
+            let mut text = text.to_string();
+            for word in words.iter().filter(|w| !w.trim().is_empty()) {
+                // Take a following comma with the filler ("um, so" -> "so")
+                let filler = RegexBuilder::new(&format!(r"\b{}\b,?", regex::escape(word.trim())))
+                    .case_insensitive(true)
+                    .build()
+                    .with_context(|| format!("Invalid filler word '{}'", word))?;
+                text = filler.replace_all(&text, "").into_owned();
+            }
+            tidy_whitespace(&text)
+        }
+        PostProcessingStep::ExpandAbbreviations { map } => {
+            let mut text = text.to_string();
+            for (abbreviation, expansion) in map.iter().filter(|(a, _)| !a.trim().is_empty()) {
+                text = whole_word(abbreviation, false)?
+                    .replace_all(&text, NoExpand(expansion))
+                    .into_owned();
+            }
+            text
+        }
+        PostProcessingStep::CapitalizeProperNouns { nouns } => {
+            let mut text = text.to_string();
+            for noun in nouns.iter().filter(|n| !n.trim().is_empty()) {
+                text = whole_word(noun, true)?
+                    .replace_all(&text, NoExpand(noun.trim()))
+                    .into_owned();
+            }
+            text
+        }
+        PostProcessingStep::RegexReplace { pattern, replacement } => Regex::new(pattern)
+            .with_context(|| format!("Invalid pattern '{}'", pattern))?
+            .replace_all(text, replacement.as_str())
+            .into_owned(),
+    })
+}
+
+/// Runs every step in order; fails on the first step with an invalid pattern.
+pub fn apply_post_processing_pipeline(text: &str, pipeline: &PostProcessingPipeline) -> Result<String> {
+    pipeline
+        .steps
+        .iter()
+        .try_fold(text.to_string(), |text, step| apply_step(&text, step))
+}
+
+/// Checks every pattern compiles so errors surface when the pipeline is saved, not mid-recording.
+pub fn validate_pipeline(pipeline: &PostProcessingPipeline) -> Result<()> {
+    apply_post_processing_pipeline("", pipeline).map(|_| ())
+}
@@ -0,0 +1,137 @@
+// SQLite store of the raw segments behind each transcript, so a meeting can be re-processed without re-recording
+use anyhow::{anyhow, Context, Result};
+use log::{error as log_error, info as log_info};
+use once_cell::sync::OnceCell;
+use rusqlite::{params, Connection};
+use std::fs;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::{diarization, store, TranscriptSegment};
+
+const DB_FILE: &str = "transcript_segments.db";
+
+// Set by start_recording; segments are keyed by session until the transcript is saved as a meeting
+pub const PENDING_SEGMENTS_KEY: &str = "pendingSegmentsSessionId";
+
+static DB: OnceCell<Mutex<Connection>> = OnceCell::new();
+
+pub fn init<R: Runtime>(app: &AppHandle<R>) -> Result<()> {
+    let data_dir = app.path().app_data_dir().context("Failed to resolve app data directory")?;
+    fs::create_dir_all(&data_dir).with_context(|| format!("Failed to create {}", data_dir.display()))?;
+
+    let path = data_dir.join(DB_FILE);
+    let conn = Connection::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS transcript_segments (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             meeting_id TEXT NOT NULL,
+             t0 REAL NOT NULL,
+             t1 REAL NOT NULL,
+             text TEXT NOT NULL,
+             source TEXT NOT NULL,
+             created_at TEXT NOT NULL,
+             speaker_id TEXT
+         );
+         CREATE INDEX IF NOT EXISTS transcript_segments_meeting ON transcript_segments (meeting_id);",
+    )?;
+    // Stores created before speakers were recorded
+    let has_speaker: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('transcript_segments') WHERE name = 'speaker_id'",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_speaker {
+        conn.execute_batch("ALTER TABLE transcript_segments ADD COLUMN speaker_id TEXT;")?;
+    }
+    DB.set(Mutex::new(conn)).map_err(|_| anyhow!("Transcript segment store already initialized"))?;
+    log_info!("Transcript segment store opened at {}", path.display());
+    Ok(())
+}
+
+fn with_db<T>(f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Result<T> {
+    let db = DB.get().context("Transcript segment store is not initialized")?;
+    let conn = db.lock().map_err(|_| anyhow!("Transcript segment store poisoned"))?;
+    Ok(f(&conn)?)
+}
+
+pub fn insert(meeting_id: &str, segment: &TranscriptSegment, source: &str) -> Result<()> {
+    with_db(|conn| {
+        conn.execute(
+            "INSERT INTO transcript_segments (meeting_id, t0, t1, text, source, created_at, speaker_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                meeting_id,
+                segment.t0,
+                segment.t1,
+                segment.text,
+                source,
+                chrono::Utc::now().to_rfc3339(),
+                segment.speaker_id
+            ],
+        )
+    })?;
+    Ok(())
+}
+
+/// Segments for `meeting_id` in the order they were transcribed.
+pub fn load(meeting_id: &str) -> Result<Vec<TranscriptSegment>> {
+    with_db(|conn| {
+        let mut statement =
+            conn.prepare("SELECT t0, t1, text, speaker_id FROM transcript_segments WHERE meeting_id = ?1 ORDER BY id")?;
+        let rows = statement.query_map(params![meeting_id], |row| {
+            Ok(TranscriptSegment { t0: row.get(0)?, t1: row.get(1)?, text: row.get(2)?, speaker_id: row.get(3)? })
+        })?;
+        rows.collect()
+    })
+}
+
+pub fn delete(meeting_id: &str) -> Result<usize> {
+    with_db(|conn| conn.execute("DELETE FROM transcript_segments WHERE meeting_id = ?1", params![meeting_id]))
+}
+
+/// Moves the segments recorded under `from` (a session id) to `to` (the meeting id).
+pub fn reassign(from: &str, to: &str) -> Result<usize> {
+    with_db(|conn| {
+        conn.execute("UPDATE transcript_segments SET meeting_id = ?2 WHERE meeting_id = ?1", params![from, to])
+    })
+}
+
+/// Relabels `meeting_id`'s segments attributed to `old_speaker`.
+pub fn rename_speaker(meeting_id: &str, old_speaker: &str, new_speaker: &str) -> Result<usize> {
+    with_db(|conn| {
+        conn.execute(
+            "UPDATE transcript_segments SET speaker_id = ?3 WHERE meeting_id = ?1 AND speaker_id = ?2",
+            params![meeting_id, old_speaker, new_speaker],
+        )
+    })
+}
+
+/// Files the pending recording's segments and speaker names under `meeting_id`, if a recording is waiting for one.
+pub fn claim_pending<R: Runtime>(app: &AppHandle<R>, meeting_id: &str) {
+    let Some(session_id) = store::get_value::<String>(app, PENDING_SEGMENTS_KEY) else {
+        return;
+    };
+    match reassign(&session_id, meeting_id) {
+        Ok(moved) => log_info!("Filed {} raw segments from session {} under meeting {}", moved, session_id, meeting_id),
+        Err(e) => log_error!("Failed to file raw segments under meeting {}: {}", meeting_id, e),
+    }
+    if let Err(e) = diarization::reassign_speaker_names(app, &session_id, meeting_id) {
+        log_error!("Failed to file speaker names under meeting {}: {}", meeting_id, e);
+    }
+    if let Err(e) = store::delete_value(app, PENDING_SEGMENTS_KEY) {
+        log_error!("Failed to clear pending segments session: {}", e);
+    }
+}
+
+#[tauri::command]
+pub fn get_raw_segments(meeting_id: String) -> Result<Vec<TranscriptSegment>, String> {
+    load(&meeting_id).map_err(|e| format!("Failed to load raw segments: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_raw_segments(meeting_id: String) -> Result<(), String> {
+    let removed = delete(&meeting_id).map_err(|e| format!("Failed to delete raw segments: {}", e))?;
+    log_info!("Deleted {} raw segments for meeting {}", removed, meeting_id);
+    Ok(())
+}
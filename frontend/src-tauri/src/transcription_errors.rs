@@ -0,0 +1,81 @@
+// Recent transcription failures, kept across restarts so intermittent connectivity problems can be diagnosed
+use log::{error as log_error, info as log_info};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{AppHandle, Runtime};
+
+use crate::store;
+
+const HISTORY_KEY: &str = "transcriptionErrorHistory";
+const MAX_HISTORY: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionErrorRecord {
+    pub timestamp: String,
+    pub error_message: String,
+    pub chunk_index: u32,
+    pub stream_url: String,
+    pub recovery_action: String,
+}
+
+static ERROR_HISTORY: Lazy<Mutex<VecDeque<TranscriptionErrorRecord>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(MAX_HISTORY)));
+
+/// Appends a failure, dropping the oldest once the history is full.
+pub fn record(chunk_index: u32, error_message: &str, stream_url: String, recovery_action: &str) {
+    let entry = TranscriptionErrorRecord {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        error_message: error_message.to_string(),
+        chunk_index,
+        stream_url,
+        recovery_action: recovery_action.to_string(),
+    };
+    if let Ok(mut history) = ERROR_HISTORY.lock() {
+        while history.len() >= MAX_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(entry);
+    }
+}
+
+// Called once at startup
+pub fn load<R: Runtime>(app: &AppHandle<R>) {
+    let Some(saved) = store::get_value::<Vec<TranscriptionErrorRecord>>(app, HISTORY_KEY) else {
+        return;
+    };
+    if let Ok(mut history) = ERROR_HISTORY.lock() {
+        let skip = saved.len().saturating_sub(MAX_HISTORY);
+        *history = saved.into_iter().skip(skip).collect();
+        log_info!("Restored {} transcription errors from the last session", history.len());
+    }
+}
+
+// Called when a recording stops; errors are only written to disk then to keep the hot path cheap
+pub fn persist<R: Runtime>(app: &AppHandle<R>) {
+    let history: Vec<TranscriptionErrorRecord> = match ERROR_HISTORY.lock() {
+        Ok(history) => history.iter().cloned().collect(),
+        Err(_) => return,
+    };
+    if let Err(e) = store::set_value(app, HISTORY_KEY, &history) {
+        log_error!("Failed to save transcription error history: {}", e);
+    }
+}
+
+#[tauri::command]
+pub fn get_transcription_error_history() -> Vec<TranscriptionErrorRecord> {
+    ERROR_HISTORY
+        .lock()
+        .map(|history| history.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn clear_transcription_error_history<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    ERROR_HISTORY
+        .lock()
+        .map_err(|_| "Transcription error history is poisoned".to_string())?
+        .clear();
+    store::delete_value(&app, HISTORY_KEY)
+}
@@ -0,0 +1,140 @@
+// Switches live transcription to a backup server after repeated failures on the primary
+use log::{info as log_info, warn as log_warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::store;
+
+const CONFIG_KEY: &str = "backupTranscriptionConfig";
+pub const SWITCHED_TO_BACKUP_EVENT: &str = "switched-to-backup-transcription";
+pub const SWITCHED_TO_PRIMARY_EVENT: &str = "switched-to-primary-transcription";
+
+static FAILOVER: Lazy<Mutex<FailoverState>> = Lazy::new(|| Mutex::new(FailoverState::default()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupTranscriptionConfig {
+    pub enabled: bool,
+    pub url: String,
+    pub trigger_after_consecutive_failures: u32,
+}
+
+#[derive(Debug, Default)]
+struct FailoverState {
+    config: Option<BackupTranscriptionConfig>,
+    consecutive_failures: u32,
+    using_backup: bool,
+    // Event for the next caller with an AppHandle to emit; send_audio_chunk has none
+    pending_event: Option<&'static str>,
+}
+
+impl FailoverState {
+    fn backup_url(&self) -> Option<String> {
+        self.config
+            .as_ref()
+            .filter(|config| config.enabled && !config.url.is_empty())
+            .map(|config| config.url.clone())
+    }
+}
+
+fn with_state<T>(f: impl FnOnce(&mut FailoverState) -> T) -> Option<T> {
+    FAILOVER.lock().ok().map(|mut state| f(&mut state))
+}
+
+pub fn load<R: Runtime>(app: &AppHandle<R>) {
+    let config = store::get_value::<BackupTranscriptionConfig>(app, CONFIG_KEY);
+    with_state(|state| {
+        state.config = config;
+        state.consecutive_failures = 0;
+        state.using_backup = false;
+        state.pending_event = None;
+    });
+}
+
+/// The backup server URL while failed over, otherwise None.
+pub fn active_backup_url() -> Option<String> {
+    with_state(|state| if state.using_backup { state.backup_url() } else { None }).flatten()
+}
+
+pub fn record_primary_success() {
+    with_state(|state| {
+        state.consecutive_failures = 0;
+        if state.using_backup {
+            log_info!("Primary transcription server recovered, switching back");
+            state.using_backup = false;
+            state.pending_event = Some(SWITCHED_TO_PRIMARY_EVENT);
+        }
+    });
+}
+
+/// Counts a chunk that failed every retry on the primary; returns the backup URL
+/// if this failure is the one that triggers the switch.
+pub fn record_primary_failure() -> Option<String> {
+    with_state(|state| {
+        state.consecutive_failures += 1;
+        let threshold = state.config.as_ref()?.trigger_after_consecutive_failures.max(1);
+        let url = state.backup_url()?;
+        if state.using_backup || state.consecutive_failures < threshold {
+            return None;
+        }
+        log_warn!(
+            "Primary transcription server failed {} chunks in a row, switching to backup {}",
+            state.consecutive_failures, url
+        );
+        state.using_backup = true;
+        state.pending_event = Some(SWITCHED_TO_BACKUP_EVENT);
+        Some(url)
+    })
+    .flatten()
+}
+
+pub fn emit_pending_event<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(event) = with_state(|state| state.pending_event.take()).flatten() {
+        if let Err(e) = app.emit(event, ()) {
+            log_warn!("Failed to emit {}: {}", event, e);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn set_backup_transcription_config<R: Runtime>(app: AppHandle<R>, config: BackupTranscriptionConfig) -> Result<(), String> {
+    let url = config.url.trim().trim_end_matches('/').to_string();
+    if config.enabled {
+        reqwest::Url::parse(&url).map_err(|e| format!("Invalid backup transcription URL {}: {}", url, e))?;
+    }
+    let config = BackupTranscriptionConfig { url, ..config };
+    store::set_value(&app, CONFIG_KEY, &config)?;
+    log_info!("Backup transcription {} ({})", if config.enabled { "enabled" } else { "disabled" }, config.url);
+    with_state(|state| {
+        if !config.enabled {
+            state.using_backup = false;
+        }
+        state.config = Some(config);
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_backup_transcription_config<R: Runtime>(app: AppHandle<R>) -> Option<BackupTranscriptionConfig> {
+    store::get_value::<BackupTranscriptionConfig>(&app, CONFIG_KEY)
+}
+
+// Any HTTP response counts as reachable; the stream endpoint only accepts audio POSTs
+#[tauri::command]
+pub async fn test_backup_transcription_server<R: Runtime>(app: AppHandle<R>) -> Result<bool, String> {
+    let config = store::get_value::<BackupTranscriptionConfig>(&app, CONFIG_KEY)
+        .filter(|config| !config.url.is_empty())
+        .ok_or_else(|| "No backup transcription server configured".to_string())?;
+    let response = reqwest::Client::new()
+        .get(&config.url)
+        .timeout(Duration::from_secs(3))
+        .send()
+        .await;
+    match response {
+        Ok(response) => Ok(!response.status().is_server_error()),
+        Err(e) if e.is_timeout() || e.is_connect() => Ok(false),
+        Err(e) => Err(format!("Failed to reach backup transcription server: {}", e)),
+    }
+}
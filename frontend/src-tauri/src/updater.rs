@@ -0,0 +1,148 @@
+// Checks GitHub releases for a newer version of the app
+use log::{info as log_info, warn as log_warn};
+use once_cell::sync::Lazy;
+use reqwest::{header, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Runtime};
+
+use crate::{http_client, store};
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/sobiesie/meeting-minutes/releases/latest";
+const UPDATE_INFO_KEY: &str = "updateInfo";
+const UPDATE_ETAG_KEY: &str = "updateEtag";
+
+// Mirrored from the "updateInfo" store key so the last result is available without an AppHandle
+static CACHED_UPDATE: Lazy<Mutex<Option<UpdateInfo>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+    pub release_notes: String,
+    pub download_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+// Installer extensions in order of preference for the running platform
+fn platform_extensions() -> &'static [&'static str] {
+    if cfg!(target_os = "macos") {
+        &[".dmg"]
+    } else if cfg!(target_os = "windows") {
+        &[".msi", ".exe"]
+    } else {
+        &[".AppImage", ".deb"]
+    }
+}
+
+fn download_url(release: &GithubRelease) -> String {
+    platform_extensions()
+        .iter()
+        .find_map(|ext| release.assets.iter().find(|asset| asset.name.ends_with(ext)))
+        .map(|asset| asset.browser_download_url.clone())
+        .unwrap_or_else(|| release.html_url.clone())
+}
+
+fn parse_version(version: &str) -> Result<semver::Version, String> {
+    let trimmed = version.trim().trim_start_matches('v');
+    semver::Version::parse(trimmed).map_err(|e| format!("Invalid version '{}': {}", version, e))
+}
+
+fn update_info(release: &GithubRelease) -> Result<UpdateInfo, String> {
+    let current = parse_version(env!("CARGO_PKG_VERSION"))?;
+    let latest = parse_version(&release.tag_name)?;
+    Ok(UpdateInfo {
+        current_version: current.to_string(),
+        latest_version: latest.to_string(),
+        update_available: latest > current,
+        release_notes: release.body.clone().unwrap_or_default(),
+        download_url: download_url(release),
+    })
+}
+
+fn set_cached(info: Option<UpdateInfo>) {
+    if let Ok(mut guard) = CACHED_UPDATE.lock() {
+        *guard = info;
+    }
+}
+
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    set_cached(store::get_value::<UpdateInfo>(app, UPDATE_INFO_KEY));
+}
+
+#[tauri::command]
+pub async fn check_for_updates<R: Runtime>(app: AppHandle<R>) -> Result<UpdateInfo, String> {
+    let cached = get_cached_update_info();
+    let mut request = http_client::get_client()
+        .get(LATEST_RELEASE_URL)
+        .header(header::ACCEPT, "application/vnd.github+json");
+    // Only send the ETag when there is a cached result to fall back on for 304
+    if let (Some(_), Some(etag)) = (&cached, store::get_value::<String>(&app, UPDATE_ETAG_KEY)) {
+        request = request.header(header::IF_NONE_MATCH, etag);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        if let Some(info) = cached {
+            log_info!("Latest release unchanged since last check ({})", info.latest_version);
+            return Ok(info);
+        }
+    }
+    if response.status() == StatusCode::FORBIDDEN || response.status() == StatusCode::TOO_MANY_REQUESTS {
+        log_warn!("GitHub rate limit reached while checking for updates");
+        return cached.ok_or_else(|| "GitHub rate limit reached; try again later".to_string());
+    }
+    if !response.status().is_success() {
+        return Err(format!("Update check failed with HTTP {}", response.status()));
+    }
+
+    let etag = response
+        .headers()
+        .get(header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let release: GithubRelease = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse release information: {}", e))?;
+    let info = update_info(&release)?;
+
+    store::set_value(&app, UPDATE_INFO_KEY, &info)?;
+    match etag {
+        Some(etag) => store::set_value(&app, UPDATE_ETAG_KEY, &etag)?,
+        None => store::delete_value(&app, UPDATE_ETAG_KEY)?,
+    }
+    set_cached(Some(info.clone()));
+    log_info!(
+        "Update check: current {}, latest {}, update available: {}",
+        info.current_version,
+        info.latest_version,
+        info.update_available
+    );
+    Ok(info)
+}
+
+#[tauri::command]
+pub fn get_cached_update_info() -> Option<UpdateInfo> {
+    CACHED_UPDATE.lock().ok().and_then(|info| info.clone())
+}
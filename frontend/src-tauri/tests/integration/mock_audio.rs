@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use app_lib::audio::{AudioDevice, AudioStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+// Matches the 100 ms callbacks a real device delivers at typical buffer sizes
+const CHUNK_DURATION: Duration = Duration::from_millis(100);
+
+/// Broadcasts a WAV file's samples in a loop, the way `AudioStream` broadcasts device audio.
+pub struct MockAudioStream {
+    device: Arc<AudioDevice>,
+    sample_rate: u32,
+    transmitter: broadcast::Sender<Vec<f32>>,
+    feeder: JoinHandle<()>,
+}
+
+impl MockAudioStream {
+    pub fn from_wav(path: &str, device: AudioDevice) -> Result<Self, hound::Error> {
+        let mut reader = hound::WavReader::open(path)?;
+        let spec = reader.spec();
+        let samples: Vec<f32> = match spec.sample_format {
+            hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+            hound::SampleFormat::Int => {
+                let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+                reader
+                    .samples::<i32>()
+                    .map(|sample| sample.map(|s| s as f32 / scale))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+        // Keep the first channel; the capture pipeline works on mono
+        let mono: Vec<f32> = samples.into_iter().step_by(spec.channels.max(1) as usize).collect();
+
+        let (transmitter, _) = broadcast::channel(1000);
+        let feeder = tokio::spawn(feed_loop(transmitter.clone(), mono, spec.sample_rate));
+
+        Ok(Self { device: Arc::new(device), sample_rate: spec.sample_rate, transmitter, feeder })
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Vec<f32>> {
+        self.transmitter.subscribe()
+    }
+
+    /// An `AudioStream` backed by this mock's channel, for injection into the recording state.
+    pub fn audio_stream(&self) -> Arc<AudioStream> {
+        Arc::new(AudioStream::from_sender(self.device.clone(), self.sample_rate, self.transmitter.clone()))
+    }
+}
+
+impl Drop for MockAudioStream {
+    fn drop(&mut self) {
+        self.feeder.abort();
+    }
+}
+
+async fn feed_loop(transmitter: broadcast::Sender<Vec<f32>>, samples: Vec<f32>, sample_rate: u32) {
+    if samples.is_empty() {
+        return;
+    }
+    let chunk_len = ((sample_rate as u128 * CHUNK_DURATION.as_millis()) / 1000).max(1) as usize;
+    let mut interval = tokio::time::interval(CHUNK_DURATION);
+    for chunk in samples.chunks(chunk_len).cycle() {
+        interval.tick().await;
+        // No subscribers yet is fine; a real device keeps producing audio either way
+        let _ = transmitter.send(chunk.to_vec());
+    }
+}
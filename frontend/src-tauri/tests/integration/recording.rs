@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use app_lib::test_support;
+use hound::{SampleFormat, WavReader};
+use tauri::test::MockRuntime;
+use tauri::AppHandle;
+use tempfile::tempdir;
+
+use crate::{setup_test_recording_state, with_timeout, write_fixture_wav};
+
+#[tokio::test]
+async fn start_recording_initializes_state() {
+    with_timeout(async {
+        let dir = tempdir().unwrap();
+        let ctx = setup_test_recording_state(&write_fixture_wav(dir.path()));
+        let mut audio = ctx.mic.subscribe();
+
+        let session_id = test_support::start_recording(ctx.handle()).await.expect("start_recording failed");
+        assert!(!session_id.is_empty());
+        assert!(test_support::holds_recording_state(&ctx.handle()).await);
+        assert!(!audio.recv().await.expect("mock audio stopped").is_empty());
+
+        test_support::stop_recording(ctx.handle(), String::new()).await.unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn is_recording_returns_true_after_start() {
+    with_timeout(async {
+        let dir = tempdir().unwrap();
+        let ctx = setup_test_recording_state(&write_fixture_wav(dir.path()));
+        assert!(!test_support::is_recording());
+
+        test_support::start_recording(ctx.handle()).await.expect("start_recording failed");
+        assert!(test_support::is_recording());
+
+        test_support::stop_recording(ctx.handle(), String::new()).await.unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn stop_recording_cleans_up_state() {
+    with_timeout(async {
+        let dir = tempdir().unwrap();
+        let ctx = setup_test_recording_state(&write_fixture_wav(dir.path()));
+
+        test_support::start_recording(ctx.handle()).await.expect("start_recording failed");
+        test_support::stop_recording(ctx.handle(), String::new()).await.expect("stop_recording failed");
+
+        assert!(!test_support::is_recording());
+        assert!(!test_support::holds_recording_state(&ctx.handle()).await);
+    })
+    .await;
+}
+
+// Separate tasks on a multi-threaded runtime, so both starts really can run at the same time
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn concurrent_start_recording_returns_error() {
+    with_timeout(async {
+        let dir = tempdir().unwrap();
+        let ctx = setup_test_recording_state(&write_fixture_wav(dir.path()));
+
+        let first = tokio::spawn(test_support::start_recording(ctx.handle()));
+        let second = tokio::spawn(test_support::start_recording(ctx.handle()));
+        let (first, second) = (first.await.unwrap(), second.await.unwrap());
+        assert_eq!(
+            [first.is_ok(), second.is_ok()].iter().filter(|ok| **ok).count(),
+            1,
+            "exactly one start should win: {:?} / {:?}",
+            first,
+            second
+        );
+        assert!(test_support::is_recording());
+
+        test_support::stop_recording(ctx.handle(), String::new()).await.unwrap();
+    })
+    .await;
+}
+
+// Gives the capture loop time to move some mock audio into the recording buffers
+async fn wait_for_buffered_audio(app: &AppHandle<MockRuntime>) {
+    while test_support::buffered_mic_samples(app).await == 0 {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+#[tokio::test]
+async fn stop_recording_writes_16khz_mono_wav() {
+    with_timeout(async {
+        let dir = tempdir().unwrap();
+        let ctx = setup_test_recording_state(&write_fixture_wav(dir.path()));
+        let save_path = dir.path().join("recording.wav");
+
+        test_support::start_recording(ctx.handle()).await.expect("start_recording failed");
+        wait_for_buffered_audio(&ctx.handle()).await;
+        test_support::stop_recording_with(ctx.handle(), save_path.to_string_lossy().into_owned(), true)
+            .await
+            .expect("stop_recording failed");
+
+        let reader = WavReader::open(&save_path).expect("recording was not saved");
+        let spec = reader.spec();
+        assert_eq!(spec.channels, 1);
+        assert_eq!(spec.sample_rate, 16000);
+        assert_eq!(spec.bits_per_sample, 16);
+        assert_eq!(spec.sample_format, SampleFormat::Int);
+        assert!(reader.len() > 0);
+
+        for channel in ["recording_mic.wav", "recording_system.wav"] {
+            let spec = WavReader::open(dir.path().join(channel)).expect("raw channel was not saved").spec();
+            assert_eq!((spec.channels, spec.sample_rate), (1, 16000));
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn stop_recording_reports_unwritable_path() {
+    with_timeout(async {
+        let dir = tempdir().unwrap();
+        let fixture = write_fixture_wav(dir.path());
+        let ctx = setup_test_recording_state(&fixture);
+        // A file can't be a directory, so nothing can be created underneath it
+        let save_path = format!("{}/recording.wav", fixture);
+
+        test_support::start_recording(ctx.handle()).await.expect("start_recording failed");
+        wait_for_buffered_audio(&ctx.handle()).await;
+        let err = test_support::stop_recording(ctx.handle(), save_path).await.unwrap_err();
+
+        assert!(err.contains("Failed to save recording"), "unexpected error: {}", err);
+        assert!(!test_support::is_recording());
+    })
+    .await;
+}